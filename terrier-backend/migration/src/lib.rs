@@ -5,6 +5,24 @@ mod m20250929_221212_add_user_role;
 mod m20250930_182935_create_hackathons_table;
 mod m20250930_183801_create_user_hackathon_roles_table;
 mod m20250930_225342_remove_individual_user_role;
+mod m20251006_191204_create_api_tokens_table;
+mod m20251008_153027_create_user_identities_table;
+mod m20251009_204417_add_user_is_global_admin;
+mod m20251010_141502_add_hackathon_archived_at;
+mod m20251011_103244_add_hackathon_phases;
+mod m20251012_160815_create_hackathon_settings;
+mod m20251013_112740_create_organizations_tables;
+mod m20251014_093118_create_user_profiles_table;
+mod m20251015_142356_add_resumes;
+mod m20251016_101422_create_sponsors_tables;
+mod m20251017_153012_create_sponsor_leads;
+mod m20251018_110547_create_help_tickets;
+mod m20251019_093214_create_hardware_tables;
+mod m20251020_101507_create_reimbursements;
+mod m20251021_091845_create_distributions;
+mod m20251022_084216_create_audit_log;
+mod m20251023_091530_create_hackathon_invites;
+mod m20251024_103012_add_user_hackathon_role_created_at;
 
 pub struct Migrator;
 
@@ -17,6 +35,24 @@ impl MigratorTrait for Migrator {
             Box::new(m20250930_182935_create_hackathons_table::Migration),
             Box::new(m20250930_183801_create_user_hackathon_roles_table::Migration),
             Box::new(m20250930_225342_remove_individual_user_role::Migration),
+            Box::new(m20251006_191204_create_api_tokens_table::Migration),
+            Box::new(m20251008_153027_create_user_identities_table::Migration),
+            Box::new(m20251009_204417_add_user_is_global_admin::Migration),
+            Box::new(m20251010_141502_add_hackathon_archived_at::Migration),
+            Box::new(m20251011_103244_add_hackathon_phases::Migration),
+            Box::new(m20251012_160815_create_hackathon_settings::Migration),
+            Box::new(m20251013_112740_create_organizations_tables::Migration),
+            Box::new(m20251014_093118_create_user_profiles_table::Migration),
+            Box::new(m20251015_142356_add_resumes::Migration),
+            Box::new(m20251016_101422_create_sponsors_tables::Migration),
+            Box::new(m20251017_153012_create_sponsor_leads::Migration),
+            Box::new(m20251018_110547_create_help_tickets::Migration),
+            Box::new(m20251019_093214_create_hardware_tables::Migration),
+            Box::new(m20251020_101507_create_reimbursements::Migration),
+            Box::new(m20251021_091845_create_distributions::Migration),
+            Box::new(m20251022_084216_create_audit_log::Migration),
+            Box::new(m20251023_091530_create_hackathon_invites::Migration),
+            Box::new(m20251024_103012_add_user_hackathon_role_created_at::Migration),
         ]
     }
 }
@@ -0,0 +1,141 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserIdentities::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserIdentities::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserIdentities::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(UserIdentities::OidcIssuer)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserIdentities::OidcSub).string().not_null())
+                    .col(ColumnDef::new(UserIdentities::Email).string())
+                    .col(
+                        ColumnDef::new(UserIdentities::EmailVerified)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(UserIdentities::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(UserIdentities::LastLoginAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(UserIdentities::Table, UserIdentities::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // An identity belongs to exactly one user
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_identities_issuer_sub_unique")
+                    .table(UserIdentities::Table)
+                    .col(UserIdentities::OidcIssuer)
+                    .col(UserIdentities::OidcSub)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every existing user keeps the identity they signed up with. Emails were never
+        // checked for verification, so they cannot be used to link accounts.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO user_identities (user_id, oidc_issuer, oidc_sub, email, email_verified) \
+                 SELECT id, oidc_issuer, oidc_sub, email, false FROM users",
+            )
+            .await?;
+
+        // Subjects are only unique per issuer
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE users DROP CONSTRAINT IF EXISTS users_oidc_sub_key")
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_users_issuer_sub_unique")
+                    .table(Users::Table)
+                    .col(Users::OidcIssuer)
+                    .col(Users::OidcSub)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_issuer_sub_unique")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE users ADD CONSTRAINT users_oidc_sub_key UNIQUE (oidc_sub)",
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(UserIdentities::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserIdentities {
+    Table,
+    Id,
+    UserId,
+    OidcIssuer,
+    OidcSub,
+    Email,
+    EmailVerified,
+    CreatedAt,
+    LastLoginAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+    OidcSub,
+    OidcIssuer,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Hackathons::Table)
+                    .add_column(
+                        ColumnDef::new(Hackathons::Phase)
+                            .string()
+                            .not_null()
+                            .default("draft"),
+                    )
+                    .add_column(
+                        ColumnDef::new(Hackathons::PhaseManual)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(Hackathons::ApplicationsOpenAt).timestamp())
+                    .add_column(ColumnDef::new(Hackathons::ApplicationsCloseAt).timestamp())
+                    .add_column(ColumnDef::new(Hackathons::HackingEndAt).timestamp())
+                    .add_column(ColumnDef::new(Hackathons::JudgingStartAt).timestamp())
+                    .add_column(ColumnDef::new(Hackathons::ResultsAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Other phases are filled in from the schedule when the backend starts
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Hackathons::Table)
+                    .value(Hackathons::Phase, "archived")
+                    .and_where(Expr::col(Hackathons::ArchivedAt).is_not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Hackathons::Table)
+                    .drop_column(Hackathons::Phase)
+                    .drop_column(Hackathons::PhaseManual)
+                    .drop_column(Hackathons::ApplicationsOpenAt)
+                    .drop_column(Hackathons::ApplicationsCloseAt)
+                    .drop_column(Hackathons::HackingEndAt)
+                    .drop_column(Hackathons::JudgingStartAt)
+                    .drop_column(Hackathons::ResultsAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Phase,
+    PhaseManual,
+    ApplicationsOpenAt,
+    ApplicationsCloseAt,
+    HackingEndAt,
+    JudgingStartAt,
+    ResultsAt,
+    ArchivedAt,
+}
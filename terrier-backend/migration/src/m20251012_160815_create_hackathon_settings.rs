@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Missing settings fall back to their defaults, so existing hackathons start empty
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Hackathons::Table)
+                    .add_column(
+                        ColumnDef::new(Hackathons::Settings)
+                            .json_binary()
+                            .not_null()
+                            .default("{}"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(HackathonSettingsChanges::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HackathonSettingsChanges::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HackathonSettingsChanges::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HackathonSettingsChanges::UserId).integer())
+                    .col(
+                        ColumnDef::new(HackathonSettingsChanges::Previous)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HackathonSettingsChanges::Settings)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HackathonSettingsChanges::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                HackathonSettingsChanges::Table,
+                                HackathonSettingsChanges::HackathonId,
+                            )
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    // Keep the history when the user who made a change is deleted
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                HackathonSettingsChanges::Table,
+                                HackathonSettingsChanges::UserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hackathon_settings_changes_hackathon_id")
+                    .table(HackathonSettingsChanges::Table)
+                    .col(HackathonSettingsChanges::HackathonId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(HackathonSettingsChanges::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Hackathons::Table)
+                    .drop_column(Hackathons::Settings)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HackathonSettingsChanges {
+    Table,
+    Id,
+    HackathonId,
+    UserId,
+    Previous,
+    Settings,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+    Settings,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,184 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Organizations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Organizations::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Organizations::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Organizations::Slug)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Organizations::Description).text())
+                    .col(
+                        ColumnDef::new(Organizations::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Organizations::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrganizationAdmins::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OrganizationAdmins::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationAdmins::OrganizationId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationAdmins::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationAdmins::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                OrganizationAdmins::Table,
+                                OrganizationAdmins::OrganizationId,
+                            )
+                            .to(Organizations::Table, Organizations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(OrganizationAdmins::Table, OrganizationAdmins::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_organization_admins_unique")
+                    .table(OrganizationAdmins::Table)
+                    .col(OrganizationAdmins::OrganizationId)
+                    .col(OrganizationAdmins::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Organizations must be emptied before they can be deleted
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Hackathons::Table)
+                    .add_column(ColumnDef::new(Hackathons::OrganizationId).integer())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_hackathons_organization_id")
+                            .from_tbl(Hackathons::Table)
+                            .from_col(Hackathons::OrganizationId)
+                            .to_tbl(Organizations::Table)
+                            .to_col(Organizations::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hackathons_organization_id")
+                    .table(Hackathons::Table)
+                    .col(Hackathons::OrganizationId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Hackathons::Table)
+                    .drop_column(Hackathons::OrganizationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(OrganizationAdmins::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Organizations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Organizations {
+    Table,
+    Id,
+    Name,
+    Slug,
+    Description,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OrganizationAdmins {
+    Table,
+    Id,
+    OrganizationId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    OrganizationId,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
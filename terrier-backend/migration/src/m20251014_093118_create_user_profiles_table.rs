@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserProfiles::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserProfiles::UserId)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserProfiles::Pronouns).string())
+                    .col(ColumnDef::new(UserProfiles::Phone).string())
+                    .col(ColumnDef::new(UserProfiles::School).string())
+                    .col(ColumnDef::new(UserProfiles::Major).string())
+                    .col(ColumnDef::new(UserProfiles::GraduationYear).integer())
+                    .col(
+                        ColumnDef::new(UserProfiles::DietaryRestrictions)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(UserProfiles::DietaryNotes).text())
+                    .col(ColumnDef::new(UserProfiles::AccessibilityNeeds).text())
+                    .col(ColumnDef::new(UserProfiles::ShirtSize).string())
+                    .col(ColumnDef::new(UserProfiles::EmergencyContactName).string())
+                    .col(ColumnDef::new(UserProfiles::EmergencyContactPhone).string())
+                    .col(ColumnDef::new(UserProfiles::EmergencyContactRelationship).string())
+                    .col(ColumnDef::new(UserProfiles::Github).string())
+                    .col(ColumnDef::new(UserProfiles::Linkedin).string())
+                    .col(ColumnDef::new(UserProfiles::Website).string())
+                    .col(
+                        ColumnDef::new(UserProfiles::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(UserProfiles::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(UserProfiles::Table, UserProfiles::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserProfiles::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserProfiles {
+    Table,
+    UserId,
+    Pronouns,
+    Phone,
+    School,
+    Major,
+    GraduationYear,
+    DietaryRestrictions,
+    DietaryNotes,
+    AccessibilityNeeds,
+    ShirtSize,
+    EmergencyContactName,
+    EmergencyContactPhone,
+    EmergencyContactRelationship,
+    Github,
+    Linkedin,
+    Website,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
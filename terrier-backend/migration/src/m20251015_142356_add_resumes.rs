@@ -0,0 +1,139 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserProfiles::Table)
+                    .add_column(ColumnDef::new(UserProfiles::ResumeKey).string())
+                    .add_column(ColumnDef::new(UserProfiles::ResumeFileName).string())
+                    .add_column(ColumnDef::new(UserProfiles::ResumeUploadedAt).timestamp())
+                    // Sponsors only see resumes from participants who opted in
+                    .add_column(
+                        ColumnDef::new(UserProfiles::ResumeSponsorConsent)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ResumeAccessLogs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ResumeAccessLogs::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ResumeAccessLogs::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ResumeAccessLogs::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ResumeAccessLogs::ParticipantId).integer())
+                    .col(ColumnDef::new(ResumeAccessLogs::Action).string().not_null())
+                    .col(
+                        ColumnDef::new(ResumeAccessLogs::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ResumeAccessLogs::Table, ResumeAccessLogs::HackathonId)
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ResumeAccessLogs::Table, ResumeAccessLogs::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ResumeAccessLogs::Table, ResumeAccessLogs::ParticipantId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_resume_access_logs_hackathon_id")
+                    .table(ResumeAccessLogs::Table)
+                    .col(ResumeAccessLogs::HackathonId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ResumeAccessLogs::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserProfiles::Table)
+                    .drop_column(UserProfiles::ResumeKey)
+                    .drop_column(UserProfiles::ResumeFileName)
+                    .drop_column(UserProfiles::ResumeUploadedAt)
+                    .drop_column(UserProfiles::ResumeSponsorConsent)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserProfiles {
+    Table,
+    ResumeKey,
+    ResumeFileName,
+    ResumeUploadedAt,
+    ResumeSponsorConsent,
+}
+
+#[derive(DeriveIden)]
+enum ResumeAccessLogs {
+    Table,
+    Id,
+    HackathonId,
+    UserId,
+    ParticipantId,
+    Action,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
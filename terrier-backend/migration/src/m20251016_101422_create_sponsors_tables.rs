@@ -0,0 +1,264 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SponsorTiers::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SponsorTiers::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SponsorTiers::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SponsorTiers::Name).string().not_null())
+                    // Lower ranks are listed first, so the top tier is rank 0
+                    .col(
+                        ColumnDef::new(SponsorTiers::Rank)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(SponsorTiers::ResumeBook)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(SponsorTiers::PrizeTrack)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(SponsorTiers::Benefits).text())
+                    .col(
+                        ColumnDef::new(SponsorTiers::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SponsorTiers::Table, SponsorTiers::HackathonId)
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sponsor_tiers_hackathon_name")
+                    .table(SponsorTiers::Table)
+                    .col(SponsorTiers::HackathonId)
+                    .col(SponsorTiers::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Sponsors::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Sponsors::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Sponsors::HackathonId).integer().not_null())
+                    .col(ColumnDef::new(Sponsors::TierId).integer())
+                    .col(ColumnDef::new(Sponsors::Name).string().not_null())
+                    .col(ColumnDef::new(Sponsors::Description).text())
+                    .col(ColumnDef::new(Sponsors::Website).string())
+                    .col(ColumnDef::new(Sponsors::LogoUrl).string())
+                    .col(ColumnDef::new(Sponsors::PrizeTrackName).string())
+                    .col(ColumnDef::new(Sponsors::PrizeTrackDescription).text())
+                    .col(
+                        ColumnDef::new(Sponsors::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Sponsors::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Sponsors::Table, Sponsors::HackathonId)
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    // Sponsors stay listed without benefits when their tier is deleted
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Sponsors::Table, Sponsors::TierId)
+                            .to(SponsorTiers::Table, SponsorTiers::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sponsors_hackathon_id")
+                    .table(Sponsors::Table)
+                    .col(Sponsors::HackathonId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SponsorRepresentatives::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SponsorRepresentatives::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SponsorRepresentatives::SponsorId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SponsorRepresentatives::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SponsorRepresentatives::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                SponsorRepresentatives::Table,
+                                SponsorRepresentatives::SponsorId,
+                            )
+                            .to(Sponsors::Table, Sponsors::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                SponsorRepresentatives::Table,
+                                SponsorRepresentatives::UserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sponsor_representatives_unique")
+                    .table(SponsorRepresentatives::Table)
+                    .col(SponsorRepresentatives::SponsorId)
+                    .col(SponsorRepresentatives::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(SponsorRepresentatives::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Sponsors::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SponsorTiers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SponsorTiers {
+    Table,
+    Id,
+    HackathonId,
+    Name,
+    Rank,
+    ResumeBook,
+    PrizeTrack,
+    Benefits,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Sponsors {
+    Table,
+    Id,
+    HackathonId,
+    TierId,
+    Name,
+    Description,
+    Website,
+    LogoUrl,
+    PrizeTrackName,
+    PrizeTrackDescription,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum SponsorRepresentatives {
+    Table,
+    Id,
+    SponsorId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
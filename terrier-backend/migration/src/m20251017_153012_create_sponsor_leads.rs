@@ -0,0 +1,167 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Encoded in the QR code on a participant's badge, generated when first requested
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserHackathonRoles::Table)
+                    .add_column(
+                        ColumnDef::new(UserHackathonRoles::BadgeToken)
+                            .string()
+                            .unique_key(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserProfiles::Table)
+                    .add_column(
+                        ColumnDef::new(UserProfiles::LeadSharingConsent)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SponsorLeads::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SponsorLeads::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SponsorLeads::SponsorId).integer().not_null())
+                    .col(
+                        ColumnDef::new(SponsorLeads::ParticipantId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SponsorLeads::ScannedBy).integer())
+                    .col(ColumnDef::new(SponsorLeads::Notes).text())
+                    .col(ColumnDef::new(SponsorLeads::Rating).integer())
+                    .col(
+                        ColumnDef::new(SponsorLeads::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SponsorLeads::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SponsorLeads::Table, SponsorLeads::SponsorId)
+                            .to(Sponsors::Table, Sponsors::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SponsorLeads::Table, SponsorLeads::ParticipantId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    // Keep the lead when the representative who scanned it leaves
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SponsorLeads::Table, SponsorLeads::ScannedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Scanning the same participant again updates the existing lead
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sponsor_leads_unique")
+                    .table(SponsorLeads::Table)
+                    .col(SponsorLeads::SponsorId)
+                    .col(SponsorLeads::ParticipantId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SponsorLeads::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserProfiles::Table)
+                    .drop_column(UserProfiles::LeadSharingConsent)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserHackathonRoles::Table)
+                    .drop_column(UserHackathonRoles::BadgeToken)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserHackathonRoles {
+    Table,
+    BadgeToken,
+}
+
+#[derive(DeriveIden)]
+enum UserProfiles {
+    Table,
+    LeadSharingConsent,
+}
+
+#[derive(DeriveIden)]
+enum SponsorLeads {
+    Table,
+    Id,
+    SponsorId,
+    ParticipantId,
+    ScannedBy,
+    Notes,
+    Rating,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Sponsors {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,120 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HelpTickets::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HelpTickets::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HelpTickets::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HelpTickets::UserId).integer().not_null())
+                    .col(ColumnDef::new(HelpTickets::Topic).string().not_null())
+                    .col(ColumnDef::new(HelpTickets::Description).text())
+                    .col(ColumnDef::new(HelpTickets::Location).string().not_null())
+                    .col(
+                        ColumnDef::new(HelpTickets::Status)
+                            .string()
+                            .not_null()
+                            .default("open"),
+                    )
+                    .col(ColumnDef::new(HelpTickets::MentorId).integer())
+                    .col(ColumnDef::new(HelpTickets::ClaimedAt).timestamp())
+                    .col(ColumnDef::new(HelpTickets::ClosedAt).timestamp())
+                    .col(
+                        ColumnDef::new(HelpTickets::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(HelpTickets::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HelpTickets::Table, HelpTickets::HackathonId)
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HelpTickets::Table, HelpTickets::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    // Keep response time statistics when a mentor is deleted
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HelpTickets::Table, HelpTickets::MentorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_help_tickets_hackathon_status")
+                    .table(HelpTickets::Table)
+                    .col(HelpTickets::HackathonId)
+                    .col(HelpTickets::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HelpTickets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HelpTickets {
+    Table,
+    Id,
+    HackathonId,
+    UserId,
+    Topic,
+    Description,
+    Location,
+    Status,
+    MentorId,
+    ClaimedAt,
+    ClosedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,196 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HardwareItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HardwareItems::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HardwareItems::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HardwareItems::Name).string().not_null())
+                    .col(ColumnDef::new(HardwareItems::Description).text())
+                    // Total number owned, including units currently lent out
+                    .col(
+                        ColumnDef::new(HardwareItems::Quantity)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(HardwareItems::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(HardwareItems::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HardwareItems::Table, HardwareItems::HackathonId)
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hardware_items_hackathon_name")
+                    .table(HardwareItems::Table)
+                    .col(HardwareItems::HackathonId)
+                    .col(HardwareItems::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(HardwareLoans::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HardwareLoans::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(HardwareLoans::ItemId).integer().not_null())
+                    .col(ColumnDef::new(HardwareLoans::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(HardwareLoans::Quantity)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(HardwareLoans::Status)
+                            .string()
+                            .not_null()
+                            .default("requested"),
+                    )
+                    .col(ColumnDef::new(HardwareLoans::Notes).text())
+                    .col(ColumnDef::new(HardwareLoans::ReviewedBy).integer())
+                    .col(ColumnDef::new(HardwareLoans::DueAt).timestamp())
+                    .col(ColumnDef::new(HardwareLoans::CheckedOutAt).timestamp())
+                    .col(ColumnDef::new(HardwareLoans::ReturnedAt).timestamp())
+                    .col(
+                        ColumnDef::new(HardwareLoans::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(HardwareLoans::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HardwareLoans::Table, HardwareLoans::ItemId)
+                            .to(HardwareItems::Table, HardwareItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HardwareLoans::Table, HardwareLoans::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HardwareLoans::Table, HardwareLoans::ReviewedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hardware_loans_item_status")
+                    .table(HardwareLoans::Table)
+                    .col(HardwareLoans::ItemId)
+                    .col(HardwareLoans::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HardwareLoans::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(HardwareItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HardwareItems {
+    Table,
+    Id,
+    HackathonId,
+    Name,
+    Description,
+    Quantity,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum HardwareLoans {
+    Table,
+    Id,
+    ItemId,
+    UserId,
+    Quantity,
+    Status,
+    Notes,
+    ReviewedBy,
+    DueAt,
+    CheckedOutAt,
+    ReturnedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
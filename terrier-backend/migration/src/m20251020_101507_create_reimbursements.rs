@@ -0,0 +1,244 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Organizers who can review reimbursements and see payouts
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserHackathonRoles::Table)
+                    .add_column(
+                        ColumnDef::new(UserHackathonRoles::Finance)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReimbursementRequests::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::Region)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ReimbursementRequests::Description).text())
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::AmountRequestedCents)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ReimbursementRequests::AmountApprovedCents).big_integer())
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::Status)
+                            .string()
+                            .not_null()
+                            .default("submitted"),
+                    )
+                    .col(ColumnDef::new(ReimbursementRequests::ReviewedBy).integer())
+                    .col(ColumnDef::new(ReimbursementRequests::ReviewNotes).text())
+                    .col(ColumnDef::new(ReimbursementRequests::ReviewedAt).timestamp())
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementRequests::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                ReimbursementRequests::Table,
+                                ReimbursementRequests::HackathonId,
+                            )
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ReimbursementRequests::Table, ReimbursementRequests::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                ReimbursementRequests::Table,
+                                ReimbursementRequests::ReviewedBy,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Each participant has a single request per hackathon, so caps apply per person
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_reimbursement_requests_hackathon_user")
+                    .table(ReimbursementRequests::Table)
+                    .col(ReimbursementRequests::HackathonId)
+                    .col(ReimbursementRequests::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReimbursementReceipts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReimbursementReceipts::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementReceipts::RequestId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementReceipts::StorageKey)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementReceipts::FileName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementReceipts::ContentType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementReceipts::Size)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReimbursementReceipts::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                ReimbursementReceipts::Table,
+                                ReimbursementReceipts::RequestId,
+                            )
+                            .to(ReimbursementRequests::Table, ReimbursementRequests::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReimbursementReceipts::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ReimbursementRequests::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserHackathonRoles::Table)
+                    .drop_column(UserHackathonRoles::Finance)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ReimbursementRequests {
+    Table,
+    Id,
+    HackathonId,
+    UserId,
+    Region,
+    Description,
+    AmountRequestedCents,
+    AmountApprovedCents,
+    Status,
+    ReviewedBy,
+    ReviewNotes,
+    ReviewedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ReimbursementReceipts {
+    Table,
+    Id,
+    RequestId,
+    StorageKey,
+    FileName,
+    ContentType,
+    Size,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum UserHackathonRoles {
+    Table,
+    Finance,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
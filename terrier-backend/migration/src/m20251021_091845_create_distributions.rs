@@ -0,0 +1,178 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DistributionItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DistributionItems::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DistributionItems::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DistributionItems::Name).string().not_null())
+                    // Either meal or swag
+                    .col(ColumnDef::new(DistributionItems::Kind).string().not_null())
+                    .col(
+                        ColumnDef::new(DistributionItems::PerPersonLimit)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    // Total that can be handed out, if stock is limited
+                    .col(ColumnDef::new(DistributionItems::Quantity).integer())
+                    .col(
+                        ColumnDef::new(DistributionItems::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(DistributionItems::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DistributionItems::Table, DistributionItems::HackathonId)
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_distribution_items_hackathon_name")
+                    .table(DistributionItems::Table)
+                    .col(DistributionItems::HackathonId)
+                    .col(DistributionItems::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(DistributionClaims::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DistributionClaims::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DistributionClaims::ItemId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DistributionClaims::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DistributionClaims::ScannedBy).integer())
+                    .col(
+                        ColumnDef::new(DistributionClaims::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DistributionClaims::Table, DistributionClaims::ItemId)
+                            .to(DistributionItems::Table, DistributionItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DistributionClaims::Table, DistributionClaims::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DistributionClaims::Table, DistributionClaims::ScannedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_distribution_claims_item_user")
+                    .table(DistributionClaims::Table)
+                    .col(DistributionClaims::ItemId)
+                    .col(DistributionClaims::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DistributionClaims::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(DistributionItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DistributionItems {
+    Table,
+    Id,
+    HackathonId,
+    Name,
+    Kind,
+    PerPersonLimit,
+    Quantity,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum DistributionClaims {
+    Table,
+    Id,
+    ItemId,
+    UserId,
+    ScannedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,120 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HackathonInvites::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HackathonInvites::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HackathonInvites::HackathonId)
+                            .integer()
+                            .not_null(),
+                    )
+                    // Lowercase, and matched against verified emails when users sign in
+                    .col(ColumnDef::new(HackathonInvites::Email).string().not_null())
+                    .col(ColumnDef::new(HackathonInvites::Name).string())
+                    .col(ColumnDef::new(HackathonInvites::Role).string().not_null())
+                    // The sponsor an invited representative will belong to
+                    .col(ColumnDef::new(HackathonInvites::SponsorId).integer())
+                    .col(ColumnDef::new(HackathonInvites::InvitedBy).integer())
+                    .col(
+                        ColumnDef::new(HackathonInvites::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HackathonInvites::Table, HackathonInvites::HackathonId)
+                            .to(Hackathons::Table, Hackathons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HackathonInvites::Table, HackathonInvites::SponsorId)
+                            .to(Sponsors::Table, Sponsors::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(HackathonInvites::Table, HackathonInvites::InvitedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hackathon_invites_unique")
+                    .table(HackathonInvites::Table)
+                    .col(HackathonInvites::HackathonId)
+                    .col(HackathonInvites::Email)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hackathon_invites_email")
+                    .table(HackathonInvites::Table)
+                    .col(HackathonInvites::Email)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HackathonInvites::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HackathonInvites {
+    Table,
+    Id,
+    HackathonId,
+    Email,
+    Name,
+    Role,
+    SponsorId,
+    InvitedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hackathons {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Sponsors {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing roles are left without a time, since when they were created is unknown
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserHackathonRoles::Table)
+                    .add_column(ColumnDef::new(UserHackathonRoles::CreatedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserHackathonRoles::Table)
+                    .modify_column(
+                        ColumnDef::new(UserHackathonRoles::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserHackathonRoles::Table)
+                    .drop_column(UserHackathonRoles::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserHackathonRoles {
+    Table,
+    CreatedAt,
+}
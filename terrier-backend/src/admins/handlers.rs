@@ -0,0 +1,231 @@
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    TransactionTrait,
+    sea_query::{Expr, Func},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    audit::log::{self as audit, Entry},
+    auth::extractors::RequireGlobalAdmin,
+    entities::{prelude::*, users},
+    error::AppError,
+    validation::{Path, ValidJson, Validate, Validator},
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminInfo {
+    pub id: i32,
+    pub email: String,
+    pub name: Option<String>,
+    /// Listed in `ADMIN_EMAILS`, so admin rights cannot be revoked through the API
+    pub from_config: bool,
+}
+
+impl AdminInfo {
+    fn from_model(user: users::Model, admin_emails: &[String]) -> Self {
+        AdminInfo {
+            from_config: admin_emails.contains(&user.email.to_lowercase()),
+            id: user.id,
+            email: user.email,
+            name: user.name,
+        }
+    }
+}
+
+/// List global admins
+#[utoipa::path(
+    get,
+    path = "/admins",
+    responses(
+        (status = 200, description = "All global admins", body = Vec<AdminInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a global admin"),
+    ),
+    tag = "Admins"
+)]
+pub async fn list_admins(
+    _admin: RequireGlobalAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AdminInfo>>, AppError> {
+    let admins = Users::find()
+        .filter(users::Column::IsGlobalAdmin.eq(true))
+        .order_by_asc(users::Column::Email)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        admins
+            .into_iter()
+            .map(|user| AdminInfo::from_model(user, &state.config.admin_emails))
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct GrantAdminRequest {
+    /// Email of an existing user
+    pub email: String,
+}
+
+impl Validate for GrantAdminRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", self.email.trim());
+    }
+}
+
+/// Make a user a global admin
+#[utoipa::path(
+    post,
+    path = "/admins",
+    request_body = GrantAdminRequest,
+    responses(
+        (status = 200, description = "User is now a global admin", body = AdminInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a global admin"),
+        (status = 404, description = "No user with that email"),
+        (status = 409, description = "Several users have that email"),
+        (status = 422, description = "Not an email address"),
+    ),
+    tag = "Admins"
+)]
+pub async fn grant_admin(
+    admin: RequireGlobalAdmin,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<GrantAdminRequest>,
+) -> Result<Json<AdminInfo>, AppError> {
+    let user = find_user_by_email(&state, &req.email).await?;
+
+    let txn = state.db.begin().await?;
+    let user = set_global_admin(&txn, user, true).await?;
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: admin.user_id,
+            action: "admin.grant",
+            target_type: "user",
+            target_id: Some(user.id),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    state.user_cache.invalidate(user.id).await;
+    tracing::info!("{} granted global admin to {}", admin.email, user.email);
+
+    Ok(Json(AdminInfo::from_model(
+        user,
+        &state.config.admin_emails,
+    )))
+}
+
+/// Revoke a user's global admin rights
+#[utoipa::path(
+    delete,
+    path = "/admins/{user_id}",
+    params(
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Global admin revoked"),
+        (status = 400, description = "Admins cannot revoke themselves"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a global admin"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "User is listed in ADMIN_EMAILS"),
+    ),
+    tag = "Admins"
+)]
+pub async fn revoke_admin(
+    admin: RequireGlobalAdmin,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    // Prevents the last admin from locking everyone out
+    if user_id == admin.user_id {
+        return Err(AppError::BadRequest("Admins cannot revoke themselves"));
+    }
+
+    let user = Users::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("User not found"))?;
+
+    // They would be made an admin again on the next restart
+    if state
+        .config
+        .admin_emails
+        .contains(&user.email.to_lowercase())
+    {
+        return Err(AppError::Conflict(
+            "This admin is listed in ADMIN_EMAILS and cannot be revoked",
+        ));
+    }
+
+    let txn = state.db.begin().await?;
+    let user = set_global_admin(&txn, user, false).await?;
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: admin.user_id,
+            action: "admin.revoke",
+            target_type: "user",
+            target_id: Some(user.id),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    state.user_cache.invalidate(user.id).await;
+    tracing::info!("{} revoked global admin from {}", admin.email, user.email);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The only user with `email`
+///
+/// Emails are not unique, since separate users can sign in through providers asserting the
+/// same email, so an email shared by several users is rejected instead of picking one. Older
+/// users kept the casing their provider sent, so the stored email is compared lowercased.
+pub async fn find_user_by_email(state: &AppState, email: &str) -> Result<users::Model, AppError> {
+    let mut users = Users::find()
+        .filter(
+            Expr::expr(Func::lower(Expr::col(users::Column::Email)))
+                .eq(email.trim().to_lowercase()),
+        )
+        .limit(2)
+        .all(&state.db)
+        .await?;
+
+    if users.len() > 1 {
+        return Err(AppError::Conflict("Several users have this email"));
+    }
+
+    users
+        .pop()
+        .ok_or(AppError::NotFound("No user with this email"))
+}
+
+async fn set_global_admin(
+    db: &impl ConnectionTrait,
+    user: users::Model,
+    is_global_admin: bool,
+) -> Result<users::Model, AppError> {
+    if user.is_global_admin == is_global_admin {
+        return Ok(user);
+    }
+
+    let mut user: users::ActiveModel = user.into();
+    user.is_global_admin = Set(is_global_admin);
+    user.updated_at = Set(Utc::now().naive_utc());
+
+    Ok(user.update(db).await?)
+}
@@ -0,0 +1,41 @@
+use std::{sync::Arc, time::Duration};
+
+use moka::future::Cache;
+use sea_orm::{DatabaseConnection, DbErr};
+
+use super::handlers::{HackathonAnalytics, compute};
+
+/// How stale the dashboard can be, which keeps refreshing organizers off the database
+const TIME_TO_LIVE: Duration = Duration::from_secs(60);
+const MAX_CAPACITY: u64 = 1_000;
+
+/// Analytics by hackathon ID, computed at most once a minute however many organizers are
+/// watching the dashboard.
+#[derive(Clone)]
+pub struct AnalyticsCache(Cache<i32, Arc<HackathonAnalytics>>);
+
+impl Default for AnalyticsCache {
+    fn default() -> Self {
+        AnalyticsCache(
+            Cache::builder()
+                .max_capacity(MAX_CAPACITY)
+                .time_to_live(TIME_TO_LIVE)
+                .build(),
+        )
+    }
+}
+
+impl AnalyticsCache {
+    /// Get the cached analytics, or compute them once for every request waiting on them
+    pub async fn get(
+        &self,
+        db: &DatabaseConnection,
+        hackathon_id: i32,
+    ) -> Result<Arc<HackathonAnalytics>, Arc<DbErr>> {
+        self.0
+            .try_get_with(hackathon_id, async {
+                compute(db, hackathon_id).await.map(Arc::new)
+            })
+            .await
+    }
+}
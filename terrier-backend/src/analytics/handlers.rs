@@ -0,0 +1,320 @@
+use std::collections::BTreeMap;
+
+use axum::{Json, extract::State};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, TryGetable,
+    sea_query::{Alias, Expr, Func, Query, SelectStatement, SimpleExpr},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    auth::extractors::HackathonRole,
+    entities::{
+        distribution_claims, distribution_items, hackathon_invites, hardware_items, hardware_loans,
+        help_tickets, prelude::*, user_hackathon_roles, user_profiles,
+    },
+    error::AppError,
+    profiles::handlers::parse_dietary_restrictions,
+};
+
+/// Roles of everyone who applied, whether or not they have been accepted yet
+const APPLIED_ROLES: [&str; 2] = ["applicant", "participant"];
+
+/// Schools and majors listed, with the rest left out
+const TOP_VALUES: u64 = 25;
+
+#[derive(Clone, Serialize, ToSchema)]
+pub struct Funnel {
+    /// Applicants and participants
+    pub applied: u64,
+    /// Participants
+    pub accepted: u64,
+    /// Applicants and participants who have filled in a profile
+    pub profiles: u64,
+}
+
+#[derive(Clone, Serialize, ToSchema)]
+pub struct DailyCount {
+    pub date: NaiveDate,
+    pub count: u64,
+}
+
+#[derive(Clone, Serialize, ToSchema)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Profiles of applicants and participants
+#[derive(Clone, Serialize, ToSchema)]
+pub struct Demographics {
+    /// Most common schools first
+    pub schools: Vec<ValueCount>,
+    /// Most common majors first
+    pub majors: Vec<ValueCount>,
+    pub graduation_years: BTreeMap<i32, u64>,
+    pub shirt_sizes: BTreeMap<String, u64>,
+    pub dietary_restrictions: BTreeMap<String, u64>,
+}
+
+#[derive(Clone, Serialize, ToSchema)]
+pub struct HackathonAnalytics {
+    /// When the numbers were computed, since they are cached for up to a minute
+    pub generated_at: NaiveDateTime,
+    /// Number of people with each role
+    pub roles: BTreeMap<String, u64>,
+    pub funnel: Funnel,
+    /// Applications by the day they were made, including those already accepted, but not
+    /// those made before the days of applications were recorded
+    pub applications_by_day: Vec<DailyCount>,
+    pub demographics: Demographics,
+    /// Invites not yet redeemed by signing in
+    pub pending_invites: u64,
+    /// Claims of meals and swag by item kind
+    pub claims_by_kind: BTreeMap<String, u64>,
+    pub tickets_by_status: BTreeMap<String, u64>,
+    pub loans_by_status: BTreeMap<String, u64>,
+}
+
+/// Get statistics for the organizer dashboard
+///
+/// The numbers are cached for up to a minute, so the dashboard can be left open during the
+/// event.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/analytics",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Hackathon statistics", body = HackathonAnalytics),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_analytics(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<HackathonAnalytics>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    let analytics = state
+        .analytics_cache
+        .get(&state.db, role.hackathon_id)
+        .await
+        .map_err(|e| AppError::internal(format_args!("Database error: {e}")))?;
+
+    Ok(Json(analytics.as_ref().clone()))
+}
+
+/// Compute every statistic of a hackathon with aggregate queries
+pub async fn compute(
+    db: &DatabaseConnection,
+    hackathon_id: i32,
+) -> Result<HackathonAnalytics, DbErr> {
+    let roles: BTreeMap<String, u64> = UserHackathonRoles::find()
+        .select_only()
+        .column(user_hackathon_roles::Column::Role)
+        .column_as(Expr::col(user_hackathon_roles::Column::Id).count(), "count")
+        .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon_id))
+        .group_by(user_hackathon_roles::Column::Role)
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(role, count)| (role, count as u64))
+        .collect();
+
+    let day: SimpleExpr = Func::cust(Alias::new("DATE"))
+        .arg(Expr::col(user_hackathon_roles::Column::CreatedAt))
+        .into();
+    let applications_by_day = UserHackathonRoles::find()
+        .select_only()
+        .column_as(day.clone(), "date")
+        .column_as(Expr::col(user_hackathon_roles::Column::Id).count(), "count")
+        .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon_id))
+        .filter(user_hackathon_roles::Column::Role.is_in(APPLIED_ROLES))
+        .filter(user_hackathon_roles::Column::CreatedAt.is_not_null())
+        .group_by(day.clone())
+        .order_by_asc(day)
+        .into_tuple::<(NaiveDate, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(date, count)| DailyCount {
+            date,
+            count: count as u64,
+        })
+        .collect();
+
+    let applied = users_with_roles(hackathon_id, &APPLIED_ROLES);
+
+    let profiles = UserProfiles::find()
+        .filter(user_profiles::Column::UserId.in_subquery(applied.clone()))
+        .count(db)
+        .await?;
+
+    let funnel = Funnel {
+        applied: APPLIED_ROLES
+            .iter()
+            .filter_map(|role| roles.get(*role))
+            .sum(),
+        accepted: roles.get("participant").copied().unwrap_or(0),
+        profiles,
+    };
+
+    // Restrictions are stored together, so count each combination and split them here
+    let mut dietary_restrictions: BTreeMap<String, u64> = BTreeMap::new();
+    for (restrictions, count) in count_profiles::<String>(
+        db,
+        &applied,
+        user_profiles::Column::DietaryRestrictions,
+        None,
+    )
+    .await?
+    {
+        for restriction in parse_dietary_restrictions(&restrictions) {
+            *dietary_restrictions.entry(restriction).or_default() += count;
+        }
+    }
+
+    let demographics = Demographics {
+        schools: value_counts(
+            count_profiles(
+                db,
+                &applied,
+                user_profiles::Column::School,
+                Some(TOP_VALUES),
+            )
+            .await?,
+        ),
+        majors: value_counts(
+            count_profiles(db, &applied, user_profiles::Column::Major, Some(TOP_VALUES)).await?,
+        ),
+        graduation_years: count_profiles(db, &applied, user_profiles::Column::GraduationYear, None)
+            .await?
+            .into_iter()
+            .collect(),
+        shirt_sizes: count_profiles(db, &applied, user_profiles::Column::ShirtSize, None)
+            .await?
+            .into_iter()
+            .collect(),
+        dietary_restrictions,
+    };
+
+    let pending_invites = HackathonInvites::find()
+        .filter(hackathon_invites::Column::HackathonId.eq(hackathon_id))
+        .count(db)
+        .await?;
+
+    let claims_by_kind = DistributionClaims::find()
+        .select_only()
+        .column(distribution_items::Column::Kind)
+        .column_as(
+            Expr::col((DistributionClaims, distribution_claims::Column::Id)).count(),
+            "count",
+        )
+        .inner_join(DistributionItems)
+        .filter(distribution_items::Column::HackathonId.eq(hackathon_id))
+        .group_by(distribution_items::Column::Kind)
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(kind, count)| (kind, count as u64))
+        .collect();
+
+    let tickets_by_status = HelpTickets::find()
+        .select_only()
+        .column(help_tickets::Column::Status)
+        .column_as(Expr::col(help_tickets::Column::Id).count(), "count")
+        .filter(help_tickets::Column::HackathonId.eq(hackathon_id))
+        .group_by(help_tickets::Column::Status)
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(status, count)| (status, count as u64))
+        .collect();
+
+    let loans_by_status = HardwareLoans::find()
+        .select_only()
+        .column(hardware_loans::Column::Status)
+        .column_as(
+            Expr::col((HardwareLoans, hardware_loans::Column::Id)).count(),
+            "count",
+        )
+        .inner_join(HardwareItems)
+        .filter(hardware_items::Column::HackathonId.eq(hackathon_id))
+        .group_by(hardware_loans::Column::Status)
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(status, count)| (status, count as u64))
+        .collect();
+
+    Ok(HackathonAnalytics {
+        generated_at: Utc::now().naive_utc(),
+        roles,
+        funnel,
+        applications_by_day,
+        demographics,
+        pending_invites,
+        claims_by_kind,
+        tickets_by_status,
+        loans_by_status,
+    })
+}
+
+/// IDs of users with any of `roles` in the hackathon, to filter other tables by
+pub fn users_with_roles(hackathon_id: i32, roles: &[&str]) -> SelectStatement {
+    Query::select()
+        .column(user_hackathon_roles::Column::UserId)
+        .from(UserHackathonRoles)
+        .and_where(user_hackathon_roles::Column::HackathonId.eq(hackathon_id))
+        .and_where(user_hackathon_roles::Column::Role.is_in(roles.iter().copied()))
+        .to_owned()
+}
+
+/// Count the profiles of `users` by `column`, most common values first, leaving out those
+/// without a value
+async fn count_profiles<V: TryGetable>(
+    db: &DatabaseConnection,
+    users: &SelectStatement,
+    column: user_profiles::Column,
+    limit: Option<u64>,
+) -> Result<Vec<(V, u64)>, DbErr> {
+    let count = Expr::col(user_profiles::Column::UserId).count();
+
+    Ok(UserProfiles::find()
+        .select_only()
+        .column(column)
+        .column_as(count.clone(), "count")
+        .filter(user_profiles::Column::UserId.in_subquery(users.clone()))
+        .filter(column.is_not_null())
+        .group_by(column)
+        .order_by_desc(count)
+        .limit(limit)
+        .into_tuple::<(V, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(value, count)| (value, count as u64))
+        .collect())
+}
+
+fn value_counts(counts: Vec<(String, u64)>) -> Vec<ValueCount> {
+    counts
+        .into_iter()
+        .map(|(value, count)| ValueCount { value, count })
+        .collect()
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use axum::{Json, extract::State};
+use chrono::NaiveDateTime;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    AppState,
+    auth::extractors::RequireGlobalAdmin,
+    entities::{audit_log, hackathons, prelude::*, users},
+    error::AppError,
+    pagination::{self, Page, PageParams, Sort},
+    validation::Query,
+};
+
+const SORT: Sort<audit_log::Column> = Sort {
+    fields: &[("created_at", audit_log::Column::CreatedAt)],
+    default: "-created_at",
+    id: audit_log::Column::Id,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct AuditLogEntry {
+    pub id: i32,
+    /// Null if the user has since been deleted
+    pub actor_id: Option<i32>,
+    pub actor_email: Option<String>,
+    /// Null for actions outside a hackathon, or if the hackathon has since been deleted
+    pub hackathon_id: Option<i32>,
+    pub hackathon_slug: Option<String>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<i32>,
+    /// Changed fields as `{"field": {"from": ..., "to": ...}}`, or other details of the action
+    pub diff: Option<serde_json::Value>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogQuery {
+    /// Only return actions performed by this user
+    actor_id: Option<i32>,
+    /// Only return this action, or every action starting with it if it ends with a dot, such as
+    /// `hackathon.`
+    action: Option<String>,
+    /// Only return actions within the hackathon with this slug
+    hackathon: Option<String>,
+    /// Only return actions at or after this time
+    since: Option<NaiveDateTime>,
+    /// Only return actions before this time
+    until: Option<NaiveDateTime>,
+}
+
+/// Query the audit log of privileged actions
+#[utoipa::path(
+    get,
+    path = "/admins/audit-log",
+    params(AuditLogQuery, PageParams),
+    responses(
+        (status = 200, description = "Matching entries, newest first", body = Page<AuditLogEntry>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a global admin"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 422, description = "Invalid page, sort or cursor"),
+    ),
+    tag = "Admins"
+)]
+pub async fn list_audit_log(
+    _admin: RequireGlobalAdmin,
+    State(state): State<AppState>,
+    Query(query): Query<AuditLogQuery>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<AuditLogEntry>>, AppError> {
+    let mut select = AuditLog::find();
+
+    if let Some(actor_id) = query.actor_id {
+        select = select.filter(audit_log::Column::ActorId.eq(actor_id));
+    }
+    if let Some(action) = &query.action {
+        select = if action.ends_with('.') {
+            select.filter(audit_log::Column::Action.starts_with(action))
+        } else {
+            select.filter(audit_log::Column::Action.eq(action))
+        };
+    }
+    if let Some(slug) = &query.hackathon {
+        let hackathon = Hackathons::find()
+            .filter(hackathons::Column::Slug.eq(slug))
+            .one(&state.db)
+            .await?
+            .ok_or(AppError::NotFound("Hackathon not found"))?;
+        select = select.filter(audit_log::Column::HackathonId.eq(hackathon.id));
+    }
+    if let Some(since) = query.since {
+        select = select.filter(audit_log::Column::CreatedAt.gte(since));
+    }
+    if let Some(until) = query.until {
+        select = select.filter(audit_log::Column::CreatedAt.lt(until));
+    }
+
+    let page = pagination::paginate(&state.db, select, &page, &SORT, |e| e).await?;
+
+    let actor_ids: Vec<i32> = page.items.iter().filter_map(|e| e.actor_id).collect();
+    let emails: HashMap<i32, String> = Users::find()
+        .filter(users::Column::Id.is_in(actor_ids))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user.email))
+        .collect();
+
+    let hackathon_ids: Vec<i32> = page.items.iter().filter_map(|e| e.hackathon_id).collect();
+    let slugs: HashMap<i32, String> = Hackathons::find()
+        .filter(hackathons::Column::Id.is_in(hackathon_ids))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|hackathon| (hackathon.id, hackathon.slug))
+        .collect();
+
+    Ok(Json(page.map(|entries| {
+        entries
+            .into_iter()
+            .map(|entry| AuditLogEntry {
+                id: entry.id,
+                actor_email: entry.actor_id.and_then(|id| emails.get(&id).cloned()),
+                actor_id: entry.actor_id,
+                hackathon_slug: entry.hackathon_id.and_then(|id| slugs.get(&id).cloned()),
+                hackathon_id: entry.hackathon_id,
+                action: entry.action,
+                target_type: entry.target_type,
+                target_id: entry.target_id,
+                diff: entry.diff,
+                created_at: entry.created_at,
+            })
+            .collect()
+    })))
+}
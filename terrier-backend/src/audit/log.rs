@@ -0,0 +1,83 @@
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ConnectionTrait, DbErr};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use crate::{auth::extractors::HackathonRole, entities::audit_log};
+
+/// Fields that change on every write, so they are left out of diffs
+const IGNORED_FIELDS: [&str; 1] = ["updated_at"];
+
+/// A privileged action to record in the audit log
+#[derive(Default)]
+pub struct Entry<'a> {
+    /// The user who performed the action
+    pub actor_id: i32,
+    pub hackathon_id: Option<i32>,
+    /// Dotted name such as `hackathon.update` or `mentor.add`
+    pub action: &'a str,
+    /// What the action was performed on, such as `user` or `hackathon`
+    pub target_type: &'a str,
+    pub target_id: Option<i32>,
+    /// What changed, usually built with [`diff`]
+    pub diff: Option<Value>,
+}
+
+impl<'a> Entry<'a> {
+    /// An action taken by `role` within its hackathon
+    pub fn in_hackathon(
+        role: &HackathonRole,
+        action: &'a str,
+        target_type: &'a str,
+        target_id: i32,
+    ) -> Self {
+        Entry {
+            actor_id: role.user_id,
+            hackathon_id: Some(role.hackathon_id),
+            action,
+            target_type,
+            target_id: Some(target_id),
+            diff: None,
+        }
+    }
+}
+
+/// Record a privileged action
+///
+/// Pass the transaction the action runs in, so the action is never committed without its
+/// entry.
+pub async fn record<C: ConnectionTrait>(db: &C, entry: Entry<'_>) -> Result<(), DbErr> {
+    audit_log::ActiveModel {
+        actor_id: Set(Some(entry.actor_id)),
+        hackathon_id: Set(entry.hackathon_id),
+        action: Set(entry.action.to_string()),
+        target_type: Set(entry.target_type.to_string()),
+        target_id: Set(entry.target_id),
+        diff: Set(entry.diff),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(())
+}
+
+/// The top-level fields that differ between `before` and `after`, as
+/// `{"field": {"from": ..., "to": ...}}`, or `None` if nothing changed
+pub fn diff<T: Serialize>(before: &T, after: &T) -> Option<Value> {
+    let (Ok(Value::Object(before)), Ok(Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return None;
+    };
+
+    let changes: Map<String, Value> = after
+        .into_iter()
+        .filter(|(field, _)| !IGNORED_FIELDS.contains(&field.as_str()))
+        .filter_map(|(field, to)| {
+            let from = before.get(&field).cloned().unwrap_or(Value::Null);
+            (from != to).then(|| (field, json!({ "from": from, "to": to })))
+        })
+        .collect();
+
+    (!changes.is_empty()).then_some(Value::Object(changes))
+}
@@ -0,0 +1,183 @@
+//! Development login that bypasses OIDC, enabled with `AUTH_MODE=dev`.
+//!
+//! Any caller can become any user, so this must never be enabled in production.
+
+use axum::{
+    extract::State,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+use serde::Deserialize;
+use tower_sessions::Session;
+
+use crate::{
+    AppState,
+    auth::{
+        extractors::HACKATHON_ROLES,
+        identity::{self, IdentityClaims, SESSION_USER_KEY},
+    },
+    entities::{hackathons, prelude::*, user_hackathon_roles},
+    error::AppError,
+    validation::Query,
+};
+
+/// Issuer recorded on users created through dev auth
+const DEV_ISSUER: &str = "dev";
+
+/// Login form up to the options of its role select, which are built from [`HACKATHON_ROLES`]
+const LOGIN_FORM_HEAD: &str = r#"<!doctype html>
+<html>
+<head><title>Terrier dev login</title></head>
+<body>
+<h1>Terrier dev login</h1>
+<form method="get">
+<p><label>Email <input name="email" type="email" required></label></p>
+<p><label>Name <input name="name"></label></p>
+<p><label>Hackathon slug <input name="hackathon"></label></p>
+<p><label>Role <select name="role">
+<option value="">(none)</option>
+"#;
+
+const LOGIN_FORM_TAIL: &str = r#"</select></label></p>
+<p><button type="submit">Log in</button></p>
+</form>
+</body>
+</html>"#;
+
+/// Build the login form, offering every role so none can be forgotten when one is added
+fn login_form() -> String {
+    let options: String = HACKATHON_ROLES
+        .iter()
+        .map(|role| format!("<option>{role}</option>\n"))
+        .collect();
+    format!("{LOGIN_FORM_HEAD}{options}{LOGIN_FORM_TAIL}")
+}
+
+#[derive(Deserialize)]
+pub struct DevLoginQuery {
+    email: Option<String>,
+    name: Option<String>,
+    /// Hackathon to grant `role` in
+    hackathon: Option<String>,
+    role: Option<String>,
+    redirect_uri: Option<String>,
+}
+
+/// Log in as the user with the given email, creating them if needed.
+///
+/// Without an email, a login form is returned instead. If a hackathon and role are
+/// given, the user's role in that hackathon is set, so every role can be exercised.
+pub async fn login(
+    State(state): State<AppState>,
+    session: Session,
+    Query(params): Query<DevLoginQuery>,
+) -> Result<Response, AppError> {
+    let Some(email) = params
+        .email
+        .map(|e| e.trim().to_lowercase())
+        .filter(|e| !e.is_empty())
+    else {
+        return Ok(Html(login_form()).into_response());
+    };
+
+    // Dev emails count as verified, so logging in as a real user's email acts as them
+    let claims = IdentityClaims {
+        issuer: DEV_ISSUER.to_string(),
+        subject: email.clone(),
+        email: Some(email),
+        email_verified: true,
+        name: params.name.filter(|n| !n.is_empty()),
+        given_name: None,
+        family_name: None,
+        picture: None,
+    };
+
+    let user = identity::resolve_user(&state.db, claims, None, &state.config.admin_emails)
+        .await
+        .map_err(|e| AppError::internal(format_args!("Failed to resolve dev user: {e:?}")))?;
+
+    state.user_cache.invalidate(user.id).await;
+
+    if let (Some(slug), Some(role)) = (
+        params.hackathon.filter(|s| !s.is_empty()),
+        params.role.filter(|r| !r.is_empty()),
+    ) {
+        set_role(&state, user.id, &slug, role).await?;
+    }
+
+    // Prevent session fixation now that the session is privileged
+    session.cycle_id().await?;
+    session.insert(SESSION_USER_KEY, user.id).await?;
+
+    let redirect_to = params
+        .redirect_uri
+        .filter(|uri| uri.starts_with(&state.config.app_url))
+        .unwrap_or_else(|| state.config.app_url.clone());
+
+    Ok(Redirect::to(&redirect_to).into_response())
+}
+
+async fn set_role(
+    state: &AppState,
+    user_id: i32,
+    slug: &str,
+    role: String,
+) -> Result<(), AppError> {
+    if !HACKATHON_ROLES.contains(&role.as_str()) {
+        return Err(AppError::BadRequest("Unknown role"));
+    }
+
+    let hackathon = Hackathons::find()
+        .filter(hackathons::Column::Slug.eq(slug))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Hackathon not found"))?;
+
+    let existing = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::UserId.eq(user_id))
+        .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon.id))
+        .one(&state.db)
+        .await?;
+
+    let result = match existing {
+        Some(existing) => {
+            let mut existing: user_hackathon_roles::ActiveModel = existing.into();
+            existing.role = Set(role);
+            existing.update(&state.db).await
+        }
+        None => {
+            user_hackathon_roles::ActiveModel {
+                user_id: Set(user_id),
+                hackathon_id: Set(hackathon.id),
+                role: Set(role),
+                ..Default::default()
+            }
+            .insert(&state.db)
+            .await
+        }
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/// Log the current dev user out
+pub async fn logout(State(state): State<AppState>, session: Session) -> Result<Redirect, AppError> {
+    session.flush().await?;
+
+    Ok(Redirect::to(&state.config.app_url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HACKATHON_ROLES, login_form};
+
+    #[test]
+    fn form_offers_every_role() {
+        let form = login_form();
+        for role in HACKATHON_ROLES {
+            assert!(form.contains(&format!("<option>{role}</option>")), "{role}");
+        }
+    }
+}
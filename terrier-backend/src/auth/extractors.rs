@@ -1,50 +1,225 @@
 use axum::{
     RequestPartsExt,
-    extract::{FromRequestParts, Path},
-    http::{StatusCode, request::Parts},
+    extract::{FromRequestParts, RawPathParams},
+    http::request::Parts,
 };
-use axum_oidc::{EmptyAdditionalClaims, OidcClaims};
-use sea_orm::{ColumnTrait, EntityTrait, JoinType, QueryFilter, QuerySelect, RelationTrait};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tower_sessions::Session;
 
 use crate::{
     AppState,
-    entities::{hackathons, prelude::*, user_hackathon_roles, users},
+    auth::{
+        identity::SESSION_USER_KEY,
+        tokens::{self, TokenScope},
+    },
+    entities::{
+        api_tokens, hackathons, organization_admins, organizations, prelude::*,
+        user_hackathon_roles, users,
+    },
+    error::AppError,
+    hackathons::phase::{self, HackathonPhase},
 };
 
+/// The authenticated user, from either an OIDC session or a bearer API token
+pub struct AuthUser {
+    pub user: users::Model,
+    /// Set when the request was authenticated with an API token
+    pub token: Option<api_tokens::Model>,
+}
+
+impl AuthUser {
+    /// API tokens only carry global admin rights if they were issued with the admin scope
+    pub fn is_global_admin(&self) -> bool {
+        let token_allows = self
+            .token
+            .as_ref()
+            .is_none_or(|t| tokens::has_scope(t, TokenScope::Admin));
+
+        token_allows && self.user.is_global_admin
+    }
+}
+
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(raw) = tokens::bearer_token(&parts.headers) {
+            let (user, token) = tokens::authenticate(&state.db, raw).await?;
+
+            let required = if parts.method.is_safe() {
+                TokenScope::Read
+            } else {
+                TokenScope::Write
+            };
+
+            if !tokens::has_scope(&token, required) {
+                return Err(AppError::Forbidden);
+            }
+
+            return Ok(AuthUser {
+                user,
+                token: Some(token),
+            });
+        }
+
+        let session = Session::from_request_parts(parts, state)
+            .await
+            .map_err(|_| AppError::Unauthorized)?;
+
+        let user_id = session
+            .get::<i32>(SESSION_USER_KEY)
+            .await?
+            .ok_or(AppError::Unauthorized)?;
+
+        let user = state
+            .user_cache
+            .get(&state.db, user_id)
+            .await?
+            .ok_or(AppError::Unauthorized)?;
+
+        Ok(AuthUser { user, token: None })
+    }
+}
+
 pub struct RequireGlobalAdmin {
+    pub user_id: i32,
     pub email: String,
 }
 
 impl FromRequestParts<AppState> for RequireGlobalAdmin {
-    type Rejection = StatusCode;
+    type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
-        let claims = OidcClaims::<EmptyAdditionalClaims>::from_request_parts(parts, &state)
-            .await
-            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+        let auth = AuthUser::from_request_parts(parts, state).await?;
 
-        let email = claims
-            .0
-            .email()
-            .map(|e| e.to_string())
-            .ok_or(StatusCode::UNAUTHORIZED)?;
+        // Tokens scoped to a single hackathon never grant deployment-wide access
+        let hackathon_scoped = auth
+            .token
+            .as_ref()
+            .is_some_and(|t| t.hackathon_id.is_some());
 
-        if state.config.admin_emails.contains(&email.to_lowercase()) {
-            Ok(RequireGlobalAdmin { email })
+        if !hackathon_scoped && auth.is_global_admin() {
+            Ok(RequireGlobalAdmin {
+                user_id: auth.user.id,
+                email: auth.user.email,
+            })
         } else {
-            Err(StatusCode::FORBIDDEN)
+            Err(AppError::Forbidden)
         }
     }
 }
 
+/// Read the `slug` path parameter, which may be followed by others such as an ID
+async fn slug_param(parts: &mut Parts) -> Result<String, AppError> {
+    let params = parts
+        .extract::<RawPathParams>()
+        .await
+        .map_err(|_| AppError::BadRequest("Invalid path"))?;
+
+    params
+        .iter()
+        .find(|(key, _)| *key == "slug")
+        .map(|(_, value)| value.to_string())
+        .ok_or(AppError::BadRequest("Missing slug in path"))
+}
+
+/// Whether the user can manage an organization and the hackathons it owns
+pub async fn can_manage_organization(
+    state: &AppState,
+    auth: &AuthUser,
+    organization_id: i32,
+) -> Result<bool, AppError> {
+    // Tokens scoped to a single hackathon never grant organization-wide access
+    if auth
+        .token
+        .as_ref()
+        .is_some_and(|t| t.hackathon_id.is_some())
+    {
+        return Ok(false);
+    }
+
+    if auth.is_global_admin() {
+        return Ok(true);
+    }
+
+    is_organization_admin(state, auth.user.id, organization_id).await
+}
+
+async fn is_organization_admin(
+    state: &AppState,
+    user_id: i32,
+    organization_id: i32,
+) -> Result<bool, AppError> {
+    let admin = OrganizationAdmins::find()
+        .filter(organization_admins::Column::OrganizationId.eq(organization_id))
+        .filter(organization_admins::Column::UserId.eq(user_id))
+        .one(&state.db)
+        .await?;
+
+    Ok(admin.is_some())
+}
+
+/// An admin of the organization in the path, or a global admin
+pub struct OrganizationAdmin {
+    pub user_id: i32,
+    pub email: String,
+    pub organization: organizations::Model,
+}
+
+impl FromRequestParts<AppState> for OrganizationAdmin {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let slug = slug_param(parts).await?;
+        let auth = AuthUser::from_request_parts(parts, state).await?;
+
+        let organization = Organizations::find()
+            .filter(organizations::Column::Slug.eq(&slug))
+            .one(&state.db)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found"))?;
+
+        if !can_manage_organization(state, &auth, organization.id).await? {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(OrganizationAdmin {
+            user_id: auth.user.id,
+            email: auth.user.email,
+            organization,
+        })
+    }
+}
+
+/// Every role a user can hold within a hackathon
+pub const HACKATHON_ROLES: [&str; 8] = [
+    "admin",
+    "organizer",
+    "judge",
+    "sponsor",
+    "mentor",
+    "volunteer",
+    "participant",
+    "applicant",
+];
+
 pub struct HackathonRole {
     pub user_id: i32,
     pub hackathon_id: i32,
     pub role: String,
     pub slug: String,
+    pub phase: HackathonPhase,
+    /// Organizers who can review reimbursements and see payouts
+    pub finance: bool,
 }
 
 impl HackathonRole {
@@ -65,85 +240,95 @@ impl HackathonRole {
         matches!(self.role.as_str(), "admin" | "organizer" | "sponsor")
     }
 
+    pub fn is_mentor(&self) -> bool {
+        matches!(self.role.as_str(), "admin" | "organizer" | "mentor")
+    }
+
+    pub fn is_volunteer(&self) -> bool {
+        matches!(self.role.as_str(), "admin" | "organizer" | "volunteer")
+    }
+
+    /// Admins, and organizers who were given access to the hackathon's finances
+    pub fn is_finance(&self) -> bool {
+        self.is_admin() || (self.role == "organizer" && self.finance)
+    }
+
     pub fn is_participant(&self) -> bool {
         matches!(self.role.as_str(), "admin" | "participant")
     }
 
-    pub fn is_applicant(&self) -> bool {
-        matches!(self.role.as_str(), "admin" | "applicant")
+    /// Reject the request unless the hackathon is in one of the `allowed` phases
+    pub fn require_phase(&self, allowed: &[HackathonPhase]) -> Result<(), AppError> {
+        if allowed.contains(&self.phase) {
+            Ok(())
+        } else {
+            Err(AppError::Conflict(
+                "Not possible in the hackathon's current phase",
+            ))
+        }
     }
 }
 
 impl FromRequestParts<AppState> for HackathonRole {
-    type Rejection = StatusCode;
+    type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
         // Extract hackathon slug from path
-        let Path(slug) = parts
-            .extract::<Path<String>>()
-            .await
-            .map_err(|_| StatusCode::BAD_REQUEST)?;
+        let slug = slug_param(parts).await?;
 
-        let claims = OidcClaims::<EmptyAdditionalClaims>::from_request_parts(parts, state)
-            .await
-            .map_err(|_| StatusCode::UNAUTHORIZED)?;
-
-        let email = claims
-            .0
-            .email()
-            .map(|e| e.to_string())
-            .ok_or(StatusCode::UNAUTHORIZED)?;
-
-        // Global admins have admin role in all hackathons
-        if state.config.admin_emails.contains(&email.to_lowercase()) {
-            let hackathon = Hackathons::find()
-                .filter(hackathons::Column::Slug.eq(&slug))
-                .one(&state.db)
-                .await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-                .ok_or(StatusCode::NOT_FOUND)?;
-
-            let user = Users::find()
-                .filter(users::Column::OidcSub.eq(claims.0.subject().to_string()))
-                .one(&state.db)
-                .await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-                .ok_or(StatusCode::UNAUTHORIZED)?;
+        let auth = AuthUser::from_request_parts(parts, state).await?;
 
+        let hackathon = Hackathons::find()
+            .filter(hackathons::Column::Slug.eq(&slug))
+            .one(&state.db)
+            .await?
+            .ok_or(AppError::NotFound("Hackathon not found"))?;
+
+        // Tokens scoped to a hackathon cannot be used for any other hackathon
+        if let Some(scoped_id) = auth.token.as_ref().and_then(|t| t.hackathon_id)
+            && scoped_id != hackathon.id
+        {
+            return Err(AppError::Forbidden);
+        }
+
+        // Global admins have admin role in all hackathons, and organization admins in all of
+        // their organization's hackathons
+        let is_organization_admin = match hackathon.organization_id {
+            Some(organization_id) => {
+                is_organization_admin(state, auth.user.id, organization_id).await?
+            }
+            None => false,
+        };
+
+        if auth.is_global_admin() || is_organization_admin {
             return Ok(HackathonRole {
-                user_id: user.id,
+                user_id: auth.user.id,
                 hackathon_id: hackathon.id,
                 role: "admin".to_string(),
                 slug,
+                phase: phase::current(&hackathon),
+                finance: true,
             });
         }
 
         // Look up role in database
         let result = UserHackathonRoles::find()
-            .join(
-                JoinType::InnerJoin,
-                user_hackathon_roles::Relation::Users.def(),
-            )
-            .join(
-                JoinType::InnerJoin,
-                user_hackathon_roles::Relation::Hackathons.def(),
-            )
-            .filter(users::Column::OidcSub.eq(claims.0.subject().to_string()))
-            .filter(hackathons::Column::Slug.eq(&slug))
-            .find_also_related(Hackathons)
+            .filter(user_hackathon_roles::Column::UserId.eq(auth.user.id))
+            .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon.id))
             .one(&state.db)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-            .ok_or(StatusCode::FORBIDDEN)?;
+            .await?
+            .ok_or(AppError::Forbidden)?;
 
         Ok(HackathonRole {
-            user_id: result.0.user_id,
-            hackathon_id: result.0.hackathon_id,
-            role: result.0.role,
+            user_id: result.user_id,
+            hackathon_id: result.hackathon_id,
+            role: result.role,
             slug,
+            phase: phase::current(&hackathon),
+            finance: result.finance,
         })
     }
 }
@@ -1,19 +1,35 @@
 use axum::{
     Json,
-    extract::{Query, State},
-    http::{StatusCode, Uri},
-    response::{IntoResponse, Redirect},
+    extract::State,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use openidconnect::{
+    AccessTokenHash, AuthorizationCode, CsrfToken, Nonce, OAuth2TokenResponse, PkceCodeChallenge,
+    PkceCodeVerifier, PostLogoutRedirectUrl, Scope, TokenResponse,
+    core::{CoreAuthenticationFlow, CoreIdToken},
+    url::Url,
 };
-use axum_oidc::{EmptyAdditionalClaims, OidcClaims, OidcRpInitiatedLogout};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde::{Deserialize, Serialize};
+use tower_sessions::Session;
 use utoipa::ToSchema;
 
 use crate::{
     AppState,
-    entities::{prelude::*, users},
+    auth::{
+        extractors::AuthUser,
+        identity::{self, IdentityClaims, IdentityError, SESSION_USER_KEY},
+        oidc::OidcProviders,
+    },
+    error::AppError,
+    validation::Query,
 };
 
+/// Session key holding the login in progress while the user is at their provider
+const PENDING_LOGIN_KEY: &str = "pending_login";
+/// Session keys used for RP-initiated logout at the provider the user logged in with
+const LOGIN_PROVIDER_KEY: &str = "login_provider";
+const ID_TOKEN_KEY: &str = "id_token";
+
 #[derive(Serialize, ToSchema)]
 pub struct UserInfo {
     id: String,
@@ -33,73 +49,309 @@ pub struct UserInfo {
     ),
     tag = "Authentication"
 )]
-#[axum::debug_handler]
-pub async fn status(
-    State(state): State<AppState>,
-    claims: Option<OidcClaims<EmptyAdditionalClaims>>,
-) -> Result<Json<UserInfo>, StatusCode> {
-    match claims {
-        Some(claims) => {
-            let email = claims
-                .0
-                .email()
-                .map(|s| s.to_string())
-                .ok_or(StatusCode::UNAUTHORIZED)?;
-
-            let is_admin = state.config.admin_emails.contains(&email.to_lowercase());
-
-            let oidc_sub = claims.0.subject().to_string();
-            let user = Users::find()
-                .filter(users::Column::OidcSub.eq(&oidc_sub))
-                .one(&state.db)
-                .await
-                .ok()
-                .flatten();
-
-            user.map(|user| {
-                Json(UserInfo {
-                    id: user.id.to_string(),
-                    email: user.email,
-                    name: user.name,
-                    picture: user.picture,
-                    is_admin,
-                })
+#[axum::debug_handler(state = AppState)]
+pub async fn status(auth: AuthUser) -> Result<Json<UserInfo>, AppError> {
+    let is_admin = auth.is_global_admin();
+    let user = auth.user;
+
+    Ok(Json(UserInfo {
+        id: user.id.to_string(),
+        email: user.email,
+        name: user.name,
+        picture: user.picture,
+        is_admin,
+    }))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProviderInfo {
+    id: String,
+    name: String,
+}
+
+/// List the identity providers users can log in with
+#[utoipa::path(
+    get,
+    path = "/auth/providers",
+    responses(
+        (status = 200, description = "Available identity providers", body = Vec<ProviderInfo>)
+    ),
+    tag = "Authentication"
+)]
+pub async fn providers(State(state): State<AppState>) -> Json<Vec<ProviderInfo>> {
+    Json(
+        state
+            .oidc
+            .iter()
+            .map(|p| ProviderInfo {
+                id: p.id.clone(),
+                name: p.name.clone(),
             })
-            .ok_or(StatusCode::UNAUTHORIZED)
-        }
-        None => Err(StatusCode::UNAUTHORIZED),
-    }
+            .collect(),
+    )
 }
 
 #[derive(Deserialize, ToSchema)]
 pub struct LoginQuery {
     redirect_uri: Option<String>,
+    provider: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PendingLogin {
+    provider: String,
+    csrf_token: String,
+    nonce: String,
+    pkce_verifier: String,
+    redirect_to: String,
 }
 
 /// Initiate login flow
+///
+/// When several providers are configured and none is chosen, a provider chooser is
+/// returned. Choosing a provider while already logged in links that identity to the
+/// current user.
 #[utoipa::path(
     get,
     path = "/auth/login",
     params(
-        ("redirect_uri" = Option<String>, Query, description = "URI to redirect to after login")
+        ("redirect_uri" = Option<String>, Query, description = "URI to redirect to after login"),
+        ("provider" = Option<String>, Query, description = "Identity provider to log in with")
     ),
     responses(
-        (status = 302, description = "Redirect to app after login")
+        (status = 200, description = "Provider chooser"),
+        (status = 302, description = "Redirect to the identity provider, or to the app if already logged in"),
+        (status = 404, description = "Unknown provider")
     ),
     tag = "Authentication"
 )]
 pub async fn login(
-    _claims: OidcClaims<EmptyAdditionalClaims>,
     State(state): State<AppState>,
+    session: Session,
     Query(params): Query<LoginQuery>,
-) -> impl IntoResponse {
-    // OidcLoginLayer will have handled login, so redirect the user back at this point
+) -> Result<Response, AppError> {
     let redirect_to = params
         .redirect_uri
         .filter(|uri| uri.starts_with(&state.config.app_url))
         .unwrap_or_else(|| state.config.app_url.clone());
 
-    Redirect::to(&redirect_to)
+    let logged_in = session.get::<i32>(SESSION_USER_KEY).await?.is_some();
+
+    let provider = match &params.provider {
+        Some(id) => state
+            .oidc
+            .get(id)
+            .ok_or(AppError::NotFound("Unknown login provider"))?,
+        None if logged_in => return Ok(Redirect::to(&redirect_to).into_response()),
+        None => match state.oidc.only() {
+            Some(provider) => provider,
+            None => return provider_chooser(&state, &redirect_to),
+        },
+    };
+
+    let client = state.oidc.client(provider);
+    let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+
+    let (auth_url, csrf_token, nonce) = client
+        .authorize_url(
+            CoreAuthenticationFlow::AuthorizationCode,
+            CsrfToken::new_random,
+            Nonce::new_random,
+        )
+        .add_scope(Scope::new("email".to_string()))
+        .add_scope(Scope::new("profile".to_string()))
+        .set_pkce_challenge(pkce_challenge)
+        .url();
+
+    let pending = PendingLogin {
+        provider: provider.id.clone(),
+        csrf_token: csrf_token.secret().clone(),
+        nonce: nonce.secret().clone(),
+        pkce_verifier: pkce_verifier.secret().clone(),
+        redirect_to,
+    };
+
+    session.insert(PENDING_LOGIN_KEY, pending).await?;
+
+    Ok(Redirect::to(auth_url.as_str()).into_response())
+}
+
+fn provider_chooser(state: &AppState, redirect_to: &str) -> Result<Response, AppError> {
+    let login_url = format!("{}/auth/login", state.config.api_url);
+    let mut links = String::new();
+
+    for provider in state.oidc.iter() {
+        let url = Url::parse_with_params(
+            &login_url,
+            [
+                ("provider", provider.id.as_str()),
+                ("redirect_uri", redirect_to),
+            ],
+        )
+        .map_err(|e| AppError::internal(format_args!("Invalid login URL: {e}")))?;
+
+        links.push_str(&format!(
+            "<li><a href=\"{}\">{}</a></li>\n",
+            escape_html(url.as_str()),
+            escape_html(&provider.name)
+        ));
+    }
+
+    Ok(Html(format!(
+        "<!doctype html>\n<html>\n<head><title>Log in to Terrier</title></head>\n<body>\n\
+         <h1>Log in with</h1>\n<ul>\n{links}</ul>\n</body>\n</html>"
+    ))
+    .into_response())
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[derive(Deserialize)]
+pub struct CallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+/// Complete login after the identity provider redirects back
+#[utoipa::path(
+    get,
+    path = "/auth/callback",
+    responses(
+        (status = 302, description = "Redirect to app after login"),
+        (status = 400, description = "No login in progress or mismatched state"),
+        (status = 401, description = "The provider rejected the login"),
+        (status = 409, description = "The identity is already linked to another user")
+    ),
+    tag = "Authentication"
+)]
+pub async fn callback(
+    State(state): State<AppState>,
+    session: Session,
+    Query(params): Query<CallbackQuery>,
+) -> Result<Redirect, AppError> {
+    let pending = session
+        .remove::<PendingLogin>(PENDING_LOGIN_KEY)
+        .await?
+        .ok_or(AppError::BadRequest("No login in progress"))?;
+
+    if let Some(error) = params.error {
+        tracing::warn!("OIDC login with {} failed: {}", pending.provider, error);
+        return Err(AppError::Unauthorized);
+    }
+
+    if params.state.as_deref() != Some(pending.csrf_token.as_str()) {
+        return Err(AppError::BadRequest("Login state does not match"));
+    }
+
+    let code = params
+        .code
+        .ok_or(AppError::BadRequest("Missing authorization code"))?;
+    let provider = state
+        .oidc
+        .get(&pending.provider)
+        .ok_or(AppError::BadRequest("Unknown login provider"))?;
+    let client = state.oidc.client(provider);
+
+    let token_response = client
+        .exchange_code(AuthorizationCode::new(code))
+        .map_err(|e| {
+            AppError::internal(format_args!(
+                "OIDC provider {} has no token endpoint: {e:?}",
+                provider.id
+            ))
+        })?
+        .set_pkce_verifier(PkceCodeVerifier::new(pending.pkce_verifier))
+        .request_async(&state.oidc.http_client)
+        .await
+        .map_err(|e| {
+            tracing::error!("OIDC code exchange with {} failed: {:?}", provider.id, e);
+            AppError::Unauthorized
+        })?;
+
+    let id_token = token_response.id_token().ok_or(AppError::Unauthorized)?;
+    let verifier = client.id_token_verifier();
+    let claims = id_token
+        .claims(&verifier, &Nonce::new(pending.nonce))
+        .map_err(|e| {
+            tracing::warn!("Invalid ID token from {}: {:?}", provider.id, e);
+            AppError::Unauthorized
+        })?;
+
+    // Make sure the access token was not substituted for another user's
+    if let Some(expected_hash) = claims.access_token_hash() {
+        let signing_alg = id_token.signing_alg().map_err(|_| AppError::Unauthorized)?;
+        let signing_key = id_token
+            .signing_key(&verifier)
+            .map_err(|_| AppError::Unauthorized)?;
+        let actual_hash =
+            AccessTokenHash::from_token(token_response.access_token(), signing_alg, signing_key)
+                .map_err(|_| AppError::Unauthorized)?;
+
+        if actual_hash != *expected_hash {
+            return Err(AppError::Unauthorized);
+        }
+    }
+
+    let identity_claims = IdentityClaims {
+        issuer: claims.issuer().to_string(),
+        subject: claims.subject().to_string(),
+        email: claims.email().map(|e| e.to_lowercase()),
+        email_verified: claims.email_verified().unwrap_or(false),
+        name: claims
+            .name()
+            .and_then(|n| n.get(None))
+            .map(|s| s.to_string()),
+        given_name: claims
+            .given_name()
+            .and_then(|n| n.get(None))
+            .map(|s| s.to_string()),
+        family_name: claims
+            .family_name()
+            .and_then(|n| n.get(None))
+            .map(|s| s.to_string()),
+        picture: claims
+            .picture()
+            .and_then(|p| p.get(None))
+            .map(|s| s.to_string()),
+    };
+
+    let current_user = session.get::<i32>(SESSION_USER_KEY).await?;
+
+    let user = identity::resolve_user(
+        &state.db,
+        identity_claims,
+        current_user,
+        &state.config.admin_emails,
+    )
+    .await
+    .map_err(|e| match e {
+        IdentityError::AlreadyLinked => {
+            AppError::Conflict("This account is already linked to another user")
+        }
+        IdentityError::Db(e) => AppError::internal(format_args!("Failed to resolve user: {e:?}")),
+    })?;
+
+    // The profile may have just been refreshed from the new claims
+    state.user_cache.invalidate(user.id).await;
+
+    // Prevent session fixation now that the session is privileged
+    session.cycle_id().await?;
+
+    for result in [
+        session.insert(SESSION_USER_KEY, user.id).await,
+        session.insert(LOGIN_PROVIDER_KEY, &provider.id).await,
+        session.insert(ID_TOKEN_KEY, id_token).await,
+    ] {
+        result?;
+    }
+
+    Ok(Redirect::to(&pending.redirect_to))
 }
 
 /// Log the current user out
@@ -112,13 +364,41 @@ pub async fn login(
     tag = "Authentication"
 )]
 #[axum::debug_handler]
-pub async fn logout(
-    logout: OidcRpInitiatedLogout,
-    State(state): State<AppState>,
-) -> impl IntoResponse {
-    logout
-        .with_post_logout_redirect(
-            Uri::from_maybe_shared(state.config.app_url).expect("valid APP_URL"),
-        )
-        .into_response()
+pub async fn logout(State(state): State<AppState>, session: Session) -> Result<Redirect, AppError> {
+    let provider = session.get::<String>(LOGIN_PROVIDER_KEY).await?;
+    let id_token = session.get::<CoreIdToken>(ID_TOKEN_KEY).await?;
+
+    session.flush().await?;
+
+    let logout_url = provider
+        .and_then(|id| rp_initiated_logout_url(&state.oidc, &id, id_token, &state.config.app_url));
+
+    Ok(Redirect::to(
+        logout_url.as_deref().unwrap_or(&state.config.app_url),
+    ))
+}
+
+/// Log out at the provider too, if it supports RP-initiated logout
+fn rp_initiated_logout_url(
+    oidc: &OidcProviders,
+    provider_id: &str,
+    id_token: Option<CoreIdToken>,
+    app_url: &str,
+) -> Option<String> {
+    let provider = oidc.get(provider_id)?;
+    let end_session_endpoint = provider
+        .metadata
+        .additional_metadata()
+        .end_session_endpoint
+        .clone()?;
+
+    let mut request = openidconnect::LogoutRequest::from(end_session_endpoint)
+        .set_client_id(provider.client_id.clone())
+        .set_post_logout_redirect_uri(PostLogoutRedirectUrl::new(app_url.to_string()).ok()?);
+
+    if let Some(id_token) = &id_token {
+        request = request.set_id_token_hint(id_token);
+    }
+
+    Some(request.http_get_url().to_string())
 }
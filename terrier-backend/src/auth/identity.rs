@@ -0,0 +1,213 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, TransactionTrait,
+    sea_query::{Expr, Func},
+};
+
+use crate::{
+    entities::{prelude::*, user_identities, users},
+    invites::redeem::redeem_invites,
+};
+
+/// Session key holding the ID of the logged in user
+pub const SESSION_USER_KEY: &str = "user_id";
+
+/// Claims about a user as asserted by an identity provider
+pub struct IdentityClaims {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    pub picture: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum IdentityError {
+    /// The identity is already linked to a different user
+    AlreadyLinked,
+    Db(DbErr),
+}
+
+impl From<DbErr> for IdentityError {
+    fn from(e: DbErr) -> Self {
+        IdentityError::Db(e)
+    }
+}
+
+/// Find or create the user an identity belongs to.
+///
+/// Identities are matched by issuer and subject. A new identity is linked to `current_user`
+/// if someone is already logged in, otherwise to the user with another identity asserting
+/// the same verified email, otherwise a new user is created. New users with a verified
+/// email in `admin_emails` start out as global admins. Invites to the verified email are redeemed.
+pub async fn resolve_user(
+    db: &DatabaseConnection,
+    claims: IdentityClaims,
+    current_user: Option<i32>,
+    admin_emails: &[String],
+) -> Result<users::Model, IdentityError> {
+    let verified_email = claims.email.clone().filter(|_| claims.email_verified);
+
+    let existing = UserIdentities::find()
+        .filter(user_identities::Column::OidcIssuer.eq(&claims.issuer))
+        .filter(user_identities::Column::OidcSub.eq(&claims.subject))
+        .find_also_related(Users)
+        .one(db)
+        .await?;
+
+    if let Some((identity, Some(user))) = existing {
+        if current_user.is_some_and(|id| id != user.id) {
+            return Err(IdentityError::AlreadyLinked);
+        }
+
+        let mut identity: user_identities::ActiveModel = identity.into();
+        identity.email = Set(claims.email.clone());
+        identity.email_verified = Set(claims.email_verified);
+        identity.last_login_at = Set(Utc::now().naive_utc());
+        identity.update(db).await?;
+
+        let user = sync_profile(db, user, claims).await?;
+        if let Some(email) = &verified_email {
+            redeem_invites(db, &user, email).await?;
+        }
+        return Ok(user);
+    }
+
+    let linked_user = match current_user {
+        Some(id) => Users::find_by_id(id).one(db).await?,
+        None => find_by_verified_email(db, &claims).await?,
+    };
+
+    let txn = db.begin().await?;
+
+    let user = match linked_user {
+        Some(user) => user,
+        None => {
+            let is_global_admin = verified_email
+                .as_ref()
+                .is_some_and(|email| admin_emails.contains(&email.to_lowercase()));
+
+            users::ActiveModel {
+                oidc_sub: Set(claims.subject.clone()),
+                // Left empty until a provider verifies an email, since roles are granted
+                // to whoever has a user's email
+                email: Set(verified_email.clone().unwrap_or_default()),
+                name: Set(claims.name),
+                given_name: Set(claims.given_name),
+                family_name: Set(claims.family_name),
+                picture: Set(claims.picture),
+                oidc_issuer: Set(claims.issuer.clone()),
+                is_global_admin: Set(is_global_admin),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?
+        }
+    };
+
+    user_identities::ActiveModel {
+        user_id: Set(user.id),
+        oidc_issuer: Set(claims.issuer),
+        oidc_sub: Set(claims.subject),
+        email: Set(claims.email),
+        email_verified: Set(claims.email_verified),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    txn.commit().await?;
+
+    if let Some(email) = &verified_email {
+        redeem_invites(db, &user, email).await?;
+    }
+
+    Ok(user)
+}
+
+/// Make every existing user listed in `ADMIN_EMAILS` a global admin, returning how many
+/// users were promoted. Admins granted through the API are left alone.
+pub async fn seed_global_admins(
+    db: &DatabaseConnection,
+    admin_emails: &[String],
+) -> Result<u64, DbErr> {
+    if admin_emails.is_empty() {
+        return Ok(0);
+    }
+
+    let result = Users::update_many()
+        .col_expr(users::Column::IsGlobalAdmin, Expr::value(true))
+        .col_expr(
+            users::Column::UpdatedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(users::Column::IsGlobalAdmin.eq(false))
+        .filter(Expr::expr(Func::lower(Expr::col(users::Column::Email))).is_in(admin_emails))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected)
+}
+
+/// Refresh the user's profile from the claims of their latest login. Claims the provider
+/// did not send leave the stored value alone, since another linked provider may have set it,
+/// and so does an email the provider has not verified.
+async fn sync_profile(
+    db: &DatabaseConnection,
+    user: users::Model,
+    claims: IdentityClaims,
+) -> Result<users::Model, DbErr> {
+    let mut active: users::ActiveModel = user.clone().into();
+
+    let email_verified = claims.email_verified;
+    if let Some(email) = claims.email.filter(|e| email_verified && *e != user.email) {
+        active.email = Set(email);
+    }
+
+    for (column, value, current) in [
+        (&mut active.name, claims.name, &user.name),
+        (&mut active.given_name, claims.given_name, &user.given_name),
+        (
+            &mut active.family_name,
+            claims.family_name,
+            &user.family_name,
+        ),
+        (&mut active.picture, claims.picture, &user.picture),
+    ] {
+        if value.is_some() && value != *current {
+            *column = Set(value);
+        }
+    }
+
+    if !active.is_changed() {
+        return Ok(user);
+    }
+
+    active.updated_at = Set(Utc::now().naive_utc());
+    active.update(db).await
+}
+
+/// Only emails verified by a provider are trusted for linking, on both the new identity and
+/// the existing one, so nobody can take over an account by claiming its email elsewhere
+async fn find_by_verified_email(
+    db: &DatabaseConnection,
+    claims: &IdentityClaims,
+) -> Result<Option<users::Model>, DbErr> {
+    let Some(email) = claims.email.as_ref().filter(|_| claims.email_verified) else {
+        return Ok(None);
+    };
+
+    let linked = UserIdentities::find()
+        .filter(user_identities::Column::Email.eq(email.to_lowercase()))
+        .filter(user_identities::Column::EmailVerified.eq(true))
+        .find_also_related(Users)
+        .one(db)
+        .await?;
+
+    Ok(linked.and_then(|(_, user)| user))
+}
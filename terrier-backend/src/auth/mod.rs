@@ -1,3 +1,7 @@
+pub mod dev;
 pub mod extractors;
 pub mod handlers;
-pub mod middleware;
+pub mod identity;
+pub mod oidc;
+pub mod tokens;
+pub mod user_cache;
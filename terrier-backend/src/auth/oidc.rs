@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use openidconnect::{
+    ClientId, ClientSecret, EndpointMaybeSet, EndpointNotSet, EndpointSet, IssuerUrl,
+    ProviderMetadataWithLogout, RedirectUrl, core::CoreClient, reqwest,
+};
+
+use crate::config::Config;
+
+pub type OidcClient = CoreClient<
+    EndpointSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointMaybeSet,
+    EndpointMaybeSet,
+>;
+
+pub struct OidcProvider {
+    pub id: String,
+    pub name: String,
+    pub metadata: ProviderMetadataWithLogout,
+    pub client_id: ClientId,
+    client_secret: ClientSecret,
+}
+
+/// The identity providers users can log in with, discovered at startup
+#[derive(Clone)]
+pub struct OidcProviders {
+    providers: Arc<Vec<OidcProvider>>,
+    pub http_client: reqwest::Client,
+    redirect_url: RedirectUrl,
+}
+
+impl OidcProviders {
+    pub async fn discover(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let http_client = reqwest::ClientBuilder::new()
+            // Following redirects opens the client up to SSRF vulnerabilities
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+
+        let mut providers = Vec::with_capacity(config.oidc_providers.len());
+        for provider in &config.oidc_providers {
+            let metadata = ProviderMetadataWithLogout::discover_async(
+                IssuerUrl::new(provider.issuer.clone())?,
+                &http_client,
+            )
+            .await
+            .map_err(|e| format!("OIDC discovery failed for {}: {e}", provider.id))?;
+
+            providers.push(OidcProvider {
+                id: provider.id.clone(),
+                name: provider.name.clone(),
+                metadata,
+                client_id: ClientId::new(provider.client_id.clone()),
+                client_secret: ClientSecret::new(provider.client_secret.clone()),
+            });
+        }
+
+        Ok(OidcProviders {
+            providers: Arc::new(providers),
+            http_client,
+            redirect_url: RedirectUrl::new(format!("{}/auth/callback", config.api_url))?,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&OidcProvider> {
+        self.providers.iter().find(|p| p.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &OidcProvider> {
+        self.providers.iter()
+    }
+
+    /// The provider to use when none was chosen, if there is no choice to make
+    pub fn only(&self) -> Option<&OidcProvider> {
+        match self.providers.as_slice() {
+            [provider] => Some(provider),
+            _ => None,
+        }
+    }
+
+    pub fn client(&self, provider: &OidcProvider) -> OidcClient {
+        CoreClient::from_provider_metadata(
+            provider.metadata.clone(),
+            provider.client_id.clone(),
+            Some(provider.client_secret.clone()),
+        )
+        .set_redirect_uri(self.redirect_url.clone())
+    }
+}
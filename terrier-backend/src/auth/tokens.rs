@@ -0,0 +1,121 @@
+use axum::http::{HeaderMap, header::AUTHORIZATION};
+use chrono::Utc;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, sea_query::Expr};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use crate::{
+    entities::{api_tokens, prelude::*, users},
+    error::AppError,
+};
+
+/// Prefix of every personal access token, so leaked tokens are easy to recognize
+pub const TOKEN_PREFIX: &str = "trr_";
+
+/// Number of characters of a token kept in plain text so users can tell tokens apart
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// What a token may do, where each scope includes the ones before it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenScope {
+    /// Safe (GET/HEAD) requests
+    Read,
+    /// Requests that modify data, and reads
+    Write,
+    /// Global admin endpoints, along with reads and writes, only grantable by global admins
+    Admin,
+}
+
+impl TokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::Read => "read",
+            TokenScope::Write => "write",
+            TokenScope::Admin => "admin",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "read" => Some(TokenScope::Read),
+            "write" => Some(TokenScope::Write),
+            "admin" => Some(TokenScope::Admin),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the comma-separated scopes column
+pub fn parse_scopes(scopes: &str) -> Vec<TokenScope> {
+    scopes.split(',').filter_map(TokenScope::parse).collect()
+}
+
+/// Serialize scopes into the comma-separated scopes column
+pub fn format_scopes(scopes: &[TokenScope]) -> String {
+    scopes
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Whether the token was granted `scope` or a scope that includes it
+pub fn has_scope(token: &api_tokens::Model, scope: TokenScope) -> bool {
+    parse_scopes(&token.scopes)
+        .into_iter()
+        .any(|granted| granted >= scope)
+}
+
+/// Generate a new random token, returning the raw token and its display prefix
+pub fn generate_token() -> (String, String) {
+    let bytes: [u8; 32] = rand::random();
+    let token = format!("{}{}", TOKEN_PREFIX, hex::encode(bytes));
+    let prefix = token[..DISPLAY_PREFIX_LEN].to_string();
+
+    (token, prefix)
+}
+
+/// Tokens are high-entropy, so a fast unsalted hash is sufficient
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Extract the raw token from an `Authorization: Bearer` header, if present
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// Look up the user a bearer token belongs to, rejecting unknown, expired and revoked tokens
+pub async fn authenticate(
+    db: &DatabaseConnection,
+    token: &str,
+) -> Result<(users::Model, api_tokens::Model), AppError> {
+    let (token, user) = ApiTokens::find()
+        .filter(api_tokens::Column::TokenHash.eq(hash_token(token)))
+        .find_also_related(Users)
+        .one(db)
+        .await?
+        .ok_or(AppError::Unauthorized)?;
+
+    let user = user.ok_or(AppError::Unauthorized)?;
+    let now = Utc::now().naive_utc();
+
+    if token.revoked_at.is_some() || token.expires_at <= now {
+        return Err(AppError::Unauthorized);
+    }
+
+    ApiTokens::update_many()
+        .col_expr(api_tokens::Column::LastUsedAt, Expr::value(now))
+        .filter(api_tokens::Column::Id.eq(token.id))
+        .exec(db)
+        .await?;
+
+    Ok((user, token))
+}
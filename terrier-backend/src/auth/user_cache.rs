@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use moka::future::Cache;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait};
+
+use crate::entities::{prelude::*, users};
+
+/// Upper bound on how stale a cached user can be if an update misses invalidation
+const TIME_TO_LIVE: Duration = Duration::from_secs(5 * 60);
+const MAX_CAPACITY: u64 = 10_000;
+
+/// Users by ID, so authenticating a session is not a database round-trip on every request.
+///
+/// Anything that modifies a user must call [`UserCache::invalidate`].
+#[derive(Clone)]
+pub struct UserCache(Cache<i32, users::Model>);
+
+impl Default for UserCache {
+    fn default() -> Self {
+        UserCache(
+            Cache::builder()
+                .max_capacity(MAX_CAPACITY)
+                .time_to_live(TIME_TO_LIVE)
+                .build(),
+        )
+    }
+}
+
+impl UserCache {
+    pub async fn get(
+        &self,
+        db: &DatabaseConnection,
+        user_id: i32,
+    ) -> Result<Option<users::Model>, DbErr> {
+        if let Some(user) = self.0.get(&user_id).await {
+            return Ok(Some(user));
+        }
+
+        let user = Users::find_by_id(user_id).one(db).await?;
+        if let Some(user) = &user {
+            self.0.insert(user_id, user.clone()).await;
+        }
+
+        Ok(user)
+    }
+
+    pub async fn invalidate(&self, user_id: i32) {
+        self.0.invalidate(&user_id).await;
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{Json, extract::State};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    auth::extractors::HackathonRole,
+    entities::{prelude::*, user_hackathon_roles},
+    error::AppError,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct BadgeResponse {
+    /// Encoded in the QR code on the participant's badge
+    pub token: String,
+}
+
+/// Find the participant whose badge was scanned in a hackathon
+pub async fn find_by_badge(
+    state: &AppState,
+    hackathon_id: i32,
+    token: &str,
+) -> Result<user_hackathon_roles::Model, AppError> {
+    UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon_id))
+        .filter(user_hackathon_roles::Column::BadgeToken.eq(token.trim()))
+        .filter(user_hackathon_roles::Column::Role.eq("participant"))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("No participant with this badge"))
+}
+
+/// Get the token for the current participant's badge QR code
+///
+/// The token is generated the first time it is requested and stays the same afterwards, so
+/// printed badges keep working.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/badge",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "The badge token", body = BadgeResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a participant in this hackathon"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn get_badge(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<BadgeResponse>, AppError> {
+    // Admins get their role from their account rather than a row, so they have no badge
+    if role.role != "participant" {
+        return Err(AppError::Forbidden);
+    }
+
+    let participant = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.eq(role.user_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::Forbidden)?;
+
+    if let Some(token) = participant.badge_token {
+        return Ok(Json(BadgeResponse { token }));
+    }
+
+    let token = hex::encode(rand::random::<[u8; 16]>());
+    let mut active: user_hackathon_roles::ActiveModel = participant.into();
+    active.badge_token = Set(Some(token.clone()));
+    active.update(&state.db).await?;
+
+    Ok(Json(BadgeResponse { token }))
+}
@@ -1,12 +1,41 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthMode {
+    /// Log in through the configured OIDC providers
+    Oidc,
+    /// Log in as any user without a provider, for local development and tests only
+    Dev,
+}
+
+#[derive(Clone, Debug)]
+pub struct OidcProviderConfig {
+    /// Identifier used to pick the provider, as in `/auth/login?provider=...`
+    pub id: String,
+    /// Name shown in the provider chooser
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub app_url: String,
     pub api_url: String,
     pub database_url: String,
-    pub oidc_issuer: String,
-    pub oidc_client_id: String,
-    pub oidc_client_secret: String,
+    pub auth_mode: AuthMode,
+    pub oidc_providers: Vec<OidcProviderConfig>,
     pub admin_emails: Vec<String>,
+    /// Uploads are disabled without it
+    pub storage: Option<StorageConfig>,
+}
+
+/// An S3-compatible bucket for uploaded files, MinIO in the default deployment
+#[derive(Clone, Debug)]
+pub struct StorageConfig {
+    pub endpoint: String,
+    pub access_key: String,
+    pub secret_key: String,
+    pub bucket: String,
 }
 
 impl Config {
@@ -20,14 +49,83 @@ impl Config {
             .filter(|s| !s.is_empty())
             .collect();
 
+        let auth_mode = match dotenvy::var("AUTH_MODE").as_deref() {
+            Ok("oidc") | Err(_) => AuthMode::Oidc,
+            Ok("dev") => AuthMode::Dev,
+            Ok(other) => return Err(format!("invalid AUTH_MODE: {other}").into()),
+        };
+
+        // OIDC settings are only required when logging in through a provider
+        let oidc_providers = match auth_mode {
+            AuthMode::Oidc => oidc_providers_from_env()?,
+            AuthMode::Dev => Vec::new(),
+        };
+
         Ok(Config {
             app_url: dotenvy::var("APP_URL")?,
             api_url: dotenvy::var("API_URL")?,
             database_url: dotenvy::var("DATABASE_URL")?,
-            oidc_issuer: dotenvy::var("OIDC_ISSUER")?,
-            oidc_client_id: dotenvy::var("OIDC_CLIENT_ID")?,
-            oidc_client_secret: dotenvy::var("OIDC_CLIENT_SECRET")?,
+            auth_mode,
+            oidc_providers,
             admin_emails,
+            storage: storage_from_env()?,
         })
     }
 }
+
+/// Read the bucket from `S3_ENDPOINT`, `S3_ACCESS_KEY`, `S3_SECRET_KEY` and an optional
+/// `S3_BUCKET_NAME`, or nothing if `S3_ENDPOINT` is not set
+fn storage_from_env() -> Result<Option<StorageConfig>, Box<dyn std::error::Error>> {
+    let Ok(endpoint) = dotenvy::var("S3_ENDPOINT") else {
+        return Ok(None);
+    };
+
+    Ok(Some(StorageConfig {
+        endpoint,
+        access_key: dotenvy::var("S3_ACCESS_KEY")?,
+        secret_key: dotenvy::var("S3_SECRET_KEY")?,
+        bucket: dotenvy::var("S3_BUCKET_NAME").unwrap_or_else(|_| "terrier-files".to_string()),
+    }))
+}
+
+/// Read providers listed in `OIDC_PROVIDERS` (e.g. `cmu,google`), each configured with
+/// `OIDC_<ID>_ISSUER`, `OIDC_<ID>_CLIENT_ID`, `OIDC_<ID>_CLIENT_SECRET` and an optional
+/// `OIDC_<ID>_NAME`. Without `OIDC_PROVIDERS`, a single provider is read from `OIDC_ISSUER`,
+/// `OIDC_CLIENT_ID` and `OIDC_CLIENT_SECRET`.
+fn oidc_providers_from_env() -> Result<Vec<OidcProviderConfig>, Box<dyn std::error::Error>> {
+    let Some(ids) = dotenvy::var("OIDC_PROVIDERS")
+        .ok()
+        .filter(|ids| !ids.trim().is_empty())
+    else {
+        return Ok(vec![OidcProviderConfig {
+            id: "default".to_string(),
+            name: dotenvy::var("OIDC_NAME").unwrap_or_else(|_| "Single sign-on".to_string()),
+            issuer: dotenvy::var("OIDC_ISSUER")?,
+            client_id: dotenvy::var("OIDC_CLIENT_ID")?,
+            client_secret: dotenvy::var("OIDC_CLIENT_SECRET")?,
+        }]);
+    };
+
+    let providers = ids
+        .split(',')
+        .map(|id| id.trim().to_lowercase())
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            let var = |key: &str| dotenvy::var(format!("OIDC_{}_{key}", id.to_uppercase()));
+
+            Ok(OidcProviderConfig {
+                name: var("NAME").unwrap_or_else(|_| id.clone()),
+                issuer: var("ISSUER")?,
+                client_id: var("CLIENT_ID")?,
+                client_secret: var("CLIENT_SECRET")?,
+                id,
+            })
+        })
+        .collect::<Result<Vec<_>, dotenvy::Error>>()?;
+
+    if providers.is_empty() {
+        return Err("OIDC_PROVIDERS must list at least one provider".into());
+    }
+
+    Ok(providers)
+}
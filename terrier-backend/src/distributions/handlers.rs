@@ -0,0 +1,856 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    analytics::handlers::users_with_roles,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    badges::handlers::find_by_badge,
+    entities::{
+        distribution_claims, distribution_items, prelude::*, user_hackathon_roles, user_profiles,
+        users,
+    },
+    error::AppError,
+    hackathons::{handlers::require_feature, phase::HackathonPhase, roles},
+    pagination::{self, Page, PageParams, Sort},
+    profiles::handlers::parse_dietary_restrictions,
+    validation::{MAX_NAME_LENGTH, Path, Query, ValidJson, Validate, Validator},
+};
+
+/// Every kind of item that can be handed out
+pub const ITEM_KINDS: [&str; 2] = ["meal", "swag"];
+
+const CLAIM_SORT: Sort<distribution_claims::Column> = Sort {
+    fields: &[("created_at", distribution_claims::Column::CreatedAt)],
+    default: "-created_at",
+    id: distribution_claims::Column::Id,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct DistributionItemInfo {
+    pub id: i32,
+    pub name: String,
+    /// Either `meal` or `swag`
+    pub kind: String,
+    /// How many times each participant can claim the item
+    pub per_person_limit: i32,
+    /// Total that can be handed out, if stock is limited
+    pub quantity: Option<i32>,
+    pub claimed: u64,
+    pub remaining: Option<i64>,
+}
+
+impl DistributionItemInfo {
+    fn new(item: distribution_items::Model, claimed: u64) -> Self {
+        DistributionItemInfo {
+            remaining: item.quantity.map(|q| i64::from(q) - claimed as i64),
+            id: item.id,
+            name: item.name,
+            kind: item.kind,
+            per_person_limit: item.per_person_limit,
+            quantity: item.quantity,
+            claimed,
+        }
+    }
+}
+
+/// Check the hackathon uses the meals and swag module
+async fn require_meals(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    require_feature(state, role.hackathon_id, |f| f.meals).await?;
+    Ok(())
+}
+
+/// Check the caller can manage the hackathon's items, which cannot change once archived
+async fn require_meals_manager(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+    require_meals(state, role).await
+}
+
+async fn find_item(
+    state: &AppState,
+    role: &HackathonRole,
+    item_id: i32,
+) -> Result<distribution_items::Model, AppError> {
+    DistributionItems::find_by_id(item_id)
+        .filter(distribution_items::Column::HackathonId.eq(role.hackathon_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Item not found"))
+}
+
+async fn count_claims(db: &impl ConnectionTrait, item_id: i32) -> Result<u64, AppError> {
+    Ok(DistributionClaims::find()
+        .filter(distribution_claims::Column::ItemId.eq(item_id))
+        .count(db)
+        .await?)
+}
+
+async fn require_free_name(
+    db: &impl ConnectionTrait,
+    role: &HackathonRole,
+    name: &str,
+) -> Result<(), AppError> {
+    let existing = DistributionItems::find()
+        .filter(distribution_items::Column::HackathonId.eq(role.hackathon_id))
+        .filter(distribution_items::Column::Name.eq(name))
+        .one(db)
+        .await?;
+
+    match existing {
+        Some(_) => Err(AppError::Conflict("An item with this name already exists")),
+        None => Ok(()),
+    }
+}
+
+/// List the meals and swag handed out at the hackathon
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/distributions",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Items by name", body = Vec<DistributionItemInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a volunteer or organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or meals and swag are disabled")
+    ),
+    tag = "Distribution"
+)]
+pub async fn list_items(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<DistributionItemInfo>>, AppError> {
+    if !role.is_volunteer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_meals(&state, &role).await?;
+
+    let items = DistributionItems::find()
+        .filter(distribution_items::Column::HackathonId.eq(role.hackathon_id))
+        .order_by_asc(distribution_items::Column::Name)
+        .all(&state.db)
+        .await?;
+
+    let mut claimed: HashMap<i32, u64> = HashMap::new();
+    for claim in DistributionClaims::find()
+        .filter(distribution_claims::Column::ItemId.is_in(items.iter().map(|i| i.id)))
+        .all(&state.db)
+        .await?
+    {
+        *claimed.entry(claim.item_id).or_default() += 1;
+    }
+
+    Ok(Json(
+        items
+            .into_iter()
+            .map(|item| {
+                let claimed = claimed.get(&item.id).copied().unwrap_or(0);
+                DistributionItemInfo::new(item, claimed)
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateDistributionItemRequest {
+    pub name: String,
+    /// Either `meal` or `swag`
+    pub kind: String,
+    /// Defaults to 1
+    pub per_person_limit: Option<i32>,
+    /// Total that can be handed out, if stock is limited
+    pub quantity: Option<i32>,
+}
+
+impl Validate for CreateDistributionItemRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name, MAX_NAME_LENGTH);
+        v.one_of("kind", &self.kind, &ITEM_KINDS);
+        if let Some(limit) = self.per_person_limit {
+            v.at_least("per_person_limit", limit, 1);
+        }
+        if let Some(quantity) = self.quantity {
+            v.at_least("quantity", quantity, 0);
+        }
+    }
+}
+
+/// Add a meal or piece of swag to hand out
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/distributions",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = CreateDistributionItemRequest,
+    responses(
+        (status = 200, description = "Item created", body = DistributionItemInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or meals and swag are disabled"),
+        (status = 409, description = "An item with this name exists, or the hackathon is archived"),
+        (status = 422, description = "Empty name, unknown kind, or limit or quantity out of range")
+    ),
+    tag = "Distribution"
+)]
+pub async fn create_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<CreateDistributionItemRequest>,
+) -> Result<Json<DistributionItemInfo>, AppError> {
+    require_meals_manager(&state, &role).await?;
+
+    let name = req.name.trim();
+    let per_person_limit = req.per_person_limit.unwrap_or(1);
+    require_free_name(&state.db, &role, name).await?;
+
+    let item = distribution_items::ActiveModel {
+        hackathon_id: Set(role.hackathon_id),
+        name: Set(name.to_string()),
+        kind: Set(req.kind),
+        per_person_limit: Set(per_person_limit),
+        quantity: Set(req.quantity),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(Json(DistributionItemInfo::new(item, 0)))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateDistributionItemRequest {
+    pub name: Option<String>,
+    pub per_person_limit: Option<i32>,
+    /// Total that can be handed out, or a negative number to remove the limit
+    pub quantity: Option<i32>,
+}
+
+impl Validate for UpdateDistributionItemRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            v.required("name", name, MAX_NAME_LENGTH);
+        }
+        if let Some(limit) = self.per_person_limit {
+            v.at_least("per_person_limit", limit, 1);
+        }
+    }
+}
+
+/// Update a meal or piece of swag
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}/distributions/{item_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID")
+    ),
+    request_body = UpdateDistributionItemRequest,
+    responses(
+        (status = 200, description = "Item updated", body = DistributionItemInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Item not found, or meals and swag are disabled"),
+        (status = 409, description = "Name is taken, quantity is below the number already claimed, or the hackathon is archived"),
+        (status = 422, description = "Empty name, or limit out of range")
+    ),
+    tag = "Distribution"
+)]
+pub async fn update_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<UpdateDistributionItemRequest>,
+) -> Result<Json<DistributionItemInfo>, AppError> {
+    let (_, item_id) = params;
+    require_meals_manager(&state, &role).await?;
+
+    find_item(&state, &role, item_id).await?;
+
+    // Locking the item stops claims from being made while the quantity is checked
+    let txn = state.db.begin().await?;
+    let item = DistributionItems::find_by_id(item_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or(AppError::NotFound("Item not found"))?;
+    let claimed = count_claims(&txn, item.id).await?;
+    let mut active: distribution_items::ActiveModel = item.clone().into();
+
+    if let Some(name) = req.name {
+        let name = name.trim();
+        if name != item.name {
+            require_free_name(&txn, &role, name).await?;
+        }
+        active.name = Set(name.to_string());
+    }
+
+    if let Some(limit) = req.per_person_limit {
+        active.per_person_limit = Set(limit);
+    }
+
+    if let Some(quantity) = req.quantity {
+        if quantity >= 0 && (quantity as u64) < claimed {
+            return Err(AppError::Conflict(
+                "More units have already been handed out",
+            ));
+        }
+        active.quantity = Set(Some(quantity).filter(|q| *q >= 0));
+    }
+
+    active.updated_at = Set(Utc::now().naive_utc());
+
+    let item = active.update(&txn).await?;
+    txn.commit().await?;
+
+    Ok(Json(DistributionItemInfo::new(item, claimed)))
+}
+
+/// Delete a meal or piece of swag, along with its claims
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/distributions/{item_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 204, description = "Item deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Item not found, or meals and swag are disabled"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Distribution"
+)]
+pub async fn delete_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, item_id) = params;
+    require_meals_manager(&state, &role).await?;
+
+    let item = find_item(&state, &role, item_id).await?;
+    DistributionItems::delete_by_id(item.id)
+        .exec(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ClaimItemRequest {
+    /// Token from the participant's badge QR code
+    pub token: String,
+}
+
+impl Validate for ClaimItemRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("token", &self.token, MAX_NAME_LENGTH);
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ClaimInfo {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: Option<String>,
+    pub email: String,
+    /// So volunteers serving a meal can hand out the right food
+    pub dietary_restrictions: Vec<String>,
+    pub dietary_notes: Option<String>,
+    pub claimed_at: NaiveDateTime,
+}
+
+impl ClaimInfo {
+    fn new(
+        claim: distribution_claims::Model,
+        user: users::Model,
+        profile: Option<user_profiles::Model>,
+    ) -> Self {
+        ClaimInfo {
+            id: claim.id,
+            user_id: user.id,
+            name: user.name,
+            email: user.email,
+            dietary_restrictions: profile
+                .as_ref()
+                .map(|p| parse_dietary_restrictions(&p.dietary_restrictions))
+                .unwrap_or_default(),
+            dietary_notes: profile.and_then(|p| p.dietary_notes),
+            claimed_at: claim.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ClaimResponse {
+    #[serde(flatten)]
+    pub claim: ClaimInfo,
+    /// How many times the participant has now claimed the item
+    pub claims: u64,
+    pub per_person_limit: i32,
+}
+
+/// Record that a participant received an item by scanning their badge
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/distributions/{item_id}/claims",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID")
+    ),
+    request_body = ClaimItemRequest,
+    responses(
+        (status = 200, description = "Claim recorded", body = ClaimResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a volunteer or organizer of this hackathon"),
+        (status = 404, description = "Item not found, no participant with this badge, or meals and swag are disabled"),
+        (status = 409, description = "Participant reached the per-person limit, the item ran out, or the hackathon is archived"),
+        (status = 422, description = "Missing badge token")
+    ),
+    tag = "Distribution"
+)]
+pub async fn claim_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<ClaimItemRequest>,
+) -> Result<Json<ClaimResponse>, AppError> {
+    let (_, item_id) = params;
+    if !role.is_volunteer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_meals(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let item = find_item(&state, &role, item_id).await?;
+    let participant = find_by_badge(&state, role.hackathon_id, &req.token).await?;
+
+    let txn = state.db.begin().await?;
+
+    // Claims of the item wait for each other here, so a double scan or two volunteers
+    // scanning at once cannot both get past the limits
+    let item = DistributionItems::find_by_id(item.id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or(AppError::NotFound("Item not found"))?;
+
+    let previous = DistributionClaims::find()
+        .filter(distribution_claims::Column::ItemId.eq(item.id))
+        .filter(distribution_claims::Column::UserId.eq(participant.user_id))
+        .count(&txn)
+        .await?;
+
+    if previous >= item.per_person_limit as u64 {
+        return Err(AppError::Conflict(
+            "This participant has already received their share",
+        ));
+    }
+
+    if let Some(quantity) = item.quantity
+        && count_claims(&txn, item.id).await? >= quantity as u64
+    {
+        return Err(AppError::Conflict("This item has run out"));
+    }
+
+    let claim = distribution_claims::ActiveModel {
+        item_id: Set(item.id),
+        user_id: Set(participant.user_id),
+        scanned_by: Set(Some(role.user_id)),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    txn.commit().await?;
+
+    let (user, profile) = Users::find_by_id(participant.user_id)
+        .find_also_related(UserProfiles)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Participant not found"))?;
+
+    Ok(Json(ClaimResponse {
+        claim: ClaimInfo::new(claim, user, profile),
+        claims: previous + 1,
+        per_person_limit: item.per_person_limit,
+    }))
+}
+
+/// List who has claimed an item, newest first
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/distributions/{item_id}/claims",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Claims, newest first", body = Page<ClaimInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a volunteer or organizer of this hackathon"),
+        (status = 404, description = "Item not found, or meals and swag are disabled"),
+        (status = 422, description = "Invalid page, sort or cursor")
+    ),
+    tag = "Distribution"
+)]
+pub async fn list_claims(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<ClaimInfo>>, AppError> {
+    let (_, item_id) = params;
+    if !role.is_volunteer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_meals(&state, &role).await?;
+
+    let item = find_item(&state, &role, item_id).await?;
+    let select = DistributionClaims::find().filter(distribution_claims::Column::ItemId.eq(item.id));
+    let page = pagination::paginate(&state.db, select, &page, &CLAIM_SORT, |c| c).await?;
+
+    let users: HashMap<i32, (users::Model, Option<user_profiles::Model>)> = Users::find()
+        .find_also_related(UserProfiles)
+        .filter(users::Column::Id.is_in(page.items.iter().map(|c| c.user_id)))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|(u, p)| (u.id, (u, p)))
+        .collect();
+
+    Ok(Json(page.map(|claims| {
+        claims
+            .into_iter()
+            .filter_map(|claim| {
+                let (user, profile) = users.get(&claim.user_id)?.clone();
+                Some(ClaimInfo::new(claim, user, profile))
+            })
+            .collect()
+    })))
+}
+
+/// Undo a claim recorded by mistake
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/distributions/{item_id}/claims/{claim_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID"),
+        ("claim_id" = i32, Path, description = "Claim ID")
+    ),
+    responses(
+        (status = 204, description = "Claim deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a volunteer or organizer of this hackathon"),
+        (status = 404, description = "Claim not found, or meals and swag are disabled"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Distribution"
+)]
+pub async fn delete_claim(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, item_id, claim_id) = params;
+    if !role.is_volunteer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_meals(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let item = find_item(&state, &role, item_id).await?;
+    let result = DistributionClaims::delete_many()
+        .filter(distribution_claims::Column::Id.eq(claim_id))
+        .filter(distribution_claims::Column::ItemId.eq(item.id))
+        .exec(&state.db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("Claim not found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Default, Serialize, ToSchema)]
+pub struct DietaryCount {
+    /// Participants with this restriction
+    pub participants: u64,
+    /// Of those, how many have claimed the item
+    pub claimed: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DistributionSummary {
+    #[serde(flatten)]
+    pub item: DistributionItemInfo,
+    pub participants: u64,
+    /// Participants who claimed the item at least once
+    pub participants_claimed: u64,
+    /// Counts by dietary restriction from participant profiles, with `unspecified` for
+    /// participants who have not filled them in
+    pub dietary_restrictions: BTreeMap<String, DietaryCount>,
+}
+
+/// Get how many participants have claimed an item, by dietary restriction
+///
+/// Catering can compare the counts against what is left to see which options are running
+/// low.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/distributions/{item_id}/summary",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Claim counts", body = DistributionSummary),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a volunteer or organizer of this hackathon"),
+        (status = 404, description = "Item not found, or meals and swag are disabled")
+    ),
+    tag = "Distribution"
+)]
+pub async fn get_item_summary(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<DistributionSummary>, AppError> {
+    let (_, item_id) = params;
+    if !role.is_volunteer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_meals(&state, &role).await?;
+
+    let item = find_item(&state, &role, item_id).await?;
+
+    let participant_ids: Vec<i32> = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::Role.eq("participant"))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|r| r.user_id)
+        .collect();
+
+    let profiles: HashMap<i32, user_profiles::Model> = UserProfiles::find()
+        .filter(
+            user_profiles::Column::UserId
+                .in_subquery(users_with_roles(role.hackathon_id, &["participant"])),
+        )
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|p| (p.user_id, p))
+        .collect();
+
+    let claims = DistributionClaims::find()
+        .filter(distribution_claims::Column::ItemId.eq(item.id))
+        .all(&state.db)
+        .await?;
+    let claimed_by: HashSet<i32> = claims.iter().map(|c| c.user_id).collect();
+
+    let mut dietary_restrictions: BTreeMap<String, DietaryCount> = BTreeMap::new();
+    let mut participants_claimed = 0;
+
+    for user_id in &participant_ids {
+        let claimed = claimed_by.contains(user_id);
+        if claimed {
+            participants_claimed += 1;
+        }
+
+        let mut restrictions = profiles
+            .get(user_id)
+            .map(|p| parse_dietary_restrictions(&p.dietary_restrictions))
+            .unwrap_or_default();
+        if restrictions.is_empty() {
+            restrictions.push("unspecified".to_string());
+        }
+
+        for restriction in restrictions {
+            let count = dietary_restrictions.entry(restriction).or_default();
+            count.participants += 1;
+            if claimed {
+                count.claimed += 1;
+            }
+        }
+    }
+
+    Ok(Json(DistributionSummary {
+        item: DistributionItemInfo::new(item, claims.len() as u64),
+        participants: participant_ids.len() as u64,
+        participants_claimed,
+        dietary_restrictions,
+    }))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct VolunteerInfo {
+    pub user_id: i32,
+    pub email: String,
+    pub name: Option<String>,
+}
+
+/// List a hackathon's volunteers
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/volunteers",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Volunteers", body = Vec<VolunteerInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Distribution"
+)]
+pub async fn list_volunteers(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<VolunteerInfo>>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    let volunteers = Users::find()
+        .inner_join(UserHackathonRoles)
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::Role.eq("volunteer"))
+        .order_by_asc(users::Column::Email)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        volunteers
+            .into_iter()
+            .map(|u| VolunteerInfo {
+                user_id: u.id,
+                email: u.email,
+                name: u.name,
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddVolunteerRequest {
+    /// Email of an existing user
+    pub email: String,
+}
+
+impl Validate for AddVolunteerRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", self.email.trim());
+    }
+}
+
+/// Give a user the volunteer role in the hackathon
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/volunteers",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = AddVolunteerRequest,
+    responses(
+        (status = 200, description = "User is now a volunteer", body = VolunteerInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "No user with that email"),
+        (status = 409, description = "Several users have that email, user has another role, or the hackathon is archived"),
+        (status = 422, description = "Not an email address")
+    ),
+    tag = "Distribution"
+)]
+pub async fn add_volunteer(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<AddVolunteerRequest>,
+) -> Result<Json<VolunteerInfo>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let user = roles::assign_by_email(&state, &role, &req.email, "volunteer").await?;
+
+    Ok(Json(VolunteerInfo {
+        user_id: user.id,
+        email: user.email,
+        name: user.name,
+    }))
+}
+
+/// Remove a user's volunteer role in the hackathon
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/volunteers/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Volunteer removed"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "User is not a volunteer"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Distribution"
+)]
+pub async fn remove_volunteer(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, user_id) = params;
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let txn = state.db.begin().await?;
+    if !roles::unassign(&txn, role.hackathon_id, user_id, "volunteer").await? {
+        return Err(AppError::NotFound(
+            "User is not a volunteer in this hackathon",
+        ));
+    }
+
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "volunteer.remove", "user", user_id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
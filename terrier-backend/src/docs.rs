@@ -1,12 +1,15 @@
 use utoipa::{
     Modify, OpenApi,
     openapi::{
-        SecurityRequirement,
-        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+        Content, Ref, RefOr, Response, SecurityRequirement,
+        security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
     },
 };
 
-use crate::{auth, hackathons};
+use crate::{
+    admins, analytics, audit, auth, badges, distributions, error, exports, hackathons, hardware,
+    invites, leads, mentorship, organizations, profiles, reimbursements, resumes, sponsors, tokens,
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -14,21 +17,232 @@ use crate::{auth, hackathons};
         auth::handlers::status,
         auth::handlers::login,
         auth::handlers::logout,
+        auth::handlers::callback,
+        auth::handlers::providers,
+        tokens::handlers::list_tokens,
+        tokens::handlers::create_token,
+        tokens::handlers::revoke_token,
+        admins::handlers::list_admins,
+        admins::handlers::grant_admin,
+        admins::handlers::revoke_admin,
+        audit::handlers::list_audit_log,
+        organizations::handlers::list_organizations,
+        organizations::handlers::create_organization,
+        organizations::handlers::get_organization,
+        organizations::handlers::update_organization,
+        organizations::handlers::delete_organization,
+        organizations::handlers::list_organization_hackathons,
+        organizations::handlers::list_organization_admins,
+        organizations::handlers::add_organization_admin,
+        organizations::handlers::remove_organization_admin,
         hackathons::handlers::list_public_hackathons,
         hackathons::handlers::get_user_role,
         hackathons::handlers::create_hackathon,
+        hackathons::handlers::get_hackathon,
+        hackathons::handlers::update_hackathon,
+        hackathons::handlers::set_published,
+        hackathons::handlers::set_archived,
+        hackathons::handlers::set_phase,
+        hackathons::handlers::get_settings,
+        hackathons::handlers::update_settings,
+        hackathons::handlers::get_settings_history,
+        hackathons::handlers::delete_hackathon,
+        hackathons::handlers::duplicate_hackathon,
+        profiles::handlers::get_profile,
+        profiles::handlers::update_profile,
+        profiles::handlers::get_hackathon_profile,
+        profiles::handlers::get_profile_summary,
+        resumes::handlers::upload_resume,
+        resumes::handlers::get_resume,
+        resumes::handlers::delete_resume,
+        resumes::handlers::list_resumes,
+        resumes::handlers::download_resume,
+        resumes::handlers::download_resume_book,
+        resumes::handlers::get_resume_access_log,
+        sponsors::handlers::list_sponsor_tiers,
+        sponsors::handlers::create_sponsor_tier,
+        sponsors::handlers::update_sponsor_tier,
+        sponsors::handlers::delete_sponsor_tier,
+        sponsors::handlers::list_sponsors,
+        sponsors::handlers::create_sponsor,
+        sponsors::handlers::get_my_sponsor,
+        sponsors::handlers::get_sponsor,
+        sponsors::handlers::update_sponsor,
+        sponsors::handlers::delete_sponsor,
+        sponsors::handlers::add_sponsor_representative,
+        sponsors::handlers::remove_sponsor_representative,
+        badges::handlers::get_badge,
+        leads::handlers::scan_lead,
+        leads::handlers::list_leads,
+        leads::handlers::update_lead,
+        leads::handlers::delete_lead,
+        leads::handlers::export_leads,
+        mentorship::handlers::create_ticket,
+        mentorship::handlers::list_tickets,
+        mentorship::handlers::get_ticket,
+        mentorship::handlers::claim_ticket,
+        mentorship::handlers::release_ticket,
+        mentorship::handlers::resolve_ticket,
+        mentorship::handlers::cancel_ticket,
+        mentorship::handlers::get_ticket_stats,
+        mentorship::handlers::stream_ticket_events,
+        mentorship::handlers::list_mentors,
+        mentorship::handlers::add_mentor,
+        mentorship::handlers::remove_mentor,
+        hardware::handlers::list_items,
+        hardware::handlers::create_item,
+        hardware::handlers::update_item,
+        hardware::handlers::delete_item,
+        hardware::handlers::request_item,
+        hardware::handlers::list_loans,
+        hardware::handlers::approve_loan,
+        hardware::handlers::reject_loan,
+        hardware::handlers::check_out_loan,
+        hardware::handlers::return_loan,
+        hardware::handlers::cancel_loan,
+        reimbursements::handlers::create_reimbursement,
+        reimbursements::handlers::get_my_reimbursement,
+        reimbursements::handlers::list_reimbursements,
+        reimbursements::handlers::get_reimbursement,
+        reimbursements::handlers::update_reimbursement,
+        reimbursements::handlers::withdraw_reimbursement,
+        reimbursements::handlers::upload_receipt,
+        reimbursements::handlers::download_receipt,
+        reimbursements::handlers::delete_receipt,
+        reimbursements::handlers::review_reimbursement,
+        reimbursements::handlers::get_reimbursement_summary,
+        reimbursements::handlers::export_payouts,
+        reimbursements::handlers::list_finance_members,
+        reimbursements::handlers::grant_finance,
+        reimbursements::handlers::revoke_finance,
+        distributions::handlers::list_items,
+        distributions::handlers::create_item,
+        distributions::handlers::update_item,
+        distributions::handlers::delete_item,
+        distributions::handlers::claim_item,
+        distributions::handlers::list_claims,
+        distributions::handlers::delete_claim,
+        distributions::handlers::get_item_summary,
+        distributions::handlers::list_volunteers,
+        distributions::handlers::add_volunteer,
+        distributions::handlers::remove_volunteer,
+        exports::handlers::export_participants,
+        invites::handlers::import_roles,
+        invites::handlers::list_invites,
+        invites::handlers::revoke_invite,
+        analytics::handlers::get_analytics,
     ),
     components(schemas(
+        error::ErrorBody,
+        error::FieldError,
         auth::handlers::LoginQuery,
+        auth::handlers::ProviderInfo,
         auth::handlers::UserInfo,
+        auth::tokens::TokenScope,
+        tokens::handlers::TokenInfo,
+        tokens::handlers::CreateTokenRequest,
+        tokens::handlers::CreatedTokenResponse,
+        admins::handlers::AdminInfo,
+        audit::handlers::AuditLogEntry,
+        admins::handlers::GrantAdminRequest,
+        organizations::handlers::OrganizationInfo,
+        organizations::handlers::CreateOrganizationRequest,
+        organizations::handlers::UpdateOrganizationRequest,
+        organizations::handlers::OrganizationAdminInfo,
+        organizations::handlers::AddOrganizationAdminRequest,
         hackathons::handlers::HackathonInfo,
         hackathons::handlers::UserRoleResponse,
         hackathons::handlers::CreateHackathonRequest,
+        hackathons::handlers::UpdateHackathonRequest,
+        hackathons::handlers::PublishRequest,
+        hackathons::handlers::ArchiveRequest,
+        hackathons::handlers::SetPhaseRequest,
+        hackathons::handlers::DuplicateHackathonRequest,
+        hackathons::handlers::SettingsChange,
+        hackathons::phase::HackathonPhase,
+        hackathons::settings::HackathonSettings,
+        hackathons::settings::Branding,
+        hackathons::settings::Features,
+        hackathons::settings::JudgingMode,
+        hackathons::settings::Reimbursements,
+        hackathons::settings::ReimbursementRegion,
+        profiles::handlers::ProfileInfo,
+        profiles::handlers::ProfileResponse,
+        profiles::handlers::UpdateProfileRequest,
+        profiles::handlers::ProfileSummary,
+        resumes::handlers::ResumeBookEntry,
+        resumes::handlers::ResumeAccess,
+        sponsors::handlers::SponsorTierInfo,
+        sponsors::handlers::SponsorInfo,
+        sponsors::handlers::SponsorRepresentativeInfo,
+        sponsors::handlers::SponsorDetails,
+        sponsors::handlers::CreateSponsorTierRequest,
+        sponsors::handlers::UpdateSponsorTierRequest,
+        sponsors::handlers::SponsorRequest,
+        sponsors::handlers::AddSponsorRepresentativeRequest,
+        badges::handlers::BadgeResponse,
+        leads::handlers::LeadInfo,
+        leads::handlers::ScanLeadRequest,
+        leads::handlers::UpdateLeadRequest,
+        mentorship::handlers::TicketUser,
+        mentorship::handlers::TicketInfo,
+        mentorship::handlers::CreateTicketRequest,
+        mentorship::handlers::TicketStats,
+        mentorship::handlers::MentorInfo,
+        mentorship::handlers::AddMentorRequest,
+        hardware::handlers::HardwareItemInfo,
+        hardware::handlers::Borrower,
+        hardware::handlers::LoanInfo,
+        hardware::handlers::HardwareItemRequest,
+        hardware::handlers::UpdateHardwareItemRequest,
+        hardware::handlers::RequestHardwareRequest,
+        hardware::handlers::CheckOutLoanRequest,
+        reimbursements::handlers::ReceiptInfo,
+        reimbursements::handlers::Requester,
+        reimbursements::handlers::ReimbursementInfo,
+        reimbursements::handlers::CreateReimbursementRequest,
+        reimbursements::handlers::UpdateReimbursementRequest,
+        reimbursements::handlers::ReviewDecision,
+        reimbursements::handlers::ReviewReimbursementRequest,
+        reimbursements::handlers::RegionSummary,
+        reimbursements::handlers::ReimbursementSummary,
+        reimbursements::handlers::FinanceMember,
+        distributions::handlers::DistributionItemInfo,
+        distributions::handlers::CreateDistributionItemRequest,
+        distributions::handlers::UpdateDistributionItemRequest,
+        distributions::handlers::ClaimItemRequest,
+        distributions::handlers::ClaimInfo,
+        distributions::handlers::ClaimResponse,
+        distributions::handlers::DietaryCount,
+        distributions::handlers::DistributionSummary,
+        distributions::handlers::VolunteerInfo,
+        distributions::handlers::AddVolunteerRequest,
+        exports::writer::ExportFormat,
+        invites::handlers::InviteInfo,
+        invites::handlers::ImportRowResult,
+        invites::handlers::ImportResult,
+        analytics::handlers::Funnel,
+        analytics::handlers::DailyCount,
+        analytics::handlers::ValueCount,
+        analytics::handlers::Demographics,
+        analytics::handlers::HackathonAnalytics,
     )),
-    modifiers(&SecurityAddon),
+    modifiers(&SecurityAddon, &ErrorResponsesAddon),
     tags(
         (name = "Hackathons", description = "Hackathon endpoints"),
-        (name = "Authentication", description = "Authentication endpoints")
+        (name = "Authentication", description = "Authentication endpoints"),
+        (name = "Admins", description = "Global admin management and the audit log"),
+        (name = "Organizations", description = "Organizations that own hackathons"),
+        (name = "Profiles", description = "Participant profiles"),
+        (name = "Resumes", description = "Resume uploads and the sponsor resume book"),
+        (name = "Sponsors", description = "Sponsor companies, tiers and representatives"),
+        (name = "Mentorship", description = "Mentors and the help ticket queue"),
+        (name = "Hardware", description = "Hardware inventory and checkout"),
+        (name = "Reimbursements", description = "Travel reimbursements and payouts"),
+        (name = "Distribution", description = "Meals, swag and the volunteers handing them out"),
+        (name = "Exports", description = "Spreadsheet exports for sponsors, catering and the university"),
+        (name = "Invites", description = "Bulk role imports and invites for people without an account"),
+        (name = "Analytics", description = "Statistics for the organizer dashboard")
     ),
     info(
         title = "Terrier API",
@@ -48,16 +262,65 @@ impl Modify for SecurityAddon {
     fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
         let components = openapi.components.as_mut().unwrap();
         components.add_security_scheme(
-            "jwt",
+            "api_token",
             SecurityScheme::Http(
                 HttpBuilder::new()
                     .scheme(HttpAuthScheme::Bearer)
-                    .bearer_format("JWT")
+                    .bearer_format("Personal access token")
                     .build(),
             ),
         );
+        components.add_security_scheme(
+            "session",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new("id"))),
+        );
+
+        // Add global security requirement, either of which is accepted
+        openapi.security = Some(vec![
+            SecurityRequirement::new("session", Vec::<String>::new()),
+            SecurityRequirement::new("api_token", Vec::<String>::new()),
+        ]);
+    }
+}
+
+struct ErrorResponsesAddon;
+
+impl Modify for ErrorResponsesAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let body = || Content::new(Some(Ref::from_schema_name("ErrorBody")));
+
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+
+            for operation in operations.into_iter().flatten() {
+                let responses = &mut operation.responses.responses;
+
+                // Every error is rendered as an ErrorBody, see crate::error::AppError
+                for (status, response) in responses.iter_mut() {
+                    if let RefOr::T(response) = response
+                        && (status.starts_with('4') || status.starts_with('5'))
+                        && response.content.is_empty()
+                    {
+                        response
+                            .content
+                            .insert("application/json".to_string(), body());
+                    }
+                }
 
-        // Add global security requirement
-        openapi.security = Some(vec![SecurityRequirement::new("jwt", Vec::<String>::new())]);
+                let mut internal = Response::new("Internal server error");
+                internal
+                    .content
+                    .insert("application/json".to_string(), body());
+                responses
+                    .entry("500".to_string())
+                    .or_insert(RefOr::T(internal));
+            }
+        }
     }
 }
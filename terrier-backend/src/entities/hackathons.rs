@@ -20,14 +20,127 @@ pub struct Model {
     pub is_active: bool,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    pub archived_at: Option<DateTime>,
+    pub phase: String,
+    pub phase_manual: bool,
+    pub applications_open_at: Option<DateTime>,
+    pub applications_close_at: Option<DateTime>,
+    pub hacking_end_at: Option<DateTime>,
+    pub judging_start_at: Option<DateTime>,
+    pub results_at: Option<DateTime>,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub settings: Json,
+    pub organization_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::api_tokens::Entity")]
+    ApiTokens,
+    #[sea_orm(has_many = "super::audit_log::Entity")]
+    AuditLog,
+    #[sea_orm(has_many = "super::distribution_items::Entity")]
+    DistributionItems,
+    #[sea_orm(has_many = "super::hackathon_invites::Entity")]
+    HackathonInvites,
+    #[sea_orm(has_many = "super::hackathon_settings_changes::Entity")]
+    HackathonSettingsChanges,
+    #[sea_orm(has_many = "super::hardware_items::Entity")]
+    HardwareItems,
+    #[sea_orm(has_many = "super::help_tickets::Entity")]
+    HelpTickets,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Organizations,
+    #[sea_orm(has_many = "super::reimbursement_requests::Entity")]
+    ReimbursementRequests,
+    #[sea_orm(has_many = "super::resume_access_logs::Entity")]
+    ResumeAccessLogs,
+    #[sea_orm(has_many = "super::sponsor_tiers::Entity")]
+    SponsorTiers,
+    #[sea_orm(has_many = "super::sponsors::Entity")]
+    Sponsors,
     #[sea_orm(has_many = "super::user_hackathon_roles::Entity")]
     UserHackathonRoles,
 }
 
+impl Related<super::api_tokens::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApiTokens.def()
+    }
+}
+
+impl Related<super::audit_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AuditLog.def()
+    }
+}
+
+impl Related<super::distribution_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DistributionItems.def()
+    }
+}
+
+impl Related<super::hackathon_invites::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HackathonInvites.def()
+    }
+}
+
+impl Related<super::hackathon_settings_changes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HackathonSettingsChanges.def()
+    }
+}
+
+impl Related<super::hardware_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HardwareItems.def()
+    }
+}
+
+impl Related<super::help_tickets::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HelpTickets.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::reimbursement_requests::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReimbursementRequests.def()
+    }
+}
+
+impl Related<super::resume_access_logs::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ResumeAccessLogs.def()
+    }
+}
+
+impl Related<super::sponsor_tiers::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SponsorTiers.def()
+    }
+}
+
+impl Related<super::sponsors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sponsors.def()
+    }
+}
+
 impl Related<super::user_hackathon_roles::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserHackathonRoles.def()
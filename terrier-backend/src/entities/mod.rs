@@ -2,6 +2,26 @@
 
 pub mod prelude;
 
+pub mod api_tokens;
+pub mod audit_log;
+pub mod distribution_claims;
+pub mod distribution_items;
+pub mod hackathon_invites;
+pub mod hackathon_settings_changes;
 pub mod hackathons;
+pub mod hardware_items;
+pub mod hardware_loans;
+pub mod help_tickets;
+pub mod organization_admins;
+pub mod organizations;
+pub mod reimbursement_receipts;
+pub mod reimbursement_requests;
+pub mod resume_access_logs;
+pub mod sponsor_leads;
+pub mod sponsor_representatives;
+pub mod sponsor_tiers;
+pub mod sponsors;
 pub mod user_hackathon_roles;
+pub mod user_identities;
+pub mod user_profiles;
 pub mod users;
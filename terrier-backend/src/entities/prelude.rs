@@ -1,5 +1,25 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.14
 
+pub use super::api_tokens::Entity as ApiTokens;
+pub use super::audit_log::Entity as AuditLog;
+pub use super::distribution_claims::Entity as DistributionClaims;
+pub use super::distribution_items::Entity as DistributionItems;
+pub use super::hackathon_invites::Entity as HackathonInvites;
+pub use super::hackathon_settings_changes::Entity as HackathonSettingsChanges;
 pub use super::hackathons::Entity as Hackathons;
+pub use super::hardware_items::Entity as HardwareItems;
+pub use super::hardware_loans::Entity as HardwareLoans;
+pub use super::help_tickets::Entity as HelpTickets;
+pub use super::organization_admins::Entity as OrganizationAdmins;
+pub use super::organizations::Entity as Organizations;
+pub use super::reimbursement_receipts::Entity as ReimbursementReceipts;
+pub use super::reimbursement_requests::Entity as ReimbursementRequests;
+pub use super::resume_access_logs::Entity as ResumeAccessLogs;
+pub use super::sponsor_leads::Entity as SponsorLeads;
+pub use super::sponsor_representatives::Entity as SponsorRepresentatives;
+pub use super::sponsor_tiers::Entity as SponsorTiers;
+pub use super::sponsors::Entity as Sponsors;
 pub use super::user_hackathon_roles::Entity as UserHackathonRoles;
+pub use super::user_identities::Entity as UserIdentities;
+pub use super::user_profiles::Entity as UserProfiles;
 pub use super::users::Entity as Users;
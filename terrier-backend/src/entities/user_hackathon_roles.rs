@@ -13,6 +13,10 @@ pub struct Model {
     pub user_id: i32,
     pub hackathon_id: i32,
     pub role: String,
+    #[sea_orm(unique)]
+    pub badge_token: Option<String>,
+    pub finance: bool,
+    pub created_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -10,7 +10,6 @@ use serde::{Deserialize, Serialize};
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    #[sea_orm(unique)]
     pub oidc_sub: String,
     pub email: String,
     pub name: Option<String>,
@@ -21,12 +20,65 @@ pub struct Model {
     pub oidc_issuer: String,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    pub is_global_admin: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::api_tokens::Entity")]
+    ApiTokens,
+    #[sea_orm(has_many = "super::audit_log::Entity")]
+    AuditLog,
+    #[sea_orm(has_many = "super::hackathon_invites::Entity")]
+    HackathonInvites,
+    #[sea_orm(has_many = "super::hackathon_settings_changes::Entity")]
+    HackathonSettingsChanges,
+    #[sea_orm(has_many = "super::organization_admins::Entity")]
+    OrganizationAdmins,
+    #[sea_orm(has_many = "super::sponsor_representatives::Entity")]
+    SponsorRepresentatives,
     #[sea_orm(has_many = "super::user_hackathon_roles::Entity")]
     UserHackathonRoles,
+    #[sea_orm(has_many = "super::user_identities::Entity")]
+    UserIdentities,
+    #[sea_orm(has_one = "super::user_profiles::Entity")]
+    UserProfiles,
+}
+
+impl Related<super::api_tokens::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApiTokens.def()
+    }
+}
+
+impl Related<super::audit_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AuditLog.def()
+    }
+}
+
+impl Related<super::hackathon_invites::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HackathonInvites.def()
+    }
+}
+
+impl Related<super::hackathon_settings_changes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HackathonSettingsChanges.def()
+    }
+}
+
+impl Related<super::organization_admins::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OrganizationAdmins.def()
+    }
+}
+
+impl Related<super::sponsor_representatives::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SponsorRepresentatives.def()
+    }
 }
 
 impl Related<super::user_hackathon_roles::Entity> for Entity {
@@ -35,4 +87,16 @@ impl Related<super::user_hackathon_roles::Entity> for Entity {
     }
 }
 
+impl Related<super::user_identities::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserIdentities.def()
+    }
+}
+
+impl Related<super::user_profiles::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserProfiles.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,187 @@
+//! The error type returned by every handler, rendered as a JSON [`ErrorBody`].
+
+use std::panic::Location;
+
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{DbErr, SqlErr};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug)]
+pub enum AppError {
+    BadRequest(&'static str),
+    /// Some fields of the request are invalid
+    Validation(Vec<FieldError>),
+    Unauthorized,
+    Forbidden,
+    NotFound(&'static str),
+    Conflict(&'static str),
+    PayloadTooLarge,
+    UnsupportedMediaType(&'static str),
+    /// A service the request needs is not set up or not reachable
+    ServiceUnavailable(&'static str),
+    /// Something went wrong on our side, which was logged when the error was created
+    Internal,
+}
+
+/// Body of every error response
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBody {
+    /// Stable, machine-readable error code such as `not_found`
+    pub code: &'static str,
+    /// Human-readable description, safe to show to users
+    pub message: String,
+    /// Problems with individual fields of the request, if any
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldError {
+    /// Path of the field in the request body, such as `name` or `reimbursements.regions`
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        FieldError {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl AppError {
+    /// Log an unexpected error along with where it happened, and hide it from the client
+    #[track_caller]
+    pub fn internal(error: impl std::fmt::Display) -> Self {
+        let location = Location::caller();
+        tracing::error!("{} at {}:{}", error, location.file(), location.line());
+        AppError::Internal
+    }
+
+    /// Reject a single invalid field
+    pub fn invalid(field: impl Into<String>, message: impl Into<String>) -> Self {
+        AppError::Validation(vec![FieldError::new(field, message)])
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn code(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Validation(_) => "validation_failed",
+            AppError::Unauthorized => "unauthorized",
+            AppError::Forbidden => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) => "conflict",
+            AppError::PayloadTooLarge => "payload_too_large",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::Internal => "internal",
+        }
+    }
+
+    fn message(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(message)
+            | AppError::NotFound(message)
+            | AppError::Conflict(message)
+            | AppError::UnsupportedMediaType(message)
+            | AppError::ServiceUnavailable(message) => message,
+            AppError::Validation(_) => "Some fields are invalid",
+            AppError::Unauthorized => "Not logged in",
+            AppError::Forbidden => "You do not have permission to do this",
+            AppError::PayloadTooLarge => "The upload is too large",
+            AppError::Internal => "Something went wrong, please try again later",
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let body = ErrorBody {
+            code: self.code(),
+            message: self.message().to_string(),
+            fields: match self {
+                AppError::Validation(fields) => fields,
+                _ => Vec::new(),
+            },
+        };
+
+        (status, Json(body)).into_response()
+    }
+}
+
+impl From<DbErr> for AppError {
+    #[track_caller]
+    fn from(error: DbErr) -> Self {
+        // A concurrent request won the race for a unique index, such as two identical creates
+        if let Some(SqlErr::UniqueConstraintViolation(_)) = error.sql_err() {
+            return AppError::Conflict("This already exists");
+        }
+
+        AppError::internal(format_args!("Database error: {error}"))
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    #[track_caller]
+    fn from(error: serde_json::Error) -> Self {
+        AppError::internal(format_args!("Serialization error: {error}"))
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    #[track_caller]
+    fn from(error: std::io::Error) -> Self {
+        AppError::internal(format_args!("I/O error: {error}"))
+    }
+}
+
+impl From<csv::Error> for AppError {
+    #[track_caller]
+    fn from(error: csv::Error) -> Self {
+        AppError::internal(format_args!("CSV error: {error}"))
+    }
+}
+
+impl<W> From<csv::IntoInnerError<W>> for AppError {
+    #[track_caller]
+    fn from(error: csv::IntoInnerError<W>) -> Self {
+        AppError::internal(format_args!("CSV error: {}", error.error()))
+    }
+}
+
+impl From<zip::result::ZipError> for AppError {
+    #[track_caller]
+    fn from(error: zip::result::ZipError) -> Self {
+        AppError::internal(format_args!("Zip error: {error}"))
+    }
+}
+
+impl From<tower_sessions::session::Error> for AppError {
+    #[track_caller]
+    fn from(error: tower_sessions::session::Error) -> Self {
+        AppError::internal(format_args!("Session error: {error}"))
+    }
+}
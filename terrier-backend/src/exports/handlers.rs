@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use axum::{extract::State, response::Response};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::Deserialize;
+use serde_json::json;
+use utoipa::IntoParams;
+
+use crate::{
+    AppState,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    entities::{prelude::*, user_hackathon_roles, user_profiles},
+    error::AppError,
+    exports::writer::{self, ExportFormat, Rows},
+    profiles::handlers::ProfileInfo,
+    validation::Query,
+};
+
+/// Roles whose members can be exported, as participants or as applications still under review
+const EXPORTABLE_ROLES: [&str; 2] = ["participant", "applicant"];
+
+/// People read from the database at a time
+const BATCH_SIZE: u64 = 500;
+
+const COLUMNS: [&str; 21] = [
+    "user_id",
+    "name",
+    "email",
+    "role",
+    "pronouns",
+    "phone",
+    "school",
+    "major",
+    "graduation_year",
+    "shirt_size",
+    "dietary_restrictions",
+    "dietary_notes",
+    "accessibility_needs",
+    "emergency_contact_name",
+    "emergency_contact_phone",
+    "emergency_contact_relationship",
+    "github",
+    "linkedin",
+    "website",
+    "resume_sponsor_consent",
+    "lead_sharing_consent",
+];
+
+/// What a participant would put on a resume, as in the resume book
+const SPONSOR_COLUMNS: [&str; 8] = [
+    "name",
+    "email",
+    "school",
+    "major",
+    "graduation_year",
+    "github",
+    "linkedin",
+    "website",
+];
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParticipantsQuery {
+    /// `csv` (default) or `xlsx`
+    #[serde(default)]
+    #[param(inline)]
+    format: ExportFormat,
+    /// `participant` (default), or `applicant` to export applications still under review
+    role: Option<String>,
+    /// Only include people who share their resume with sponsors, and only the columns of the
+    /// resume book, so the file can be handed to sponsors
+    #[serde(default)]
+    for_sponsors: bool,
+}
+
+/// Export participants and their profiles
+///
+/// The file is streamed while it is being generated, so a failure part way through ends the
+/// download early instead of returning an error status.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/exports/participants",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ExportParticipantsQuery
+    ),
+    responses(
+        (status = 200, description = "Participants as CSV or XLSX", content(
+            ("text/csv"),
+            ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 422, description = "Unknown role")
+    ),
+    tag = "Exports"
+)]
+pub async fn export_participants(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ExportParticipantsQuery>,
+) -> Result<Response, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    let exported_role = query.role.unwrap_or_else(|| "participant".to_string());
+    if !EXPORTABLE_ROLES.contains(&exported_role.as_str()) {
+        return Err(AppError::invalid(
+            "role",
+            format!("Must be one of {}", EXPORTABLE_ROLES.join(", ")),
+        ));
+    }
+
+    audit::record(
+        &state.db,
+        Entry {
+            diff: Some(json!({
+                "role": exported_role,
+                "for_sponsors": query.for_sponsors,
+            })),
+            ..Entry::in_hackathon(&role, "export.participants", "hackathon", role.hackathon_id)
+        },
+    )
+    .await?;
+
+    let file_name = format!(
+        "{}-{}s{}",
+        role.slug,
+        exported_role,
+        if query.for_sponsors { "-sponsors" } else { "" }
+    );
+    let columns: &'static [&'static str] = if query.for_sponsors {
+        &SPONSOR_COLUMNS
+    } else {
+        &COLUMNS
+    };
+
+    let db = state.db.clone();
+    let hackathon_id = role.hackathon_id;
+    let for_sponsors = query.for_sponsors;
+
+    Ok(writer::download(
+        &file_name,
+        query.format,
+        columns,
+        move |rows| write_participants(db, hackathon_id, exported_role, for_sponsors, rows),
+    ))
+}
+
+async fn write_participants(
+    db: DatabaseConnection,
+    hackathon_id: i32,
+    role: String,
+    for_sponsors: bool,
+    rows: Rows,
+) -> Result<(), AppError> {
+    let mut last_id = 0;
+
+    loop {
+        let batch = UserHackathonRoles::find()
+            .find_also_related(Users)
+            .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon_id))
+            .filter(user_hackathon_roles::Column::Role.eq(&role))
+            .filter(user_hackathon_roles::Column::Id.gt(last_id))
+            .order_by_asc(user_hackathon_roles::Column::Id)
+            .limit(BATCH_SIZE)
+            .all(&db)
+            .await?;
+
+        let Some((last, _)) = batch.last() else {
+            return Ok(());
+        };
+        last_id = last.id;
+
+        let mut profiles: HashMap<i32, user_profiles::Model> = UserProfiles::find()
+            .filter(user_profiles::Column::UserId.is_in(batch.iter().map(|(r, _)| r.user_id)))
+            .all(&db)
+            .await?
+            .into_iter()
+            .map(|p| (p.user_id, p))
+            .collect();
+
+        for (_, user) in batch {
+            let Some(user) = user else {
+                continue;
+            };
+            let profile = profiles.remove(&user.id);
+            let profile = ProfileInfo::new(user, profile);
+
+            if for_sponsors {
+                if profile.resume_sponsor_consent {
+                    rows.send(sponsor_row(profile)).await?;
+                }
+            } else {
+                rows.send(full_row(profile, &role)).await?;
+            }
+        }
+    }
+}
+
+fn full_row(profile: ProfileInfo, role: &str) -> Vec<String> {
+    vec![
+        profile.user_id.to_string(),
+        profile.name.unwrap_or_default(),
+        profile.email.unwrap_or_default(),
+        role.to_string(),
+        profile.pronouns.unwrap_or_default(),
+        profile.phone.unwrap_or_default(),
+        profile.school.unwrap_or_default(),
+        profile.major.unwrap_or_default(),
+        profile
+            .graduation_year
+            .map(|y| y.to_string())
+            .unwrap_or_default(),
+        profile.shirt_size.unwrap_or_default(),
+        profile.dietary_restrictions.join(", "),
+        profile.dietary_notes.unwrap_or_default(),
+        profile.accessibility_needs.unwrap_or_default(),
+        profile.emergency_contact_name.unwrap_or_default(),
+        profile.emergency_contact_phone.unwrap_or_default(),
+        profile.emergency_contact_relationship.unwrap_or_default(),
+        profile.github.unwrap_or_default(),
+        profile.linkedin.unwrap_or_default(),
+        profile.website.unwrap_or_default(),
+        profile.resume_sponsor_consent.to_string(),
+        profile.lead_sharing_consent.to_string(),
+    ]
+}
+
+fn sponsor_row(profile: ProfileInfo) -> Vec<String> {
+    vec![
+        profile.name.unwrap_or_default(),
+        profile.email.unwrap_or_default(),
+        profile.school.unwrap_or_default(),
+        profile.major.unwrap_or_default(),
+        profile
+            .graduation_year
+            .map(|y| y.to_string())
+            .unwrap_or_default(),
+        profile.github.unwrap_or_default(),
+        profile.linkedin.unwrap_or_default(),
+        profile.website.unwrap_or_default(),
+    ]
+}
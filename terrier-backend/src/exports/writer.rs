@@ -0,0 +1,296 @@
+//! Spreadsheet downloads, streamed to the client while rows are still being read.
+
+use std::io::{self, Write};
+
+use axum::{
+    body::{Body, Bytes},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use utoipa::ToSchema;
+
+use crate::error::AppError;
+
+/// Rows waiting to be encoded, which stops producers from running ahead of slow clients
+const ROW_BUFFER: usize = 256;
+
+/// CSV is sent in chunks of about this many bytes
+const CHUNK_SIZE: usize = 64 * 1024;
+
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+#[derive(Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    /// Excel workbook, whose rows are kept in temporary files until every row is written and
+    /// the workbook is sent
+    Xlsx,
+}
+
+impl ExportFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Xlsx => XLSX_CONTENT_TYPE,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+type Row = Vec<String>;
+
+/// Writes to a response body from a blocking task, sending chunks of about [`CHUNK_SIZE`]
+pub struct BodyWriter {
+    body: mpsc::Sender<io::Result<Bytes>>,
+    buffer: Vec<u8>,
+}
+
+impl BodyWriter {
+    /// A writer and the body it writes to, which should be sent as the response
+    pub fn new() -> (Self, Body) {
+        let (tx, rx) = mpsc::channel(1);
+        let writer = BodyWriter {
+            body: tx,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+        };
+
+        (writer, Body::from_stream(ReceiverStream::new(rx)))
+    }
+
+    /// Send what is left once everything has been written, or cut the download short if
+    /// writing failed, since the status has already been sent
+    pub fn finish<E>(mut self, result: Result<(), E>) {
+        let result = match result {
+            Ok(()) => self.flush(),
+            Err(_) => Err(io::Error::other("Download failed")),
+        };
+
+        if let Err(error) = result {
+            let _ = self.body.blocking_send(Err(error));
+        }
+    }
+}
+
+impl Write for BodyWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(data);
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+        self.body
+            .blocking_send(Ok(chunk.into()))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+    }
+}
+
+/// Make a value that a spreadsheet would run as a formula show up as text instead, since
+/// exports are opened by people outside the team
+///
+/// Only CSV needs this, since XLSX cells are written as text and never run.
+fn neutralize_formula(value: String) -> String {
+    let is_formula = value.starts_with(['=', '+', '-', '@', '\t', '\r']);
+    // Numbers such as phone numbers are not formulas, whatever their sign
+    if is_formula && value.parse::<f64>().is_err() {
+        format!("'{value}")
+    } else {
+        value
+    }
+}
+
+/// Where the rows of an export are sent
+pub struct Rows {
+    tx: mpsc::Sender<Result<Row, AppError>>,
+}
+
+impl Rows {
+    /// Send the next row, which fails once the client has stopped downloading
+    pub async fn send(&self, row: Row) -> Result<(), AppError> {
+        // The client went away, which is not worth logging
+        self.tx.send(Ok(row)).await.map_err(|_| AppError::Internal)
+    }
+}
+
+/// Respond with a file of `columns` and the rows sent by `produce`, which runs after the
+/// response has started
+///
+/// Errors from `produce` cut the download short, since the status has already been sent.
+pub fn download<F, Fut>(
+    file_name: &str,
+    format: ExportFormat,
+    columns: &'static [&'static str],
+    produce: F,
+) -> Response
+where
+    F: FnOnce(Rows) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+{
+    let (row_tx, row_rx) = mpsc::channel(ROW_BUFFER);
+
+    let rows = Rows { tx: row_tx.clone() };
+    tokio::spawn(async move {
+        if let Err(error) = produce(rows).await {
+            let _ = row_tx.send(Err(error)).await;
+        }
+    });
+
+    let body = match format {
+        ExportFormat::Csv => {
+            let (body_tx, body_rx) = mpsc::channel(1);
+            tokio::spawn(write_csv(columns, row_rx, body_tx));
+            Body::from_stream(ReceiverStream::new(body_rx))
+        }
+        ExportFormat::Xlsx => {
+            let (writer, body) = BodyWriter::new();
+            tokio::task::spawn_blocking(move || write_xlsx(columns, row_rx, writer));
+            body
+        }
+    };
+
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{file_name}.{}\"",
+                    format.extension()
+                ),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+async fn write_csv(
+    columns: &'static [&'static str],
+    mut rows: mpsc::Receiver<Result<Row, AppError>>,
+    body: mpsc::Sender<io::Result<Bytes>>,
+) {
+    if encode_csv(columns, &mut rows, &body).await.is_err() {
+        let _ = body.send(Err(io::Error::other("Export failed"))).await;
+    }
+}
+
+async fn encode_csv(
+    columns: &[&str],
+    rows: &mut mpsc::Receiver<Result<Row, AppError>>,
+    body: &mpsc::Sender<io::Result<Bytes>>,
+) -> Result<(), AppError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(columns)?;
+
+    while let Some(row) = rows.recv().await {
+        writer.write_record(row?.into_iter().map(neutralize_formula))?;
+
+        if writer.get_ref().len() >= CHUNK_SIZE {
+            let full = std::mem::replace(&mut writer, csv::Writer::from_writer(Vec::new()));
+            send_chunk(body, full.into_inner()?).await?;
+        }
+    }
+
+    send_chunk(body, writer.into_inner()?).await
+}
+
+async fn send_chunk(
+    body: &mpsc::Sender<io::Result<Bytes>>,
+    chunk: Vec<u8>,
+) -> Result<(), AppError> {
+    // The client went away, which is not worth logging
+    body.send(Ok(chunk.into()))
+        .await
+        .map_err(|_| AppError::Internal)
+}
+
+fn write_xlsx(
+    columns: &'static [&'static str],
+    mut rows: mpsc::Receiver<Result<Row, AppError>>,
+    mut body: BodyWriter,
+) {
+    let mut workbook = Workbook::new();
+    let result = fill_workbook(&mut workbook, columns, &mut rows).and_then(|()| {
+        // The workbook is zipped straight into the body rather than into memory
+        workbook
+            .save_to_writer(&mut body)
+            .map_err(|e| AppError::internal(format!("Failed to save workbook: {e}")))
+    });
+
+    body.finish(result);
+}
+
+fn fill_workbook(
+    workbook: &mut Workbook,
+    columns: &[&str],
+    rows: &mut mpsc::Receiver<Result<Row, AppError>>,
+) -> Result<(), AppError> {
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    let xlsx_error = |e: XlsxError| AppError::internal(format!("Failed to write workbook: {e}"));
+
+    let bold = Format::new().set_bold();
+    for (col, name) in (0..).zip(columns) {
+        worksheet
+            .write_string_with_format(0, col, *name, &bold)
+            .map_err(xlsx_error)?;
+    }
+    worksheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+
+    let mut row_number = 1;
+    while let Some(row) = rows.blocking_recv() {
+        for (col, value) in (0..).zip(row?) {
+            worksheet
+                .write_string(row_number, col, value)
+                .map_err(xlsx_error)?;
+        }
+        row_number += 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::neutralize_formula;
+
+    #[test]
+    fn formulas_become_text() {
+        for value in [
+            "=1+1",
+            "+A1",
+            "-2+3",
+            "@SUM(A1)",
+            "=HYPERLINK(\"x\")",
+            "\tx",
+        ] {
+            assert_eq!(neutralize_formula(value.to_string()), format!("'{value}"));
+        }
+    }
+
+    #[test]
+    fn other_values_are_unchanged() {
+        for value in ["Alice", "", "+14125551234", "-5", "a=b", "2027"] {
+            assert_eq!(neutralize_formula(value.to_string()), value);
+        }
+    }
+}
@@ -1,13 +1,31 @@
 use axum::{Json, extract::State, http::StatusCode};
-use chrono::NaiveDateTime;
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseTransaction, EntityTrait, QueryFilter,
+    QuerySelect, TransactionTrait, TryIntoModel,
+};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use serde_json::{Value, json};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
     AppState,
-    auth::extractors::{HackathonRole, RequireGlobalAdmin},
-    entities::{hackathons, prelude::*},
+    audit::log::{self as audit, Entry},
+    auth::extractors::{AuthUser, HackathonRole, can_manage_organization},
+    entities::{
+        hackathon_settings_changes, hackathons, organizations, prelude::*, reimbursement_receipts,
+        reimbursement_requests, user_hackathon_roles,
+    },
+    error::AppError,
+    hackathons::{
+        phase::{self, HackathonPhase},
+        settings::{self, Features, HackathonSettings},
+    },
+    pagination::{self, Page, PageParams, Sort},
+    validation::{
+        self, MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, Path, Query, ValidJson, Validate, Validator,
+        parse_date,
+    },
 };
 
 #[derive(Serialize, ToSchema)]
@@ -18,39 +36,212 @@ pub struct HackathonInfo {
     pub description: Option<String>,
     pub start_date: NaiveDateTime,
     pub end_date: NaiveDateTime,
+    /// Whether the hackathon is published and listed publicly
+    pub is_active: bool,
+    pub archived_at: Option<NaiveDateTime>,
+    pub phase: HackathonPhase,
+    /// Whether the phase was set by an admin rather than following the schedule
+    pub phase_manual: bool,
+    pub applications_open_at: Option<NaiveDateTime>,
+    pub applications_close_at: Option<NaiveDateTime>,
+    pub hacking_end_at: Option<NaiveDateTime>,
+    pub judging_start_at: Option<NaiveDateTime>,
+    pub results_at: Option<NaiveDateTime>,
+    /// The organization that owns the hackathon, if any
+    pub organization_id: Option<i32>,
+}
+
+impl From<hackathons::Model> for HackathonInfo {
+    fn from(h: hackathons::Model) -> Self {
+        HackathonInfo {
+            phase: phase::current(&h),
+            id: h.id,
+            name: h.name,
+            slug: h.slug,
+            description: h.description,
+            start_date: h.start_date,
+            end_date: h.end_date,
+            is_active: h.is_active,
+            archived_at: h.archived_at,
+            phase_manual: h.phase_manual,
+            applications_open_at: h.applications_open_at,
+            applications_close_at: h.applications_close_at,
+            hacking_end_at: h.hacking_end_at,
+            judging_start_at: h.judging_start_at,
+            results_at: h.results_at,
+            organization_id: h.organization_id,
+        }
+    }
+}
+
+/// Parse an optional deadline, where an empty string clears it
+fn parse_deadline(field: &str, date: &str) -> Result<Option<NaiveDateTime>, AppError> {
+    if date.is_empty() {
+        Ok(None)
+    } else {
+        parse_date(field, date).map(Some)
+    }
+}
+
+async fn find_hackathon(
+    state: &AppState,
+    hackathon_id: i32,
+) -> Result<hackathons::Model, AppError> {
+    Hackathons::find_by_id(hackathon_id)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Hackathon not found"))
 }
 
-/// List all active hackathons
+/// Reject the request unless the hackathon has enabled an optional module in its settings
+pub async fn require_feature(
+    state: &AppState,
+    hackathon_id: i32,
+    enabled: fn(&Features) -> bool,
+) -> Result<HackathonSettings, AppError> {
+    let hackathon = find_hackathon(state, hackathon_id).await?;
+    let settings = HackathonSettings::of(&hackathon);
+
+    if enabled(&settings.features) {
+        Ok(settings)
+    } else {
+        Err(AppError::NotFound(
+            "This feature is not enabled for the hackathon",
+        ))
+    }
+}
+
+/// Check that the caller can manage the hackathon and that it can still be changed
+fn require_editable(role: &HackathonRole) -> Result<(), AppError> {
+    if !role.is_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)
+}
+
+/// Check that the user can create, copy or delete hackathons owned by the organization, or
+/// hackathons without an organization if `organization_id` is `None`
+async fn require_owner(
+    state: &AppState,
+    auth: &AuthUser,
+    organization_id: Option<i32>,
+) -> Result<(), AppError> {
+    let allowed = match organization_id {
+        Some(organization_id) => can_manage_organization(state, auth, organization_id).await?,
+        // Tokens scoped to a single hackathon never grant deployment-wide access
+        None => {
+            auth.token.as_ref().is_none_or(|t| t.hackathon_id.is_none()) && auth.is_global_admin()
+        }
+    };
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+async fn find_organization(state: &AppState, slug: &str) -> Result<organizations::Model, AppError> {
+    Organizations::find()
+        .filter(organizations::Column::Slug.eq(slug))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Organization not found"))
+}
+
+/// Save changes to a hackathon, moving it to the phase its new schedule puts it in, and
+/// record them in the audit log as `action`
+async fn update_with_phase(
+    state: &AppState,
+    role: &HackathonRole,
+    action: &str,
+    before: &hackathons::Model,
+    mut hackathon: hackathons::ActiveModel,
+) -> Result<hackathons::Model, AppError> {
+    let model = hackathon.clone().try_into_model()?;
+
+    hackathon.phase = Set(phase::current(&model).to_string());
+    hackathon.updated_at = Set(Utc::now().naive_utc());
+
+    let txn = state.db.begin().await?;
+    let after = hackathon.update(&txn).await?;
+    audit::record(
+        &txn,
+        Entry {
+            diff: audit::diff(before, &after),
+            ..Entry::in_hackathon(role, action, "hackathon", after.id)
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(after)
+}
+
+const PUBLIC_SORT: Sort<hackathons::Column> = Sort {
+    fields: &[
+        ("start_date", hackathons::Column::StartDate),
+        ("end_date", hackathons::Column::EndDate),
+        ("name", hackathons::Column::Name),
+    ],
+    default: "start_date",
+    id: hackathons::Column::Id,
+};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PublicHackathonsQuery {
+    /// Only list hackathons owned by the organization with this slug
+    organization: Option<String>,
+    /// Only list hackathons whose name contains this text, ignoring case
+    search: Option<String>,
+    /// Only list hackathons that have not ended by this time
+    ends_after: Option<NaiveDateTime>,
+}
+
+/// List published hackathons
+///
+/// Can be sorted by `start_date`, which is the default, `end_date` or `name`.
 #[utoipa::path(
     get,
     path = "/hackathons/public",
+    params(PublicHackathonsQuery, PageParams),
     responses(
-        (status = 200, description = "List of active hackathons", body = Vec<HackathonInfo>)
+        (status = 200, description = "Page of published hackathons", body = Page<HackathonInfo>),
+        (status = 404, description = "Organization not found"),
+        (status = 422, description = "Invalid page, sort or cursor")
     ),
     tag = "Hackathons"
 )]
 pub async fn list_public_hackathons(
     State(state): State<AppState>,
-) -> Result<Json<Vec<HackathonInfo>>, StatusCode> {
-    let hackathons = Hackathons::find()
-        // .filter(hackathons::Column::IsActive.eq(true))
-        .all(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Query(query): Query<PublicHackathonsQuery>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<HackathonInfo>>, AppError> {
+    let mut select = Hackathons::find()
+        .filter(hackathons::Column::IsActive.eq(true))
+        .filter(hackathons::Column::ArchivedAt.is_null());
 
-    Ok(Json(
-        hackathons
-            .into_iter()
-            .map(|h| HackathonInfo {
-                id: h.id,
-                name: h.name,
-                slug: h.slug,
-                description: h.description,
-                start_date: h.start_date,
-                end_date: h.end_date,
-            })
-            .collect(),
-    ))
+    if let Some(slug) = &query.organization {
+        let organization = find_organization(&state, slug).await?;
+        select = select.filter(hackathons::Column::OrganizationId.eq(organization.id));
+    }
+    if let Some(search) = query.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        select = select.filter(pagination::contains_ignoring_case(
+            hackathons::Column::Name,
+            search.trim(),
+        ));
+    }
+    if let Some(ends_after) = query.ends_after {
+        select = select.filter(hackathons::Column::EndDate.gt(ends_after));
+    }
+
+    let page = pagination::paginate(&state.db, select, &page, &PUBLIC_SORT, |h| h).await?;
+
+    Ok(Json(page.map(|hackathons| {
+        hackathons.into_iter().map(HackathonInfo::from).collect()
+    })))
 }
 
 #[derive(Serialize, ToSchema)]
@@ -73,7 +264,7 @@ pub struct UserRoleResponse {
     ),
     tag = "Hackathons"
 )]
-pub async fn get_user_role(role: HackathonRole) -> Result<Json<UserRoleResponse>, StatusCode> {
+pub async fn get_user_role(role: HackathonRole) -> Result<Json<UserRoleResponse>, AppError> {
     Ok(Json(UserRoleResponse { role: role.role }))
 }
 
@@ -84,68 +275,806 @@ pub struct CreateHackathonRequest {
     pub description: Option<String>,
     pub start_date: String,
     pub end_date: String,
+    /// Organization to create the hackathon in, which only its admins can do. Hackathons
+    /// without an organization can only be created by global admins.
+    pub organization_slug: Option<String>,
+}
+
+impl Validate for CreateHackathonRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name, MAX_NAME_LENGTH);
+        v.slug("slug", &self.slug);
+        v.max_length(
+            "description",
+            self.description.as_deref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
+
+        let start_date = v.date("start_date", &self.start_date);
+        let end_date = v.date("end_date", &self.end_date);
+        if let (Some(start_date), Some(end_date)) = (start_date, end_date) {
+            v.not_before("end_date", end_date, "start_date", start_date);
+        }
+    }
 }
 
 /// Create a new hackathon
+///
+/// The hackathon starts out unpublished, with its creator as an admin.
 #[utoipa::path(
     post,
     path = "/hackathons",
     request_body = CreateHackathonRequest,
     responses(
         (status = 201, description = "Hackathon created successfully", body = HackathonInfo),
-        (status = 400, description = "Invalid request or slug already exists"),
         (status = 401, description = "Not authenticated"),
-        (status = 403, description = "Not a global admin"),
+        (status = 403, description = "Not an admin of the organization, or not a global admin"),
+        (status = 404, description = "Organization not found"),
+        (status = 422, description = "Invalid field or slug already taken"),
     ),
     tag = "Hackathons"
 )]
 pub async fn create_hackathon(
-    _admin: RequireGlobalAdmin,
+    auth: AuthUser,
     State(state): State<AppState>,
-    Json(req): Json<CreateHackathonRequest>,
-) -> Result<(StatusCode, Json<HackathonInfo>), StatusCode> {
-    // Check if slug already exists
+    ValidJson(req): ValidJson<CreateHackathonRequest>,
+) -> Result<(StatusCode, Json<HackathonInfo>), AppError> {
+    let organization_id = match &req.organization_slug {
+        Some(slug) => Some(find_organization(&state, slug).await?.id),
+        None => None,
+    };
+    require_owner(&state, &auth, organization_id).await?;
+
+    let start_date = parse_date("start_date", &req.start_date)?;
+    let end_date = parse_date("end_date", &req.end_date)?;
+    require_free_slug(&state, &req.slug).await?;
+
+    let hackathon = hackathons::ActiveModel {
+        name: Set(req.name),
+        slug: Set(req.slug),
+        description: Set(req.description),
+        start_date: Set(start_date),
+        end_date: Set(end_date),
+        is_active: Set(false),
+        organization_id: Set(organization_id),
+        ..Default::default()
+    };
+
+    let txn = state.db.begin().await?;
+    let result = insert_hackathon(&txn, hackathon, auth.user.id, Vec::new()).await?;
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: auth.user.id,
+            hackathon_id: Some(result.id),
+            action: "hackathon.create",
+            target_type: "hackathon",
+            target_id: Some(result.id),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(result.into())))
+}
+
+async fn require_free_slug(state: &AppState, slug: &str) -> Result<(), AppError> {
     let existing = Hackathons::find()
-        .filter(hackathons::Column::Slug.eq(&req.slug))
+        .filter(hackathons::Column::Slug.eq(slug))
         .one(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    println!("{:?}", existing);
+        .await?;
 
     if existing.is_some() {
-        return Err(StatusCode::BAD_REQUEST);
+        Err(AppError::invalid("slug", "This slug is already taken"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Insert a hackathon with `creator` as an admin, along with any other roles
+async fn insert_hackathon(
+    txn: &DatabaseTransaction,
+    hackathon: hackathons::ActiveModel,
+    creator: i32,
+    roles: Vec<user_hackathon_roles::Model>,
+) -> Result<hackathons::Model, AppError> {
+    let result = hackathon.insert(txn).await?;
+
+    // Keep the creator as an admin even if they later lose global admin
+    let creator_role = (creator, "admin".to_string());
+    let other_roles = roles
+        .into_iter()
+        .filter(|role| role.user_id != creator)
+        .map(|role| (role.user_id, role.role));
+
+    for (user_id, role) in std::iter::once(creator_role).chain(other_roles) {
+        user_hackathon_roles::ActiveModel {
+            user_id: Set(user_id),
+            hackathon_id: Set(result.id),
+            role: Set(role),
+            ..Default::default()
+        }
+        .insert(txn)
+        .await?;
+    }
+
+    Ok(result)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct DuplicateHackathonRequest {
+    pub name: String,
+    pub slug: String,
+    /// Start of the new edition, which every other date is shifted along with
+    pub start_date: String,
+    /// Defaults to the original hackathon's description
+    pub description: Option<String>,
+}
+
+impl Validate for DuplicateHackathonRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name, MAX_NAME_LENGTH);
+        v.slug("slug", &self.slug);
+        v.date("start_date", &self.start_date);
+        v.max_length(
+            "description",
+            self.description.as_deref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
     }
+}
+
+/// Create a new edition of a hackathon from an existing one
+///
+/// Copies the settings, the schedule with every date shifted to the new start date, and the
+/// admin and organizer roles. Applicants, participants and everything they submitted are not
+/// copied. The new hackathon belongs to the same organization and starts out unpublished,
+/// with its creator as an admin.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/duplicate",
+    params(
+        ("slug" = String, Path, description = "Slug of the hackathon to copy")
+    ),
+    request_body = DuplicateHackathonRequest,
+    responses(
+        (status = 201, description = "Hackathon created", body = HackathonInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of the hackathon's organization"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 422, description = "Invalid field or slug already taken"),
+    ),
+    tag = "Hackathons"
+)]
+pub async fn duplicate_hackathon(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    ValidJson(req): ValidJson<DuplicateHackathonRequest>,
+) -> Result<(StatusCode, Json<HackathonInfo>), AppError> {
+    let source = Hackathons::find()
+        .filter(hackathons::Column::Slug.eq(&slug))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Hackathon not found"))?;
+    require_owner(&state, &auth, source.organization_id).await?;
+
+    let start_date = parse_date("start_date", &req.start_date)?;
+    require_free_slug(&state, &req.slug).await?;
+
+    let roles = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(source.id))
+        .filter(user_hackathon_roles::Column::Role.is_in(["admin", "organizer"]))
+        .all(&state.db)
+        .await?;
 
-    // Create hackathon
+    let shift = start_date - source.start_date;
     let hackathon = hackathons::ActiveModel {
         name: Set(req.name),
         slug: Set(req.slug),
-        description: Set(req.description),
-        start_date: Set(
-            NaiveDateTime::parse_from_str(&req.start_date, "%Y-%m-%dT%H:%M:%S%.3fZ").unwrap(),
-        ),
-        end_date: Set(
-            NaiveDateTime::parse_from_str(&req.end_date, "%Y-%m-%dT%H:%M:%S%.3fZ").unwrap(),
-        ),
+        description: Set(req.description.or(source.description)),
+        start_date: Set(start_date),
+        end_date: Set(source.end_date + shift),
         is_active: Set(false),
+        applications_open_at: Set(source.applications_open_at.map(|d| d + shift)),
+        applications_close_at: Set(source.applications_close_at.map(|d| d + shift)),
+        hacking_end_at: Set(source.hacking_end_at.map(|d| d + shift)),
+        judging_start_at: Set(source.judging_start_at.map(|d| d + shift)),
+        results_at: Set(source.results_at.map(|d| d + shift)),
+        settings: Set(source.settings),
+        organization_id: Set(source.organization_id),
         ..Default::default()
     };
 
-    let result = hackathon
-        .insert(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    Ok((
-        StatusCode::CREATED,
-        Json(HackathonInfo {
-            id: result.id,
-            name: result.name,
-            slug: result.slug,
-            description: result.description,
-            start_date: result.start_date,
-            end_date: result.end_date,
-        }),
-    ))
+    let txn = state.db.begin().await?;
+    let result = insert_hackathon(&txn, hackathon, auth.user.id, roles).await?;
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: auth.user.id,
+            hackathon_id: Some(result.id),
+            action: "hackathon.duplicate",
+            target_type: "hackathon",
+            target_id: Some(result.id),
+            diff: Some(json!({ "source": source.id })),
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        "{} created hackathon {} from {}",
+        auth.user.email,
+        result.slug,
+        slug
+    );
+
+    Ok((StatusCode::CREATED, Json(result.into())))
+}
+
+/// Get a hackathon, including unpublished and archived ones
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "The hackathon", body = HackathonInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "No access to this hackathon"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn get_hackathon(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<HackathonInfo>, AppError> {
+    let hackathon = find_hackathon(&state, role.hackathon_id).await?;
+    Ok(Json(hackathon.into()))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateHackathonRequest {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    /// An empty string clears the description
+    pub description: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    /// Deadlines that drive the phase schedule, where an empty string clears them
+    pub applications_open_at: Option<String>,
+    pub applications_close_at: Option<String>,
+    pub hacking_end_at: Option<String>,
+    pub judging_start_at: Option<String>,
+    pub results_at: Option<String>,
+}
+
+impl Validate for UpdateHackathonRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            v.required("name", name, MAX_NAME_LENGTH);
+        }
+        if let Some(slug) = &self.slug {
+            v.slug("slug", slug);
+        }
+        v.max_length(
+            "description",
+            self.description.as_deref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
+
+        for (field, date) in [
+            ("start_date", &self.start_date),
+            ("end_date", &self.end_date),
+        ] {
+            if let Some(date) = date {
+                v.date(field, date);
+            }
+        }
+
+        for (field, date) in [
+            ("applications_open_at", &self.applications_open_at),
+            ("applications_close_at", &self.applications_close_at),
+            ("hacking_end_at", &self.hacking_end_at),
+            ("judging_start_at", &self.judging_start_at),
+            ("results_at", &self.results_at),
+        ] {
+            if let Some(date) = date.as_deref().filter(|d| !d.is_empty()) {
+                v.date(field, date);
+            }
+        }
+    }
+}
+
+/// Update a hackathon's details and schedule
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = UpdateHackathonRequest,
+    responses(
+        (status = 200, description = "Hackathon updated", body = HackathonInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 409, description = "Hackathon is archived"),
+        (status = 422, description = "Invalid field, slug already taken or deadlines out of order")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn update_hackathon(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<UpdateHackathonRequest>,
+) -> Result<Json<HackathonInfo>, AppError> {
+    require_editable(&role)?;
+    let hackathon = find_hackathon(&state, role.hackathon_id).await?;
+
+    if let Some(slug) = req.slug.as_ref().filter(|s| **s != hackathon.slug) {
+        require_free_slug(&state, slug).await?;
+    }
+
+    let before = hackathon.clone();
+    let mut active: hackathons::ActiveModel = hackathon.into();
+
+    if let Some(name) = req.name {
+        active.name = Set(name);
+    }
+    if let Some(slug) = req.slug {
+        active.slug = Set(slug);
+    }
+    if let Some(description) = req.description {
+        active.description = Set(Some(description).filter(|d| !d.is_empty()));
+    }
+    if let Some(date) = req.start_date {
+        active.start_date = Set(parse_date("start_date", &date)?);
+    }
+    if let Some(date) = req.end_date {
+        active.end_date = Set(parse_date("end_date", &date)?);
+    }
+
+    for (field, column, value) in [
+        (
+            "applications_open_at",
+            &mut active.applications_open_at,
+            req.applications_open_at,
+        ),
+        (
+            "applications_close_at",
+            &mut active.applications_close_at,
+            req.applications_close_at,
+        ),
+        (
+            "hacking_end_at",
+            &mut active.hacking_end_at,
+            req.hacking_end_at,
+        ),
+        (
+            "judging_start_at",
+            &mut active.judging_start_at,
+            req.judging_start_at,
+        ),
+        ("results_at", &mut active.results_at, req.results_at),
+    ] {
+        if let Some(date) = value {
+            *column = Set(parse_deadline(field, &date)?);
+        }
+    }
+
+    let updated = active.clone().try_into_model()?;
+    let mut v = Validator::default();
+    phase::validate_schedule(&updated, &mut v);
+    v.finish()?;
+
+    let result = update_with_phase(&state, &role, "hackathon.update", &before, active).await?;
+
+    Ok(Json(result.into()))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PublishRequest {
+    pub published: bool,
+}
+
+impl Validate for PublishRequest {}
+
+/// Publish or unpublish a hackathon
+///
+/// Only published hackathons are listed publicly.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/publish",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = PublishRequest,
+    responses(
+        (status = 200, description = "Publish state updated", body = HackathonInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn set_published(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<PublishRequest>,
+) -> Result<Json<HackathonInfo>, AppError> {
+    require_editable(&role)?;
+
+    let hackathon = find_hackathon(&state, role.hackathon_id).await?;
+    if hackathon.is_active == req.published {
+        return Ok(Json(hackathon.into()));
+    }
+
+    let before = hackathon.clone();
+    let mut active: hackathons::ActiveModel = hackathon.into();
+    active.is_active = Set(req.published);
+
+    let result = update_with_phase(&state, &role, "hackathon.publish", &before, active).await?;
+
+    Ok(Json(result.into()))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ArchiveRequest {
+    pub archived: bool,
+}
+
+impl Validate for ArchiveRequest {}
+
+/// Archive or unarchive a hackathon
+///
+/// Archiving unpublishes the hackathon and makes it read-only until it is unarchived.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/archive",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = ArchiveRequest,
+    responses(
+        (status = 200, description = "Archive state updated", body = HackathonInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn set_archived(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<ArchiveRequest>,
+) -> Result<Json<HackathonInfo>, AppError> {
+    if !role.is_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    let hackathon = find_hackathon(&state, role.hackathon_id).await?;
+    if hackathon.archived_at.is_some() == req.archived {
+        return Ok(Json(hackathon.into()));
+    }
+
+    let before = hackathon.clone();
+    let mut active: hackathons::ActiveModel = hackathon.into();
+    // Unarchived hackathons go back to following their schedule
+    active.phase_manual = Set(false);
+    if req.archived {
+        active.archived_at = Set(Some(Utc::now().naive_utc()));
+        active.is_active = Set(false);
+    } else {
+        active.archived_at = Set(None);
+    }
+
+    let result = update_with_phase(&state, &role, "hackathon.archive", &before, active).await?;
+
+    Ok(Json(result.into()))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SetPhaseRequest {
+    /// The phase to move to, or null to go back to following the schedule
+    pub phase: Option<HackathonPhase>,
+}
+
+impl Validate for SetPhaseRequest {
+    fn validate(&self, v: &mut Validator) {
+        if self.phase == Some(HackathonPhase::Archived) {
+            v.error("phase", "Use the archive endpoint to archive a hackathon");
+        }
+    }
+}
+
+/// Override a hackathon's phase
+///
+/// A manually set phase stays until it is changed again or cleared, regardless of deadlines.
+/// Use the archive endpoint to archive a hackathon.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/phase",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = SetPhaseRequest,
+    responses(
+        (status = 200, description = "Phase updated", body = HackathonInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 409, description = "Hackathon is archived"),
+        (status = 422, description = "Cannot archive through this endpoint")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn set_phase(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<SetPhaseRequest>,
+) -> Result<Json<HackathonInfo>, AppError> {
+    require_editable(&role)?;
+
+    let hackathon = find_hackathon(&state, role.hackathon_id).await?;
+    let from = role.phase;
+
+    let before = hackathon.clone();
+    let mut active: hackathons::ActiveModel = hackathon.into();
+    active.phase_manual = Set(req.phase.is_some());
+    if let Some(phase) = req.phase {
+        active.phase = Set(phase.to_string());
+    }
+
+    let result = update_with_phase(&state, &role, "hackathon.phase", &before, active).await?;
+    tracing::info!(
+        "Hackathon {} manually moved from {} to {}",
+        result.slug,
+        from,
+        phase::current(&result)
+    );
+
+    Ok(Json(result.into()))
+}
+
+/// Permanently delete a hackathon and everything belonging to it
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 204, description = "Hackathon deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of the hackathon's organization"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn delete_hackathon(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let hackathon = Hackathons::find()
+        .filter(hackathons::Column::Slug.eq(&slug))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Hackathon not found"))?;
+    require_owner(&state, &auth, hackathon.organization_id).await?;
+
+    let txn = state.db.begin().await?;
+
+    // Receipt rows go with the hackathon, but their files have to be removed from storage
+    let receipt_keys: Vec<String> = ReimbursementReceipts::find()
+        .inner_join(ReimbursementRequests)
+        .filter(reimbursement_requests::Column::HackathonId.eq(hackathon.id))
+        .select_only()
+        .column(reimbursement_receipts::Column::StorageKey)
+        .into_tuple()
+        .all(&txn)
+        .await?;
+
+    Hackathons::delete_by_id(hackathon.id).exec(&txn).await?;
+
+    // The entry cannot reference the hackathon it outlives, so it records the slug instead
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: auth.user.id,
+            action: "hackathon.delete",
+            target_type: "hackathon",
+            target_id: Some(hackathon.id),
+            diff: Some(json!({ "slug": slug })),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    tracing::info!("{} deleted hackathon {}", auth.user.email, slug);
+
+    // The database rows are what make receipts visible, so a leftover file is harmless
+    if let Some(storage) = &state.storage {
+        for key in receipt_keys {
+            if let Err(e) = storage.delete(&key).await {
+                tracing::warn!("Failed to delete {} from storage: {}", key, e);
+            }
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get a hackathon's settings
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/settings",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "The hackathon's settings", body = HackathonSettings),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn get_settings(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<HackathonSettings>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    let hackathon = find_hackathon(&state, role.hackathon_id).await?;
+    Ok(Json(HackathonSettings::of(&hackathon)))
+}
+
+/// Update a hackathon's settings
+///
+/// The body is a JSON merge patch: only the fields given are changed, and `null` resets a
+/// field to its default.
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}/settings",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body(content = HackathonSettings, description = "Settings to change"),
+    responses(
+        (status = 200, description = "Settings updated", body = HackathonSettings),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 409, description = "Hackathon is archived"),
+        (status = 422, description = "Unknown setting, wrong type or invalid setting")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn update_settings(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(patch): ValidJson<Value>,
+) -> Result<Json<HackathonSettings>, AppError> {
+    require_editable(&role)?;
+    let hackathon = find_hackathon(&state, role.hackathon_id).await?;
+
+    let current = HackathonSettings::of(&hackathon);
+    let previous = serde_json::to_value(&current)?;
+
+    let mut merged = previous.clone();
+    settings::merge_patch(&mut merged, patch);
+
+    let updated = HackathonSettings::parse(merged)?;
+    validation::check(&updated)?;
+
+    if updated == current {
+        return Ok(Json(updated));
+    }
+
+    let new = serde_json::to_value(&updated)?;
+
+    let txn = state.db.begin().await?;
+
+    let mut active: hackathons::ActiveModel = hackathon.into();
+    active.settings = Set(new.clone());
+    active.updated_at = Set(Utc::now().naive_utc());
+    active.update(&txn).await?;
+
+    hackathon_settings_changes::ActiveModel {
+        hackathon_id: Set(role.hackathon_id),
+        user_id: Set(Some(role.user_id)),
+        previous: Set(previous),
+        settings: Set(new),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    audit::record(
+        &txn,
+        Entry {
+            diff: audit::diff(&current, &updated),
+            ..Entry::in_hackathon(&role, "settings.update", "hackathon", role.hackathon_id)
+        },
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(Json(updated))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SettingsChange {
+    pub id: i32,
+    /// Email of the user who made the change, unless they have since been deleted
+    pub changed_by: Option<String>,
+    #[schema(value_type = HackathonSettings)]
+    pub previous: Value,
+    #[schema(value_type = HackathonSettings)]
+    pub settings: Value,
+    pub created_at: NaiveDateTime,
+}
+
+const HISTORY_SORT: Sort<hackathon_settings_changes::Column> = Sort {
+    fields: &[("created_at", hackathon_settings_changes::Column::CreatedAt)],
+    default: "-created_at",
+    id: hackathon_settings_changes::Column::Id,
+};
+
+/// List changes to a hackathon's settings, newest first
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/settings/history",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Settings changes", body = Page<SettingsChange>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 422, description = "Invalid page, sort or cursor")
+    ),
+    tag = "Hackathons"
+)]
+pub async fn get_settings_history(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<SettingsChange>>, AppError> {
+    if !role.is_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    let select = HackathonSettingsChanges::find()
+        .filter(hackathon_settings_changes::Column::HackathonId.eq(role.hackathon_id))
+        .find_also_related(Users);
+    let page = pagination::paginate(&state.db, select, &page, &HISTORY_SORT, |(change, _)| {
+        change
+    })
+    .await?;
+
+    Ok(Json(page.map(|changes| {
+        changes
+            .into_iter()
+            .map(|(change, user)| SettingsChange {
+                id: change.id,
+                changed_by: user.map(|u| u.email),
+                previous: change.previous,
+                settings: change.settings,
+                created_at: change.created_at,
+            })
+            .collect()
+    })))
 }
@@ -1 +1,4 @@
 pub mod handlers;
+pub mod phase;
+pub mod roles;
+pub mod settings;
@@ -0,0 +1,453 @@
+//! The phases a hackathon moves through, driven by its schedule unless an admin sets one
+//! manually.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, sea_query::Expr};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    entities::{hackathons, prelude::*},
+    validation::Validator,
+};
+
+/// How often scheduled phase transitions are written to the database
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HackathonPhase {
+    Draft,
+    ApplicationsOpen,
+    ApplicationsClosed,
+    EventLive,
+    HackingEnded,
+    Judging,
+    ResultsPublished,
+    Archived,
+}
+
+impl HackathonPhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HackathonPhase::Draft => "draft",
+            HackathonPhase::ApplicationsOpen => "applications_open",
+            HackathonPhase::ApplicationsClosed => "applications_closed",
+            HackathonPhase::EventLive => "event_live",
+            HackathonPhase::HackingEnded => "hacking_ended",
+            HackathonPhase::Judging => "judging",
+            HackathonPhase::ResultsPublished => "results_published",
+            HackathonPhase::Archived => "archived",
+        }
+    }
+
+    /// Every phase except archived, for handlers that work until a hackathon is archived
+    pub const NOT_ARCHIVED: [HackathonPhase; 7] = [
+        HackathonPhase::Draft,
+        HackathonPhase::ApplicationsOpen,
+        HackathonPhase::ApplicationsClosed,
+        HackathonPhase::EventLive,
+        HackathonPhase::HackingEnded,
+        HackathonPhase::Judging,
+        HackathonPhase::ResultsPublished,
+    ];
+}
+
+impl fmt::Display for HackathonPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HackathonPhase {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [HackathonPhase::Archived]
+            .into_iter()
+            .chain(HackathonPhase::NOT_ARCHIVED)
+            .find(|phase| phase.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// The phase the schedule puts a hackathon in at `now`.
+///
+/// Unpublished hackathons stay in draft. Without an application opening date, applications
+/// open as soon as the hackathon is published, and hacking ends at `end_date` unless a
+/// separate deadline is set.
+pub fn scheduled(hackathon: &hackathons::Model, now: NaiveDateTime) -> HackathonPhase {
+    if hackathon.archived_at.is_some() {
+        return HackathonPhase::Archived;
+    }
+
+    if !hackathon.is_active {
+        return HackathonPhase::Draft;
+    }
+
+    let deadlines = [
+        (hackathon.results_at, HackathonPhase::ResultsPublished),
+        (hackathon.judging_start_at, HackathonPhase::Judging),
+        (
+            Some(hackathon.hacking_end_at.unwrap_or(hackathon.end_date)),
+            HackathonPhase::HackingEnded,
+        ),
+        (Some(hackathon.start_date), HackathonPhase::EventLive),
+        (
+            hackathon.applications_close_at,
+            HackathonPhase::ApplicationsClosed,
+        ),
+        (
+            Some(hackathon.applications_open_at.unwrap_or(NaiveDateTime::MIN)),
+            HackathonPhase::ApplicationsOpen,
+        ),
+    ];
+
+    deadlines
+        .into_iter()
+        .find(|(deadline, _)| deadline.is_some_and(|d| d <= now))
+        .map(|(_, phase)| phase)
+        .unwrap_or(HackathonPhase::Draft)
+}
+
+/// The phase a hackathon is in right now, which is the manually set phase if there is one
+pub fn current(hackathon: &hackathons::Model) -> HackathonPhase {
+    if hackathon.phase_manual && hackathon.archived_at.is_none() {
+        hackathon.phase.parse().unwrap_or(HackathonPhase::Draft)
+    } else {
+        scheduled(hackathon, Utc::now().naive_utc())
+    }
+}
+
+/// Check that the deadlines that are set happen in phase order
+pub fn validate_schedule(hackathon: &hackathons::Model, v: &mut Validator) {
+    // Hacking ends with the hackathon unless it has its own deadline
+    let hacking_end = match hackathon.hacking_end_at {
+        Some(date) => {
+            // The event still has to end after it starts, and hacking cannot outlast it
+            if hackathon.end_date < hackathon.start_date {
+                v.not_before(
+                    "end_date",
+                    hackathon.end_date,
+                    "start_date",
+                    hackathon.start_date,
+                );
+            } else {
+                v.not_before("end_date", hackathon.end_date, "hacking_end_at", date);
+            }
+            ("hacking_end_at", date)
+        }
+        None => ("end_date", hackathon.end_date),
+    };
+    let deadlines = [
+        ("applications_open_at", hackathon.applications_open_at),
+        ("applications_close_at", hackathon.applications_close_at),
+        ("start_date", Some(hackathon.start_date)),
+        (hacking_end.0, Some(hacking_end.1)),
+        ("judging_start_at", hackathon.judging_start_at),
+        ("results_at", hackathon.results_at),
+    ];
+
+    let mut previous: Option<(&str, NaiveDateTime)> = None;
+    for (field, date) in deadlines {
+        let Some(date) = date else { continue };
+        if let Some((previous_field, previous_date)) = previous {
+            v.not_before(field, date, previous_field, previous_date);
+        }
+        previous = Some((field, date));
+    }
+}
+
+/// Write scheduled transitions to the database. Handlers use [`current`], so this only keeps
+/// the stored phase in step for queries and records when transitions happen.
+pub async fn apply_scheduled_transitions(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let now = Utc::now().naive_utc();
+
+    let hackathons = Hackathons::find()
+        .filter(hackathons::Column::PhaseManual.eq(false))
+        .all(db)
+        .await?;
+
+    for hackathon in hackathons {
+        let phase = scheduled(&hackathon, now);
+        if hackathon.phase == phase.as_str() {
+            continue;
+        }
+
+        // Skips hackathons whose phase was set by hand since they were read
+        let result = Hackathons::update_many()
+            .col_expr(hackathons::Column::Phase, Expr::value(phase.as_str()))
+            .filter(hackathons::Column::Id.eq(hackathon.id))
+            .filter(hackathons::Column::PhaseManual.eq(false))
+            .filter(hackathons::Column::Phase.eq(&hackathon.phase))
+            .exec(db)
+            .await?;
+
+        if result.rows_affected > 0 {
+            tracing::info!(
+                "Hackathon {} moved from {} to {}",
+                hackathon.slug,
+                hackathon.phase,
+                phase
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply scheduled transitions now and then periodically in the background
+pub fn spawn_scheduler(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = apply_scheduled_transitions(&db).await {
+                tracing::error!("Failed to apply hackathon phase transitions: {:?}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::error::AppError;
+
+    fn at(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    /// A published hackathon with every deadline set
+    fn published() -> hackathons::Model {
+        hackathons::Model {
+            id: 1,
+            name: "TartanHacks".to_string(),
+            slug: "tartanhacks".to_string(),
+            description: None,
+            start_date: at("2025-03-15 09:00:00"),
+            end_date: at("2025-03-16 18:00:00"),
+            is_active: true,
+            created_at: at("2025-01-01 00:00:00"),
+            updated_at: at("2025-01-01 00:00:00"),
+            archived_at: None,
+            phase: "draft".to_string(),
+            phase_manual: false,
+            applications_open_at: Some(at("2025-02-01 00:00:00")),
+            applications_close_at: Some(at("2025-03-01 00:00:00")),
+            hacking_end_at: Some(at("2025-03-16 09:00:00")),
+            judging_start_at: Some(at("2025-03-16 10:00:00")),
+            results_at: Some(at("2025-03-16 17:00:00")),
+            settings: json!({}),
+            organization_id: None,
+        }
+    }
+
+    /// Fields rejected by [`validate_schedule`]
+    fn schedule_errors(hackathon: &hackathons::Model) -> Vec<(String, String)> {
+        let mut v = Validator::default();
+        validate_schedule(hackathon, &mut v);
+        match v.finish() {
+            Ok(()) => Vec::new(),
+            Err(AppError::Validation(fields)) => {
+                fields.into_iter().map(|f| (f.field, f.message)).collect()
+            }
+            Err(e) => panic!("unexpected error {e:?}"),
+        }
+    }
+
+    #[test]
+    fn phases_start_at_their_deadline() {
+        let hackathon = published();
+        for (deadline, before, after) in [
+            (
+                "2025-02-01 00:00:00",
+                HackathonPhase::Draft,
+                HackathonPhase::ApplicationsOpen,
+            ),
+            (
+                "2025-03-01 00:00:00",
+                HackathonPhase::ApplicationsOpen,
+                HackathonPhase::ApplicationsClosed,
+            ),
+            (
+                "2025-03-15 09:00:00",
+                HackathonPhase::ApplicationsClosed,
+                HackathonPhase::EventLive,
+            ),
+            (
+                "2025-03-16 09:00:00",
+                HackathonPhase::EventLive,
+                HackathonPhase::HackingEnded,
+            ),
+            (
+                "2025-03-16 10:00:00",
+                HackathonPhase::HackingEnded,
+                HackathonPhase::Judging,
+            ),
+            (
+                "2025-03-16 17:00:00",
+                HackathonPhase::Judging,
+                HackathonPhase::ResultsPublished,
+            ),
+        ] {
+            let deadline = at(deadline);
+            let just_before = deadline - chrono::Duration::seconds(1);
+            assert_eq!(scheduled(&hackathon, just_before), before, "{just_before}");
+            assert_eq!(scheduled(&hackathon, deadline), after, "{deadline}");
+        }
+    }
+
+    #[test]
+    fn unpublished_and_archived_hackathons_ignore_the_schedule() {
+        let now = at("2025-03-15 12:00:00");
+
+        let draft = hackathons::Model {
+            is_active: false,
+            ..published()
+        };
+        assert_eq!(scheduled(&draft, now), HackathonPhase::Draft);
+
+        let archived = hackathons::Model {
+            is_active: false,
+            archived_at: Some(at("2025-01-02 00:00:00")),
+            ..published()
+        };
+        assert_eq!(scheduled(&archived, now), HackathonPhase::Archived);
+    }
+
+    #[test]
+    fn missing_deadlines_fall_back() {
+        let hackathon = hackathons::Model {
+            applications_open_at: None,
+            applications_close_at: None,
+            hacking_end_at: None,
+            judging_start_at: None,
+            results_at: None,
+            ..published()
+        };
+
+        // Applications open as soon as the hackathon is published
+        assert_eq!(
+            scheduled(&hackathon, at("2024-01-01 00:00:00")),
+            HackathonPhase::ApplicationsOpen
+        );
+        assert_eq!(
+            scheduled(&hackathon, at("2025-03-16 17:59:59")),
+            HackathonPhase::EventLive
+        );
+        assert_eq!(
+            scheduled(&hackathon, at("2025-03-16 18:00:00")),
+            HackathonPhase::HackingEnded
+        );
+    }
+
+    #[test]
+    fn deadlines_in_order_are_valid() {
+        assert!(schedule_errors(&published()).is_empty());
+
+        // Deadlines may coincide
+        let hackathon = hackathons::Model {
+            judging_start_at: Some(at("2025-03-16 09:00:00")),
+            results_at: Some(at("2025-03-16 09:00:00")),
+            ..published()
+        };
+        assert!(schedule_errors(&hackathon).is_empty());
+    }
+
+    #[test]
+    fn deadlines_out_of_order_are_rejected() {
+        let hackathon = hackathons::Model {
+            applications_close_at: Some(at("2025-01-15 00:00:00")),
+            ..published()
+        };
+        assert_eq!(
+            schedule_errors(&hackathon),
+            [(
+                "applications_close_at".to_string(),
+                "Must not be before applications_open_at".to_string()
+            )]
+        );
+
+        let hackathon = hackathons::Model {
+            results_at: Some(at("2025-03-16 09:30:00")),
+            ..published()
+        };
+        assert_eq!(
+            schedule_errors(&hackathon),
+            [(
+                "results_at".to_string(),
+                "Must not be before judging_start_at".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn unset_deadlines_are_skipped_when_ordering() {
+        // Without a closing date, the start is compared with the opening date
+        let hackathon = hackathons::Model {
+            applications_open_at: Some(at("2025-03-20 00:00:00")),
+            applications_close_at: None,
+            ..published()
+        };
+        assert_eq!(
+            schedule_errors(&hackathon),
+            [(
+                "start_date".to_string(),
+                "Must not be before applications_open_at".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn end_date_is_checked_against_the_start() {
+        // The end date still has to follow the start when hacking ends separately
+        let hackathon = hackathons::Model {
+            end_date: at("2025-03-14 00:00:00"),
+            ..published()
+        };
+        assert_eq!(
+            schedule_errors(&hackathon),
+            [(
+                "end_date".to_string(),
+                "Must not be before start_date".to_string()
+            )]
+        );
+
+        let hackathon = hackathons::Model {
+            end_date: at("2025-03-14 00:00:00"),
+            hacking_end_at: None,
+            judging_start_at: None,
+            results_at: None,
+            ..published()
+        };
+        assert_eq!(
+            schedule_errors(&hackathon),
+            [(
+                "end_date".to_string(),
+                "Must not be before start_date".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn hacking_cannot_end_after_the_event() {
+        let hackathon = hackathons::Model {
+            hacking_end_at: Some(at("2025-03-16 19:00:00")),
+            judging_start_at: None,
+            results_at: None,
+            ..published()
+        };
+        assert_eq!(
+            schedule_errors(&hackathon),
+            [(
+                "end_date".to_string(),
+                "Must not be before hacking_end_at".to_string()
+            )]
+        );
+    }
+}
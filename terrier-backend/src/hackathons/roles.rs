@@ -0,0 +1,95 @@
+//! Roles that organizers give to existing users, such as mentors and volunteers.
+//!
+//! A user has at most one role in each hackathon, so giving a role to someone who already
+//! has another one is rejected rather than replacing it.
+
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseTransaction, EntityTrait, QueryFilter,
+    TransactionTrait,
+};
+
+use crate::{
+    AppState,
+    admins::handlers::find_user_by_email,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    entities::{prelude::*, user_hackathon_roles, users},
+    error::AppError,
+};
+
+/// Give a user `assigned` in the hackathon, returning whether they did not already have it
+pub async fn assign(
+    txn: &DatabaseTransaction,
+    hackathon_id: i32,
+    user_id: i32,
+    assigned: &str,
+) -> Result<bool, AppError> {
+    let existing = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.eq(user_id))
+        .one(txn)
+        .await?;
+
+    match existing {
+        Some(r) if r.role != assigned => Err(AppError::Conflict(
+            "User already has another role in this hackathon",
+        )),
+        Some(_) => Ok(false),
+        None => {
+            user_hackathon_roles::ActiveModel {
+                user_id: Set(user_id),
+                hackathon_id: Set(hackathon_id),
+                role: Set(assigned.to_string()),
+                ..Default::default()
+            }
+            .insert(txn)
+            .await?;
+
+            Ok(true)
+        }
+    }
+}
+
+/// Take `assigned` away from a user, returning whether they had it
+pub async fn unassign(
+    txn: &DatabaseTransaction,
+    hackathon_id: i32,
+    user_id: i32,
+    assigned: &str,
+) -> Result<bool, AppError> {
+    let result = UserHackathonRoles::delete_many()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.eq(user_id))
+        .filter(user_hackathon_roles::Column::Role.eq(assigned))
+        .exec(txn)
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
+
+/// Give the user with `email` the `assigned` role, audited as `<assigned>.add` when it is new
+pub async fn assign_by_email(
+    state: &AppState,
+    role: &HackathonRole,
+    email: &str,
+    assigned: &str,
+) -> Result<users::Model, AppError> {
+    let user = find_user_by_email(state, email).await?;
+
+    let txn = state.db.begin().await?;
+    let added = assign(&txn, role.hackathon_id, user.id, assigned).await?;
+    if added {
+        audit::record(
+            &txn,
+            Entry::in_hackathon(role, &format!("{assigned}.add"), "user", user.id),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+
+    if added {
+        tracing::info!("Added {} as a {} in {}", user.email, assigned, role.slug);
+    }
+
+    Ok(user)
+}
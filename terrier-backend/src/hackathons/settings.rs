@@ -0,0 +1,334 @@
+//! Per-hackathon settings, stored as a JSON document on the hackathon.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::{
+    entities::hackathons,
+    error::AppError,
+    validation::{MAX_NAME_LENGTH, Validate, Validator},
+};
+
+/// Profile fields a hackathon can require applicants to fill in
+pub const PROFILE_FIELDS: [&str; 13] = [
+    "pronouns",
+    "phone",
+    "school",
+    "major",
+    "graduation_year",
+    "dietary_restrictions",
+    "accessibility_needs",
+    "shirt_size",
+    "emergency_contact",
+    "github",
+    "linkedin",
+    "website",
+    "resume",
+];
+
+const MAX_TEAM_SIZE: u32 = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JudgingMode {
+    /// Judges walk between tables and score the projects they visit
+    #[default]
+    Expo,
+    /// Teams present to a panel of judges one at a time
+    Panel,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Branding {
+    /// Hex color such as `#c41230`
+    pub primary_color: Option<String>,
+    pub secondary_color: Option<String>,
+    pub logo_url: Option<String>,
+}
+
+/// Which modules are enabled for the hackathon
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Features {
+    pub applications: bool,
+    pub teams: bool,
+    pub judging: bool,
+    pub resume_book: bool,
+    pub sponsors: bool,
+    pub mentors: bool,
+    pub hardware: bool,
+    pub reimbursements: bool,
+    pub meals: bool,
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        // Modules beyond the core application and judging flow are opt-in
+        Features {
+            applications: true,
+            teams: true,
+            judging: true,
+            resume_book: false,
+            sponsors: false,
+            mentors: false,
+            hardware: false,
+            reimbursements: false,
+            meals: false,
+        }
+    }
+}
+
+/// Where participants travel from, which sets how much each of them can be reimbursed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReimbursementRegion {
+    pub name: String,
+    /// Most a participant travelling from this region can be reimbursed, in cents
+    pub cap_cents: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Reimbursements {
+    /// Total that can be approved across every request, in cents
+    pub budget_cents: Option<i64>,
+    pub regions: Vec<ReimbursementRegion>,
+}
+
+impl Reimbursements {
+    pub fn region(&self, name: &str) -> Option<&ReimbursementRegion> {
+        self.regions.iter().find(|r| r.name == name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct HackathonSettings {
+    pub max_team_size: u32,
+    /// Applications close once this many have been submitted
+    pub max_applicants: Option<u32>,
+    pub judging_mode: JudgingMode,
+    /// Fields applicants must fill in on their profile, from [`PROFILE_FIELDS`]
+    pub required_profile_fields: Vec<String>,
+    pub branding: Branding,
+    pub contact_email: Option<String>,
+    pub code_of_conduct_url: Option<String>,
+    pub features: Features,
+    pub reimbursements: Reimbursements,
+}
+
+impl Default for HackathonSettings {
+    fn default() -> Self {
+        HackathonSettings {
+            max_team_size: 4,
+            max_applicants: None,
+            judging_mode: JudgingMode::default(),
+            required_profile_fields: Vec::new(),
+            branding: Branding::default(),
+            contact_email: None,
+            code_of_conduct_url: None,
+            features: Features::default(),
+            reimbursements: Reimbursements::default(),
+        }
+    }
+}
+
+impl HackathonSettings {
+    /// The hackathon's settings, with defaults for anything it has not set
+    pub fn of(hackathon: &hackathons::Model) -> Self {
+        serde_json::from_value(hackathon.settings.clone()).unwrap_or_else(|e| {
+            tracing::warn!("Invalid settings for hackathon {}: {}", hackathon.slug, e);
+            HackathonSettings::default()
+        })
+    }
+
+    /// Read settings sent by a client, naming the field that has an unknown key or the wrong
+    /// type
+    pub fn parse(value: Value) -> Result<Self, AppError> {
+        serde_path_to_error::deserialize(value).map_err(|e| {
+            let field = match e.path().to_string() {
+                path if path == "." => "settings".to_string(),
+                path => path,
+            };
+            AppError::invalid(field, e.into_inner().to_string())
+        })
+    }
+}
+
+impl Validate for HackathonSettings {
+    fn validate(&self, v: &mut Validator) {
+        v.range("max_team_size", self.max_team_size, 1..=MAX_TEAM_SIZE);
+
+        if let Some(max_applicants) = self.max_applicants {
+            v.at_least("max_applicants", max_applicants, 1);
+        }
+
+        for field in &self.required_profile_fields {
+            v.one_of("required_profile_fields", field, &PROFILE_FIELDS);
+        }
+
+        for (field, color) in [
+            ("branding.primary_color", &self.branding.primary_color),
+            ("branding.secondary_color", &self.branding.secondary_color),
+        ] {
+            if color.as_deref().is_some_and(|c| !is_hex_color(c)) {
+                v.error(field, "Must be a hex color such as #c41230");
+            }
+        }
+
+        for (field, url) in [
+            ("branding.logo_url", &self.branding.logo_url),
+            ("code_of_conduct_url", &self.code_of_conduct_url),
+        ] {
+            if let Some(url) = url {
+                v.url(field, url);
+            }
+        }
+
+        if let Some(email) = &self.contact_email {
+            v.email("contact_email", email);
+        }
+
+        if let Some(budget) = self.reimbursements.budget_cents {
+            v.at_least("reimbursements.budget_cents", budget, 0);
+        }
+
+        let regions = &self.reimbursements.regions;
+        for (i, region) in regions.iter().enumerate() {
+            let field = format!("reimbursements.regions.{i}");
+            if regions[..i].iter().any(|r| r.name == region.name) {
+                v.error(&format!("{field}.name"), "Region names must be unique");
+            }
+            v.required(&format!("{field}.name"), &region.name, MAX_NAME_LENGTH);
+            v.at_least(&format!("{field}.cap_cents"), region.cap_cents, 0);
+        }
+    }
+}
+
+/// Apply a JSON merge patch (RFC 7386), where `null` removes a field
+pub fn merge_patch(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(&key);
+            } else {
+                merge_patch(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Apply `patch` to the default settings, as the settings endpoint does
+    fn patched(patch: Value) -> Result<HackathonSettings, AppError> {
+        let mut settings = serde_json::to_value(HackathonSettings::default()).unwrap();
+        merge_patch(&mut settings, patch);
+        HackathonSettings::parse(settings)
+    }
+
+    /// The fields named in the error from applying `patch`
+    fn invalid_fields(patch: Value) -> Vec<String> {
+        match patched(patch) {
+            Err(AppError::Validation(fields)) => fields.into_iter().map(|f| f.field).collect(),
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn fields_not_in_the_patch_are_kept() {
+        let mut target = json!({ "a": 1, "b": { "c": 2, "d": 3 } });
+        merge_patch(&mut target, json!({ "a": 4 }));
+        assert_eq!(target, json!({ "a": 4, "b": { "c": 2, "d": 3 } }));
+    }
+
+    #[test]
+    fn null_removes_a_field() {
+        let mut target = json!({ "a": 1, "b": { "c": 2, "d": 3 } });
+        merge_patch(
+            &mut target,
+            json!({ "a": null, "b": { "c": null }, "e": null }),
+        );
+        assert_eq!(target, json!({ "b": { "d": 3 } }));
+
+        // Removed fields go back to their defaults
+        let settings = patched(json!({ "max_team_size": 6 })).unwrap();
+        assert_eq!(settings.max_team_size, 6);
+        let mut value = serde_json::to_value(settings).unwrap();
+        merge_patch(&mut value, json!({ "max_team_size": null }));
+        let settings: HackathonSettings = serde_json::from_value(value).unwrap();
+        assert_eq!(settings.max_team_size, 4);
+    }
+
+    #[test]
+    fn nested_objects_are_merged() {
+        let settings = patched(json!({
+            "features": { "hardware": true },
+            "branding": { "primary_color": "#c41230" }
+        }))
+        .unwrap();
+
+        assert!(settings.features.hardware);
+        assert!(settings.features.applications);
+        assert_eq!(settings.branding.primary_color.as_deref(), Some("#c41230"));
+        assert_eq!(settings.branding.logo_url, None);
+    }
+
+    #[test]
+    fn arrays_and_values_are_replaced() {
+        let mut target = json!({ "a": [1, 2], "b": { "c": 1 } });
+        merge_patch(&mut target, json!({ "a": [3], "b": 5 }));
+        assert_eq!(target, json!({ "a": [3], "b": 5 }));
+
+        // A patch that is not an object replaces the whole document
+        merge_patch(&mut target, json!([1]));
+        assert_eq!(target, json!([1]));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert_eq!(
+            invalid_fields(json!({ "max_team_sise": 6 })),
+            ["max_team_sise"]
+        );
+        assert_eq!(
+            invalid_fields(json!({ "features": { "raffle": true } })),
+            ["features.raffle"]
+        );
+        // A patch that is not an object replaces every setting
+        assert_eq!(invalid_fields(json!(5)), ["settings"]);
+    }
+
+    #[test]
+    fn wrong_types_name_the_field() {
+        assert_eq!(
+            invalid_fields(json!({ "max_team_size": "six" })),
+            ["max_team_size"]
+        );
+        assert_eq!(
+            invalid_fields(json!({ "features": { "hardware": 1 } })),
+            ["features.hardware"]
+        );
+    }
+}
@@ -0,0 +1,1020 @@
+use std::collections::HashMap;
+
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    AppState,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    badges::handlers::find_by_badge,
+    entities::{hardware_items, hardware_loans, prelude::*, users},
+    error::AppError,
+    hackathons::{handlers::require_feature, phase::HackathonPhase},
+    pagination::{self, Page, PageParams, Sort},
+    validation::{
+        MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, Path, Query, ValidJson, Validate, Validator,
+    },
+};
+
+/// Every status a loan can have
+pub const LOAN_STATUSES: [&str; 6] = [
+    "requested",
+    "approved",
+    "rejected",
+    "checked_out",
+    "returned",
+    "cancelled",
+];
+
+/// Loans that hold units of an item, so they cannot be lent to anyone else
+const RESERVED_STATUSES: [&str; 2] = ["approved", "checked_out"];
+
+const MAX_NOTES_LENGTH: usize = 2000;
+
+const LOAN_SORT: Sort<hardware_loans::Column> = Sort {
+    fields: &[
+        ("created_at", hardware_loans::Column::CreatedAt),
+        ("updated_at", hardware_loans::Column::UpdatedAt),
+    ],
+    default: "-created_at",
+    id: hardware_loans::Column::Id,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct HardwareItemInfo {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    /// Total number owned
+    pub quantity: i32,
+    /// Units not approved for or lent to anyone
+    pub available: i32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Borrower {
+    pub id: i32,
+    pub name: Option<String>,
+    pub email: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LoanInfo {
+    pub id: i32,
+    pub item_id: i32,
+    pub item_name: String,
+    pub borrower: Option<Borrower>,
+    pub quantity: i32,
+    /// One of `requested`, `approved`, `rejected`, `checked_out`, `returned` or `cancelled`
+    pub status: String,
+    pub notes: Option<String>,
+    pub due_at: Option<NaiveDateTime>,
+    /// Checked out and past its due date, or still out after hacking ended
+    pub overdue: bool,
+    pub requested_at: NaiveDateTime,
+    pub checked_out_at: Option<NaiveDateTime>,
+    pub returned_at: Option<NaiveDateTime>,
+}
+
+/// Check the hackathon uses the hardware module
+async fn require_hardware(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    require_feature(state, role.hackathon_id, |f| f.hardware).await?;
+    Ok(())
+}
+
+/// Check the caller can manage the hackathon's inventory
+async fn require_hardware_manager(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+    require_hardware(state, role).await
+}
+
+/// Whether hacking is over, after which nothing more can be lent out and every unreturned
+/// item is overdue
+fn event_over(role: &HackathonRole) -> bool {
+    role.phase > HackathonPhase::EventLive
+}
+
+fn is_overdue(loan: &hardware_loans::Model, event_over: bool, now: NaiveDateTime) -> bool {
+    loan.status == "checked_out" && (event_over || loan.due_at.is_some_and(|d| d < now))
+}
+
+/// [`is_overdue`] as a filter on the loans table
+fn overdue_condition(event_over: bool, now: NaiveDateTime) -> Condition {
+    let condition = Condition::all().add(hardware_loans::Column::Status.eq("checked_out"));
+    if event_over {
+        condition
+    } else {
+        condition
+            .add(hardware_loans::Column::DueAt.is_not_null())
+            .add(hardware_loans::Column::DueAt.lt(now))
+    }
+}
+
+async fn find_item(
+    state: &AppState,
+    role: &HackathonRole,
+    item_id: i32,
+) -> Result<hardware_items::Model, AppError> {
+    HardwareItems::find_by_id(item_id)
+        .filter(hardware_items::Column::HackathonId.eq(role.hackathon_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Hardware item not found"))
+}
+
+/// Units of each item that are approved for or lent to someone
+async fn reserved_quantities(
+    db: &impl ConnectionTrait,
+    item_ids: Vec<i32>,
+) -> Result<HashMap<i32, i32>, AppError> {
+    let loans = HardwareLoans::find()
+        .filter(hardware_loans::Column::ItemId.is_in(item_ids))
+        .filter(hardware_loans::Column::Status.is_in(RESERVED_STATUSES))
+        .all(db)
+        .await?;
+
+    let mut reserved = HashMap::new();
+    for loan in loans {
+        *reserved.entry(loan.item_id).or_insert(0) += loan.quantity;
+    }
+
+    Ok(reserved)
+}
+
+async fn item_info(
+    state: &AppState,
+    item: hardware_items::Model,
+) -> Result<HardwareItemInfo, AppError> {
+    let reserved = reserved_quantities(&state.db, vec![item.id]).await?;
+    let available = item.quantity - reserved.get(&item.id).copied().unwrap_or(0);
+
+    Ok(HardwareItemInfo {
+        id: item.id,
+        name: item.name,
+        description: item.description,
+        quantity: item.quantity,
+        available,
+    })
+}
+
+/// Loans with their items and borrowers
+async fn loan_infos(
+    state: &AppState,
+    role: &HackathonRole,
+    loans: Vec<(hardware_loans::Model, Option<hardware_items::Model>)>,
+) -> Result<Vec<LoanInfo>, AppError> {
+    let user_ids: Vec<i32> = loans.iter().map(|(l, _)| l.user_id).collect();
+    let users: HashMap<i32, users::Model> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let event_over = event_over(role);
+    let now = Utc::now().naive_utc();
+
+    Ok(loans
+        .into_iter()
+        .filter_map(|(loan, item)| {
+            let item = item?;
+            let borrower = users.get(&loan.user_id).map(|u| Borrower {
+                id: u.id,
+                name: u.name.clone(),
+                email: u.email.clone(),
+            });
+
+            Some(LoanInfo {
+                overdue: is_overdue(&loan, event_over, now),
+                id: loan.id,
+                item_id: item.id,
+                item_name: item.name,
+                borrower,
+                quantity: loan.quantity,
+                status: loan.status,
+                notes: loan.notes,
+                due_at: loan.due_at,
+                requested_at: loan.created_at,
+                checked_out_at: loan.checked_out_at,
+                returned_at: loan.returned_at,
+            })
+        })
+        .collect())
+}
+
+async fn loan_info(
+    state: &AppState,
+    role: &HackathonRole,
+    loan: hardware_loans::Model,
+) -> Result<LoanInfo, AppError> {
+    let item = HardwareItems::find_by_id(loan.item_id)
+        .one(&state.db)
+        .await?;
+
+    loan_infos(state, role, vec![(loan, item)])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::internal("Loan missing from its own info"))
+}
+
+async fn find_loan(
+    state: &AppState,
+    role: &HackathonRole,
+    loan_id: i32,
+) -> Result<hardware_loans::Model, AppError> {
+    let loan = HardwareLoans::find_by_id(loan_id)
+        .inner_join(HardwareItems)
+        .filter(hardware_items::Column::HackathonId.eq(role.hackathon_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Loan not found"))?;
+
+    // Participants can only see their own loans
+    if !role.is_organizer() && loan.user_id != role.user_id {
+        return Err(AppError::NotFound("Loan not found"));
+    }
+
+    Ok(loan)
+}
+
+/// Check enough units of an item are left to approve or lend out `quantity` more
+///
+/// The item stays locked until `txn` ends, so the loan must be changed in the same
+/// transaction for concurrent approvals and checkouts to see each other.
+async fn require_available(
+    txn: &DatabaseTransaction,
+    item_id: i32,
+    quantity: i32,
+) -> Result<(), AppError> {
+    let item = HardwareItems::find_by_id(item_id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or(AppError::NotFound("Hardware item not found"))?;
+
+    let reserved = reserved_quantities(txn, vec![item_id]).await?;
+    if item.quantity - reserved.get(&item_id).copied().unwrap_or(0) < quantity {
+        return Err(AppError::Conflict("Not enough units are available"));
+    }
+
+    Ok(())
+}
+
+/// Move a loan from one status to another, failing if someone else changed it first
+async fn transition(
+    db: &impl ConnectionTrait,
+    loan: hardware_loans::Model,
+    from: &[&str],
+    mut active: hardware_loans::ActiveModel,
+) -> Result<hardware_loans::Model, AppError> {
+    if !from.contains(&loan.status.as_str()) {
+        return Err(AppError::Conflict(
+            "Not possible while the loan has this status",
+        ));
+    }
+
+    active.updated_at = Set(Utc::now().naive_utc());
+
+    let result = HardwareLoans::update_many()
+        .set(active)
+        .filter(hardware_loans::Column::Id.eq(loan.id))
+        .filter(hardware_loans::Column::Status.eq(&loan.status))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::Conflict(
+            "The loan was changed by someone else, please reload it",
+        ));
+    }
+
+    HardwareLoans::find_by_id(loan.id)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Loan not found"))
+}
+
+/// List the hackathon's hardware inventory
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/hardware",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Items by name", body = Vec<HardwareItemInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer or participant in this hackathon"),
+        (status = 404, description = "Hackathon not found, or hardware checkout is disabled")
+    ),
+    tag = "Hardware"
+)]
+pub async fn list_items(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<HardwareItemInfo>>, AppError> {
+    if !role.is_organizer() && !role.is_participant() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_hardware(&state, &role).await?;
+
+    let items = HardwareItems::find()
+        .filter(hardware_items::Column::HackathonId.eq(role.hackathon_id))
+        .order_by_asc(hardware_items::Column::Name)
+        .all(&state.db)
+        .await?;
+
+    let reserved = reserved_quantities(&state.db, items.iter().map(|i| i.id).collect()).await?;
+
+    Ok(Json(
+        items
+            .into_iter()
+            .map(|item| HardwareItemInfo {
+                available: item.quantity - reserved.get(&item.id).copied().unwrap_or(0),
+                id: item.id,
+                name: item.name,
+                description: item.description,
+                quantity: item.quantity,
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct HardwareItemRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// Total number owned
+    pub quantity: i32,
+}
+
+impl Validate for HardwareItemRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name, MAX_NAME_LENGTH);
+        v.max_length(
+            "description",
+            self.description.as_deref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
+        v.at_least("quantity", self.quantity, 0);
+    }
+}
+
+/// Add an item to the inventory
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/hardware",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = HardwareItemRequest,
+    responses(
+        (status = 200, description = "Item created", body = HardwareItemInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or hardware checkout is disabled"),
+        (status = 409, description = "An item with this name exists, or the hackathon is archived"),
+        (status = 422, description = "Empty name or negative quantity")
+    ),
+    tag = "Hardware"
+)]
+pub async fn create_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<HardwareItemRequest>,
+) -> Result<Json<HardwareItemInfo>, AppError> {
+    require_hardware_manager(&state, &role).await?;
+
+    let name = req.name.trim();
+
+    let existing = HardwareItems::find()
+        .filter(hardware_items::Column::HackathonId.eq(role.hackathon_id))
+        .filter(hardware_items::Column::Name.eq(name))
+        .one(&state.db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(AppError::Conflict("An item with this name already exists"));
+    }
+
+    let item = hardware_items::ActiveModel {
+        hackathon_id: Set(role.hackathon_id),
+        name: Set(name.to_string()),
+        description: Set(req.description.filter(|d| !d.trim().is_empty())),
+        quantity: Set(req.quantity),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(Json(item_info(&state, item).await?))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateHardwareItemRequest {
+    pub name: Option<String>,
+    /// An empty string clears the description
+    pub description: Option<String>,
+    pub quantity: Option<i32>,
+}
+
+impl Validate for UpdateHardwareItemRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            v.required("name", name, MAX_NAME_LENGTH);
+        }
+        v.max_length(
+            "description",
+            self.description.as_deref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
+        if let Some(quantity) = self.quantity {
+            v.at_least("quantity", quantity, 0);
+        }
+    }
+}
+
+/// Update an inventory item
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}/hardware/{item_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID")
+    ),
+    request_body = UpdateHardwareItemRequest,
+    responses(
+        (status = 200, description = "Item updated", body = HardwareItemInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Item not found, or hardware checkout is disabled"),
+        (status = 409, description = "Name is taken, quantity is below the units lent out, or the hackathon is archived"),
+        (status = 422, description = "Empty name or negative quantity")
+    ),
+    tag = "Hardware"
+)]
+pub async fn update_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<UpdateHardwareItemRequest>,
+) -> Result<Json<HardwareItemInfo>, AppError> {
+    let (_, item_id) = params;
+    require_hardware_manager(&state, &role).await?;
+
+    find_item(&state, &role, item_id).await?;
+
+    // Locking the item stops loans from being approved while the quantity is checked
+    let txn = state.db.begin().await?;
+    let item = HardwareItems::find_by_id(item_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or(AppError::NotFound("Hardware item not found"))?;
+    let mut active: hardware_items::ActiveModel = item.clone().into();
+
+    if let Some(name) = req.name {
+        let name = name.trim();
+        if name != item.name {
+            let existing = HardwareItems::find()
+                .filter(hardware_items::Column::HackathonId.eq(role.hackathon_id))
+                .filter(hardware_items::Column::Name.eq(name))
+                .one(&txn)
+                .await?;
+
+            if existing.is_some() {
+                return Err(AppError::Conflict("An item with this name already exists"));
+            }
+        }
+
+        active.name = Set(name.to_string());
+    }
+
+    if let Some(description) = req.description {
+        active.description = Set(Some(description).filter(|d| !d.trim().is_empty()));
+    }
+
+    if let Some(quantity) = req.quantity {
+        // Units that are approved or lent out cannot be removed from the inventory
+        let reserved = reserved_quantities(&txn, vec![item.id]).await?;
+        if quantity < reserved.get(&item.id).copied().unwrap_or(0) {
+            return Err(AppError::Conflict(
+                "Units that are approved or lent out cannot be removed",
+            ));
+        }
+
+        active.quantity = Set(quantity);
+    }
+
+    active.updated_at = Set(Utc::now().naive_utc());
+
+    let item = active.update(&txn).await?;
+    txn.commit().await?;
+
+    Ok(Json(item_info(&state, item).await?))
+}
+
+/// Remove an item from the inventory, along with its loan history
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/hardware/{item_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 204, description = "Item deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Item not found, or hardware checkout is disabled"),
+        (status = 409, description = "Units are still checked out, or the hackathon is archived")
+    ),
+    tag = "Hardware"
+)]
+pub async fn delete_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, item_id) = params;
+    require_hardware_manager(&state, &role).await?;
+
+    let item = find_item(&state, &role, item_id).await?;
+
+    let checked_out = HardwareLoans::find()
+        .filter(hardware_loans::Column::ItemId.eq(item.id))
+        .filter(hardware_loans::Column::Status.eq("checked_out"))
+        .one(&state.db)
+        .await?;
+
+    if checked_out.is_some() {
+        return Err(AppError::Conflict(
+            "Some units of this item are still lent out",
+        ));
+    }
+
+    HardwareItems::delete_by_id(item.id).exec(&state.db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RequestHardwareRequest {
+    pub quantity: i32,
+    /// What the team is building, or anything else organizers should know
+    pub notes: Option<String>,
+}
+
+impl Validate for RequestHardwareRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.at_least("quantity", self.quantity, 1);
+        v.max_length("notes", self.notes.as_deref(), MAX_NOTES_LENGTH);
+    }
+}
+
+/// Request to borrow an item
+///
+/// Participants with overdue items cannot borrow anything else until they return them.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/hardware/{item_id}/requests",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("item_id" = i32, Path, description = "Item ID")
+    ),
+    request_body = RequestHardwareRequest,
+    responses(
+        (status = 200, description = "Loan requested", body = LoanInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a participant in this hackathon"),
+        (status = 404, description = "Item not found, or hardware checkout is disabled"),
+        (status = 409, description = "Not enough units available, user has overdue items, or hacking has ended"),
+        (status = 422, description = "Quantity is not positive, or notes are too long")
+    ),
+    tag = "Hardware"
+)]
+pub async fn request_item(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<RequestHardwareRequest>,
+) -> Result<Json<LoanInfo>, AppError> {
+    let (_, item_id) = params;
+    if !role.is_participant() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_hardware(&state, &role).await?;
+    if event_over(&role) {
+        return Err(AppError::Conflict("Hacking has ended"));
+    }
+
+    let notes = req.notes.filter(|n| !n.trim().is_empty());
+
+    let item = find_item(&state, &role, item_id).await?;
+
+    let now = Utc::now().naive_utc();
+    let has_overdue = HardwareLoans::find()
+        .inner_join(HardwareItems)
+        .filter(hardware_items::Column::HackathonId.eq(role.hackathon_id))
+        .filter(hardware_loans::Column::UserId.eq(role.user_id))
+        .filter(hardware_loans::Column::Status.eq("checked_out"))
+        .filter(hardware_loans::Column::DueAt.lt(now))
+        .one(&state.db)
+        .await?
+        .is_some();
+
+    if has_overdue {
+        return Err(AppError::Conflict(
+            "Return your overdue hardware before borrowing more",
+        ));
+    }
+
+    let txn = state.db.begin().await?;
+    require_available(&txn, item.id, req.quantity).await?;
+
+    let loan = hardware_loans::ActiveModel {
+        item_id: Set(item.id),
+        user_id: Set(role.user_id),
+        quantity: Set(req.quantity),
+        status: Set("requested".to_string()),
+        notes: Set(notes),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        "User {} requested {} x {} in {}",
+        role.user_id,
+        loan.quantity,
+        item.name,
+        role.slug
+    );
+
+    Ok(Json(loan_info(&state, &role, loan).await?))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListLoansQuery {
+    /// Only return loans with this status
+    status: Option<String>,
+    /// Only return overdue loans, or only loans that are not overdue
+    overdue: Option<bool>,
+    /// Only return loans of this user, for organizers
+    user_id: Option<i32>,
+    /// Only return loans of the participant with this badge token, for organizers
+    badge: Option<String>,
+}
+
+/// List hardware loans
+///
+/// Organizers see every loan in the hackathon, and can look up a borrower by scanning their
+/// badge. Participants see their own loans. Can be sorted by `created_at` or `updated_at`.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/hardware/loans",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ListLoansQuery,
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Loans, newest first by default", body = Page<LoanInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer or participant in this hackathon"),
+        (status = 404, description = "No participant with this badge, or hardware checkout is disabled"),
+        (status = 422, description = "Unknown status, or invalid page, sort or cursor")
+    ),
+    tag = "Hardware"
+)]
+pub async fn list_loans(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ListLoansQuery>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<LoanInfo>>, AppError> {
+    if !role.is_organizer() && !role.is_participant() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_hardware(&state, &role).await?;
+
+    let mut select = HardwareLoans::find()
+        .find_also_related(HardwareItems)
+        .filter(hardware_items::Column::HackathonId.eq(role.hackathon_id));
+
+    if role.is_organizer() {
+        if let Some(user_id) = query.user_id {
+            select = select.filter(hardware_loans::Column::UserId.eq(user_id));
+        }
+
+        if let Some(token) = query.badge {
+            let participant = find_by_badge(&state, role.hackathon_id, &token).await?;
+            select = select.filter(hardware_loans::Column::UserId.eq(participant.user_id));
+        }
+    } else {
+        select = select.filter(hardware_loans::Column::UserId.eq(role.user_id));
+    }
+
+    if let Some(status) = query.status {
+        if !LOAN_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::invalid("status", "Unknown loan status"));
+        }
+        select = select.filter(hardware_loans::Column::Status.eq(status));
+    }
+
+    if let Some(overdue) = query.overdue {
+        let condition = overdue_condition(event_over(&role), Utc::now().naive_utc());
+        select = select.filter(if overdue { condition } else { condition.not() });
+    }
+
+    let mut page =
+        pagination::paginate(&state.db, select, &page, &LOAN_SORT, |(loan, _)| loan).await?;
+    let loans = std::mem::take(&mut page.items);
+
+    Ok(Json(
+        page.with_items(loan_infos(&state, &role, loans).await?),
+    ))
+}
+
+/// Approve a loan request, reserving the units for the borrower
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/hardware/loans/{loan_id}/approve",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("loan_id" = i32, Path, description = "Loan ID")
+    ),
+    responses(
+        (status = 200, description = "Loan approved", body = LoanInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Loan not found, or hardware checkout is disabled"),
+        (status = 409, description = "Loan is not requested, not enough units are available, or hacking has ended")
+    ),
+    tag = "Hardware"
+)]
+pub async fn approve_loan(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<LoanInfo>, AppError> {
+    let (_, loan_id) = params;
+    require_hardware_manager(&state, &role).await?;
+    if event_over(&role) {
+        return Err(AppError::Conflict("Hacking has ended"));
+    }
+
+    let loan = find_loan(&state, &role, loan_id).await?;
+
+    let txn = state.db.begin().await?;
+    require_available(&txn, loan.item_id, loan.quantity).await?;
+
+    let active = hardware_loans::ActiveModel {
+        status: Set("approved".to_string()),
+        reviewed_by: Set(Some(role.user_id)),
+        ..Default::default()
+    };
+    let loan = transition(&txn, loan, &["requested"], active).await?;
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "hardware_loan.approve", "hardware_loan", loan.id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Json(loan_info(&state, &role, loan).await?))
+}
+
+/// Reject a loan request, or withdraw an approval before the item is handed over
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/hardware/loans/{loan_id}/reject",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("loan_id" = i32, Path, description = "Loan ID")
+    ),
+    responses(
+        (status = 200, description = "Loan rejected", body = LoanInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Loan not found, or hardware checkout is disabled"),
+        (status = 409, description = "Item was already handed over, or the hackathon is archived")
+    ),
+    tag = "Hardware"
+)]
+pub async fn reject_loan(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<LoanInfo>, AppError> {
+    let (_, loan_id) = params;
+    require_hardware_manager(&state, &role).await?;
+
+    let loan = find_loan(&state, &role, loan_id).await?;
+    let active = hardware_loans::ActiveModel {
+        status: Set("rejected".to_string()),
+        reviewed_by: Set(Some(role.user_id)),
+        ..Default::default()
+    };
+    let txn = state.db.begin().await?;
+    let loan = transition(&txn, loan, &["requested", "approved"], active).await?;
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "hardware_loan.reject", "hardware_loan", loan.id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Json(loan_info(&state, &role, loan).await?))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CheckOutLoanRequest {
+    /// Defaults to the end of hacking
+    pub due_at: Option<NaiveDateTime>,
+}
+
+impl Validate for CheckOutLoanRequest {
+    fn validate(&self, v: &mut Validator) {
+        if self.due_at.is_some_and(|d| d < Utc::now().naive_utc()) {
+            v.error("due_at", "Must be in the future");
+        }
+    }
+}
+
+/// Hand an item over to the borrower
+///
+/// Requests can be checked out directly without approving them first. Nothing can be checked
+/// out once hacking has ended.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/hardware/loans/{loan_id}/checkout",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("loan_id" = i32, Path, description = "Loan ID")
+    ),
+    request_body = CheckOutLoanRequest,
+    responses(
+        (status = 200, description = "Item checked out", body = LoanInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Loan not found, or hardware checkout is disabled"),
+        (status = 409, description = "Loan is not requested or approved, not enough units are available, or hacking has ended"),
+        (status = 422, description = "Due date is in the past")
+    ),
+    tag = "Hardware"
+)]
+pub async fn check_out_loan(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<CheckOutLoanRequest>,
+) -> Result<Json<LoanInfo>, AppError> {
+    let (_, loan_id) = params;
+    require_hardware_manager(&state, &role).await?;
+    if event_over(&role) {
+        return Err(AppError::Conflict("Hacking has ended"));
+    }
+
+    let loan = find_loan(&state, &role, loan_id).await?;
+
+    let due_at = match req.due_at {
+        Some(due_at) => due_at,
+        None => {
+            let hackathon = Hackathons::find_by_id(role.hackathon_id)
+                .one(&state.db)
+                .await?
+                .ok_or(AppError::NotFound("Hackathon not found"))?;
+            hackathon.hacking_end_at.unwrap_or(hackathon.end_date)
+        }
+    };
+
+    let active = hardware_loans::ActiveModel {
+        status: Set("checked_out".to_string()),
+        reviewed_by: Set(Some(role.user_id)),
+        due_at: Set(Some(due_at)),
+        checked_out_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    let txn = state.db.begin().await?;
+
+    // Approved loans already hold their units
+    if loan.status == "requested" {
+        require_available(&txn, loan.item_id, loan.quantity).await?;
+    }
+
+    let loan = transition(&txn, loan, &["requested", "approved"], active).await?;
+    audit::record(
+        &txn,
+        Entry {
+            diff: Some(json!({ "due_at": due_at })),
+            ..Entry::in_hackathon(&role, "hardware_loan.checkout", "hardware_loan", loan.id)
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Json(loan_info(&state, &role, loan).await?))
+}
+
+/// Record that the borrower returned an item
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/hardware/loans/{loan_id}/return",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("loan_id" = i32, Path, description = "Loan ID")
+    ),
+    responses(
+        (status = 200, description = "Item returned", body = LoanInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Loan not found, or hardware checkout is disabled"),
+        (status = 409, description = "Item is not checked out, or the hackathon is archived")
+    ),
+    tag = "Hardware"
+)]
+pub async fn return_loan(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<LoanInfo>, AppError> {
+    let (_, loan_id) = params;
+    require_hardware_manager(&state, &role).await?;
+
+    let loan = find_loan(&state, &role, loan_id).await?;
+    let active = hardware_loans::ActiveModel {
+        status: Set("returned".to_string()),
+        returned_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+    let txn = state.db.begin().await?;
+    let loan = transition(&txn, loan, &["checked_out"], active).await?;
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "hardware_loan.return", "hardware_loan", loan.id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Json(loan_info(&state, &role, loan).await?))
+}
+
+/// Cancel the current user's request before the item is handed over
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/hardware/loans/{loan_id}/cancel",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("loan_id" = i32, Path, description = "Loan ID")
+    ),
+    responses(
+        (status = 200, description = "Request cancelled", body = LoanInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the borrower"),
+        (status = 404, description = "Loan not found, or hardware checkout is disabled"),
+        (status = 409, description = "Item was already handed over, or the hackathon is archived")
+    ),
+    tag = "Hardware"
+)]
+pub async fn cancel_loan(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<LoanInfo>, AppError> {
+    let (_, loan_id) = params;
+    require_hardware(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let loan = find_loan(&state, &role, loan_id).await?;
+    if loan.user_id != role.user_id {
+        return Err(AppError::Forbidden);
+    }
+
+    let active = hardware_loans::ActiveModel {
+        status: Set("cancelled".to_string()),
+        ..Default::default()
+    };
+    let loan = transition(&state.db, loan, &["requested", "approved"], active).await?;
+
+    Ok(Json(loan_info(&state, &role, loan).await?))
+}
@@ -0,0 +1,732 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json,
+    extract::{Multipart, State},
+    http::StatusCode,
+};
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, DatabaseTransaction, EntityTrait, QueryFilter, TransactionTrait,
+    sea_query::{Expr, Func},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    AppState,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    entities::{
+        hackathon_invites, prelude::*, sponsor_representatives, sponsors, user_hackathon_roles,
+        users,
+    },
+    error::AppError,
+    hackathons::{phase::HackathonPhase, roles},
+    pagination::{self, Page, PageParams, Sort},
+    validation::{MAX_NAME_LENGTH, Path, Query, Validator},
+};
+
+/// Roles that can be handed out from a spreadsheet. Organizers are added one at a time, and
+/// participants come in through applications.
+pub const IMPORTABLE_ROLES: [&str; 4] = ["judge", "mentor", "sponsor", "volunteer"];
+
+/// Largest CSV file that can be imported
+pub const MAX_IMPORT_SIZE: usize = 1024 * 1024;
+
+const MAX_IMPORT_ROWS: usize = 2000;
+
+const INVITE_SORT: Sort<hackathon_invites::Column> = Sort {
+    fields: &[
+        ("created_at", hackathon_invites::Column::CreatedAt),
+        ("email", hackathon_invites::Column::Email),
+    ],
+    default: "-created_at",
+    id: hackathon_invites::Column::Id,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct InviteInfo {
+    pub id: i32,
+    pub email: String,
+    pub name: Option<String>,
+    pub role: String,
+    /// The sponsor an invited representative will belong to
+    pub sponsor_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<hackathon_invites::Model> for InviteInfo {
+    fn from(invite: hackathon_invites::Model) -> Self {
+        InviteInfo {
+            id: invite.id,
+            email: invite.email,
+            name: invite.name,
+            role: invite.role,
+            sponsor_id: invite.sponsor_id,
+            created_at: invite.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportRolesQuery {
+    /// Check every row and report what would happen, without changing anything
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportRowResult {
+    /// Line of the row in the file, where the header is line 1
+    pub line: u64,
+    pub email: String,
+    pub role: String,
+    /// `assigned` to an existing user, `invited` until someone signs up with the email,
+    /// `unchanged` if the user or invite already has this role, or `error`
+    pub outcome: String,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportResult {
+    /// Whether this was a preview, in which case nothing was changed
+    pub dry_run: bool,
+    pub assigned: usize,
+    pub invited: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+    pub rows: Vec<ImportRowResult>,
+}
+
+/// A row of the import file
+struct ImportRow {
+    line: u64,
+    email: String,
+    name: Option<String>,
+    role: String,
+    sponsor: Option<String>,
+}
+
+/// What importing a row does
+#[derive(Debug, PartialEq)]
+enum Plan {
+    Assign {
+        user_id: i32,
+        sponsor_id: Option<i32>,
+    },
+    Invite {
+        sponsor_id: Option<i32>,
+    },
+    Unchanged,
+    Error(String),
+}
+
+/// Import judges, mentors, sponsors and volunteers from a CSV file
+///
+/// The file needs `email` and `role` columns, and can have `name` and `sponsor` columns.
+/// Sponsor representatives must name an existing sponsor. Users who already have an account
+/// get the role straight away, and everyone else is invited and gets the role when they
+/// first sign in with a verified email.
+///
+/// Rows with errors are skipped and the rest are imported, so preview with `dry_run` first.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/roles/import",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ImportRolesQuery
+    ),
+    request_body(content_type = "multipart/form-data", description = "CSV file in a `file` field"),
+    responses(
+        (status = 200, description = "What happened to each row", body = ImportResult),
+        (status = 400, description = "Missing file field, or the file is not valid CSV"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 409, description = "Hackathon is archived"),
+        (status = 413, description = "File is larger than 1 MB"),
+        (status = 422, description = "Missing a required column, or too many rows")
+    ),
+    tag = "Invites"
+)]
+pub async fn import_roles(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ImportRolesQuery>,
+    mut multipart: Multipart,
+) -> Result<Json<ImportResult>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let mut upload = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| AppError::BadRequest("Invalid multipart upload"))?
+    {
+        if field.name() == Some("file") {
+            upload = Some(field.bytes().await.map_err(|_| AppError::PayloadTooLarge)?);
+        }
+    }
+
+    let content = upload.ok_or(AppError::BadRequest("Missing file field"))?;
+    if content.len() > MAX_IMPORT_SIZE {
+        return Err(AppError::PayloadTooLarge);
+    }
+
+    let rows = parse_rows(&content)?;
+
+    let txn = state.db.begin().await?;
+    let mut plans = plan_import(&txn, &role, &rows).await?;
+
+    if !query.dry_run {
+        for (row, plan) in rows.iter().zip(plans.iter_mut()) {
+            // A role or invite added since the plan was made only fails its own row
+            let savepoint = txn.begin().await?;
+            match apply_row(&savepoint, &role, row, plan).await {
+                Ok(()) => savepoint.commit().await?,
+                Err(AppError::Conflict(message)) => {
+                    savepoint.rollback().await?;
+                    *plan = Plan::Error(message.to_string());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    let mut result = ImportResult {
+        dry_run: query.dry_run,
+        assigned: 0,
+        invited: 0,
+        unchanged: 0,
+        failed: 0,
+        rows: Vec::with_capacity(rows.len()),
+    };
+
+    for (row, plan) in rows.into_iter().zip(plans) {
+        let (outcome, error) = match plan {
+            Plan::Assign { .. } => {
+                result.assigned += 1;
+                ("assigned", None)
+            }
+            Plan::Invite { .. } => {
+                result.invited += 1;
+                ("invited", None)
+            }
+            Plan::Unchanged => {
+                result.unchanged += 1;
+                ("unchanged", None)
+            }
+            Plan::Error(error) => {
+                result.failed += 1;
+                ("error", Some(error))
+            }
+        };
+
+        result.rows.push(ImportRowResult {
+            line: row.line,
+            email: row.email,
+            role: row.role,
+            outcome: outcome.to_string(),
+            error,
+        });
+    }
+
+    // A dry run only read from the transaction, which is rolled back when dropped
+    if !query.dry_run {
+        audit::record(
+            &txn,
+            Entry {
+                diff: Some(json!({
+                    "assigned": result.assigned,
+                    "invited": result.invited,
+                    "failed": result.failed,
+                })),
+                ..Entry::in_hackathon(&role, "role.import", "hackathon", role.hackathon_id)
+            },
+        )
+        .await?;
+
+        txn.commit().await?;
+    }
+
+    if !query.dry_run {
+        tracing::info!(
+            "Imported roles in {}: {} assigned, {} invited",
+            role.slug,
+            result.assigned,
+            result.invited
+        );
+    }
+
+    Ok(Json(result))
+}
+
+/// Carry out the plan for a row
+async fn apply_row(
+    txn: &DatabaseTransaction,
+    role: &HackathonRole,
+    row: &ImportRow,
+    plan: &Plan,
+) -> Result<(), AppError> {
+    match plan {
+        Plan::Assign {
+            user_id,
+            sponsor_id,
+        } => {
+            roles::assign(txn, role.hackathon_id, *user_id, &row.role).await?;
+
+            if let Some(sponsor_id) = sponsor_id {
+                sponsor_representatives::ActiveModel {
+                    sponsor_id: Set(*sponsor_id),
+                    user_id: Set(*user_id),
+                    ..Default::default()
+                }
+                .insert(txn)
+                .await?;
+            }
+        }
+        Plan::Invite { sponsor_id } => {
+            hackathon_invites::ActiveModel {
+                hackathon_id: Set(role.hackathon_id),
+                email: Set(row.email.clone()),
+                name: Set(row.name.clone()),
+                role: Set(row.role.clone()),
+                sponsor_id: Set(*sponsor_id),
+                invited_by: Set(Some(role.user_id)),
+                ..Default::default()
+            }
+            .insert(txn)
+            .await?;
+        }
+        Plan::Unchanged | Plan::Error(_) => {}
+    }
+
+    Ok(())
+}
+
+fn parse_rows(content: &[u8]) -> Result<Vec<ImportRow>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content);
+
+    let headers = reader
+        .headers()
+        .map_err(|_| AppError::BadRequest("The file is not valid CSV"))?;
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+    };
+
+    let (Some(email_column), Some(role_column)) = (column("email"), column("role")) else {
+        return Err(AppError::invalid(
+            "file",
+            "Must have a header row with email and role columns",
+        ));
+    };
+    let name_column = column("name");
+    let sponsor_column = column("sponsor");
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|_| AppError::BadRequest("The file is not valid CSV"))?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        if rows.len() == MAX_IMPORT_ROWS {
+            return Err(AppError::invalid(
+                "file",
+                format!("Must have at most {MAX_IMPORT_ROWS} rows"),
+            ));
+        }
+
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|i| record.get(i))
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        rows.push(ImportRow {
+            line: record.position().map(|p| p.line()).unwrap_or_default(),
+            email: field(Some(email_column)).unwrap_or_default().to_lowercase(),
+            name: field(name_column),
+            role: field(Some(role_column)).unwrap_or_default().to_lowercase(),
+            sponsor: field(sponsor_column),
+        });
+    }
+
+    Ok(rows)
+}
+
+/// What already exists in the hackathon for the emails being imported
+#[derive(Default)]
+struct Existing {
+    /// Users by lowercased email, where several users can share one
+    users: HashMap<String, Vec<i32>>,
+    roles: HashMap<i32, String>,
+    invites: HashMap<String, hackathon_invites::Model>,
+    sponsors: Vec<sponsors::Model>,
+    /// The sponsor each existing representative in this hackathon belongs to
+    representatives: HashMap<i32, i32>,
+}
+
+/// Decide what to do with every row, without changing anything
+async fn plan_import(
+    db: &impl ConnectionTrait,
+    role: &HackathonRole,
+    rows: &[ImportRow],
+) -> Result<Vec<Plan>, AppError> {
+    let emails: Vec<&str> = rows.iter().map(|r| r.email.as_str()).collect();
+
+    // Older users kept the casing their provider sent
+    let mut users: HashMap<String, Vec<i32>> = HashMap::new();
+    for user in Users::find()
+        .filter(Expr::expr(Func::lower(Expr::col(users::Column::Email))).is_in(emails.clone()))
+        .all(db)
+        .await?
+    {
+        users
+            .entry(user.email.to_lowercase())
+            .or_default()
+            .push(user.id);
+    }
+
+    let roles: HashMap<i32, String> = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.is_in(users.values().flatten().copied()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|r| (r.user_id, r.role))
+        .collect();
+
+    let invites: HashMap<String, hackathon_invites::Model> = HackathonInvites::find()
+        .filter(hackathon_invites::Column::HackathonId.eq(role.hackathon_id))
+        .filter(hackathon_invites::Column::Email.is_in(emails))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|i| (i.email.clone(), i))
+        .collect();
+
+    let sponsors: Vec<sponsors::Model> = Sponsors::find()
+        .filter(sponsors::Column::HackathonId.eq(role.hackathon_id))
+        .all(db)
+        .await?;
+
+    let representatives: HashMap<i32, i32> = SponsorRepresentatives::find()
+        .filter(sponsor_representatives::Column::SponsorId.is_in(sponsors.iter().map(|s| s.id)))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|r| (r.user_id, r.sponsor_id))
+        .collect();
+
+    Ok(plan_rows(
+        rows,
+        &Existing {
+            users,
+            roles,
+            invites,
+            sponsors,
+            representatives,
+        },
+    ))
+}
+
+fn plan_rows(rows: &[ImportRow], existing: &Existing) -> Vec<Plan> {
+    let mut seen: HashMap<&str, u64> = HashMap::new();
+    let mut plans = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let mut v = Validator::default();
+        v.email("email", &row.email);
+        v.one_of("role", &row.role, &IMPORTABLE_ROLES);
+        v.max_length("name", row.name.as_deref(), MAX_NAME_LENGTH);
+        if let Err(AppError::Validation(errors)) = v.finish() {
+            let messages: Vec<String> = errors
+                .into_iter()
+                .map(|e| format!("{}: {}", e.field, e.message))
+                .collect();
+            plans.push(Plan::Error(messages.join("; ")));
+            continue;
+        }
+
+        if let Some(line) = seen.insert(&row.email, row.line) {
+            plans.push(Plan::Error(format!("Same email as line {line}")));
+            continue;
+        }
+
+        let sponsor_id = match (row.role.as_str(), &row.sponsor) {
+            ("sponsor", Some(name)) => {
+                match existing
+                    .sponsors
+                    .iter()
+                    .find(|s| s.name.eq_ignore_ascii_case(name))
+                {
+                    Some(sponsor) => Some(sponsor.id),
+                    None => {
+                        plans.push(Plan::Error(format!("No sponsor named {name}")));
+                        continue;
+                    }
+                }
+            }
+            ("sponsor", None) => {
+                plans.push(Plan::Error(
+                    "Sponsor representatives need a sponsor".to_string(),
+                ));
+                continue;
+            }
+            (_, Some(_)) => {
+                plans.push(Plan::Error(
+                    "Only sponsor representatives can have a sponsor".to_string(),
+                ));
+                continue;
+            }
+            (_, None) => None,
+        };
+
+        let plan = match existing.users.get(&row.email).map(Vec::as_slice) {
+            Some(&[user_id]) => match existing.roles.get(&user_id) {
+                None => Plan::Assign {
+                    user_id,
+                    sponsor_id,
+                },
+                Some(current)
+                    if *current == row.role
+                        && existing.representatives.get(&user_id).copied() == sponsor_id =>
+                {
+                    Plan::Unchanged
+                }
+                Some(current) if *current == row.role => {
+                    Plan::Error("Already represents another sponsor".to_string())
+                }
+                Some(current) => {
+                    Plan::Error(format!("Already has the {current} role in this hackathon"))
+                }
+            },
+            Some(_) => Plan::Error("Several users have this email".to_string()),
+            None => match existing.invites.get(&row.email) {
+                None => Plan::Invite { sponsor_id },
+                Some(invite) if invite.role == row.role && invite.sponsor_id == sponsor_id => {
+                    Plan::Unchanged
+                }
+                Some(invite) => {
+                    Plan::Error(format!("Already invited with the {} role", invite.role))
+                }
+            },
+        };
+        plans.push(plan);
+    }
+
+    plans
+}
+
+/// List invites that nobody has signed up for yet
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/invites",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Pending invites, newest first by default", body = Page<InviteInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 422, description = "Invalid page, sort or cursor")
+    ),
+    tag = "Invites"
+)]
+pub async fn list_invites(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<InviteInfo>>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    let select = HackathonInvites::find()
+        .filter(hackathon_invites::Column::HackathonId.eq(role.hackathon_id));
+    let page = pagination::paginate(&state.db, select, &page, &INVITE_SORT, |i| i).await?;
+
+    Ok(Json(page.map(|invites| {
+        invites.into_iter().map(InviteInfo::from).collect()
+    })))
+}
+
+/// Revoke a pending invite
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/invites/{invite_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("invite_id" = i32, Path, description = "Invite ID")
+    ),
+    responses(
+        (status = 204, description = "Invite revoked"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Invite not found")
+    ),
+    tag = "Invites"
+)]
+pub async fn revoke_invite(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, invite_id) = params;
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    let txn = state.db.begin().await?;
+    let result = HackathonInvites::delete_many()
+        .filter(hackathon_invites::Column::Id.eq(invite_id))
+        .filter(hackathon_invites::Column::HackathonId.eq(role.hackathon_id))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("Invite not found"));
+    }
+
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "invite.revoke", "invite", invite_id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The field and message of the validation error `result` failed with
+    fn invalid<T>(result: Result<T, AppError>) -> (String, String) {
+        match result {
+            Err(AppError::Validation(mut fields)) => {
+                let field = fields.remove(0);
+                (field.field, field.message)
+            }
+            Err(e) => panic!("unexpected error {e:?}"),
+            Ok(_) => panic!("expected a validation error"),
+        }
+    }
+
+    #[test]
+    fn headers_are_found_in_any_order_and_case() {
+        let rows = parse_rows(b"Role, Name ,EMAIL\nJudge,Ada,Ada@Example.com\n").unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].email, "ada@example.com");
+        assert_eq!(rows[0].role, "judge");
+        assert_eq!(rows[0].name.as_deref(), Some("Ada"));
+        assert_eq!(rows[0].sponsor, None);
+    }
+
+    #[test]
+    fn files_without_email_and_role_headers_are_rejected() {
+        for content in [
+            &b"email,name\na@example.com,A\n"[..],
+            b"a@example.com,judge\n",
+            b"",
+        ] {
+            assert_eq!(
+                invalid(parse_rows(content)),
+                (
+                    "file".to_string(),
+                    "Must have a header row with email and role columns".to_string()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn blank_rows_are_skipped() {
+        let rows = parse_rows(b"email,role\na@example.com,judge\n,\n  ,  \nb@example.com,mentor\n")
+            .unwrap();
+
+        let lines: Vec<u64> = rows.iter().map(|r| r.line).collect();
+        assert_eq!(lines, [2, 5]);
+    }
+
+    #[test]
+    fn too_many_rows_are_rejected() {
+        let mut content = "email,role\n".to_string();
+        for i in 0..MAX_IMPORT_ROWS {
+            content.push_str(&format!("user{i}@example.com,judge\n"));
+        }
+        assert_eq!(
+            parse_rows(content.as_bytes()).unwrap().len(),
+            MAX_IMPORT_ROWS
+        );
+
+        content.push_str("one-more@example.com,judge\n");
+        assert_eq!(
+            invalid(parse_rows(content.as_bytes())),
+            (
+                "file".to_string(),
+                format!("Must have at most {MAX_IMPORT_ROWS} rows")
+            )
+        );
+    }
+
+    #[test]
+    fn repeated_emails_fail_after_the_first() {
+        let rows = parse_rows(
+            b"email,role\na@example.com,judge\nb@example.com,judge\nA@example.com,mentor\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan_rows(&rows, &Existing::default()),
+            [
+                Plan::Invite { sponsor_id: None },
+                Plan::Invite { sponsor_id: None },
+                Plan::Error("Same email as line 2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn emails_several_users_share_are_rejected() {
+        let rows = parse_rows(b"email,role\na@example.com,judge\nb@example.com,judge\n").unwrap();
+        let existing = Existing {
+            users: HashMap::from([
+                ("a@example.com".to_string(), vec![1, 2]),
+                ("b@example.com".to_string(), vec![3]),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            plan_rows(&rows, &existing),
+            [
+                Plan::Error("Several users have this email".to_string()),
+                Plan::Assign {
+                    user_id: 3,
+                    sponsor_id: None
+                },
+            ]
+        );
+    }
+}
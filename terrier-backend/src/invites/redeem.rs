@@ -0,0 +1,71 @@
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    ModelTrait, QueryFilter, TransactionTrait,
+};
+
+use crate::entities::{
+    hackathon_invites, prelude::*, sponsor_representatives, user_hackathon_roles, users,
+};
+
+/// Give a user the roles they were invited to before they had an account
+///
+/// Only call this with an email the identity provider has verified, since whoever signs in
+/// with the email gets the roles. Invites for hackathons where the user already has a role
+/// are dropped.
+pub async fn redeem_invites(
+    db: &DatabaseConnection,
+    user: &users::Model,
+    verified_email: &str,
+) -> Result<(), DbErr> {
+    let invites = HackathonInvites::find()
+        .filter(hackathon_invites::Column::Email.eq(verified_email.to_lowercase()))
+        .all(db)
+        .await?;
+
+    if invites.is_empty() {
+        return Ok(());
+    }
+
+    let txn = db.begin().await?;
+
+    for invite in invites {
+        let has_role = UserHackathonRoles::find()
+            .filter(user_hackathon_roles::Column::HackathonId.eq(invite.hackathon_id))
+            .filter(user_hackathon_roles::Column::UserId.eq(user.id))
+            .one(&txn)
+            .await?
+            .is_some();
+
+        if !has_role {
+            user_hackathon_roles::ActiveModel {
+                user_id: Set(user.id),
+                hackathon_id: Set(invite.hackathon_id),
+                role: Set(invite.role.clone()),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            if let Some(sponsor_id) = invite.sponsor_id {
+                sponsor_representatives::ActiveModel {
+                    sponsor_id: Set(sponsor_id),
+                    user_id: Set(user.id),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+            }
+
+            tracing::info!(
+                "User {} redeemed an invite as {} in hackathon {}",
+                user.id,
+                invite.role,
+                invite.hackathon_id
+            );
+        }
+
+        invite.delete(&txn).await?;
+    }
+
+    txn.commit().await
+}
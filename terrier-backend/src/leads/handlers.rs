@@ -0,0 +1,509 @@
+use std::collections::HashMap;
+
+use axum::{Json, extract::State, http::StatusCode, response::Response};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Select,
+    sea_query::{self, OnConflict},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    AppState,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    badges::handlers::find_by_badge,
+    entities::{prelude::*, sponsor_leads, sponsors, user_profiles, users},
+    error::AppError,
+    exports::writer::{self, ExportFormat, Rows},
+    hackathons::{handlers::require_feature, phase::HackathonPhase},
+    pagination::{self, Page, PageParams, Sort},
+    sponsors::handlers::find_user_sponsor,
+    validation::{MAX_NAME_LENGTH, Path, Query, ValidJson, Validate, Validator},
+};
+
+const MAX_NOTES_LENGTH: usize = 2000;
+
+/// Leads read from the database at a time while exporting
+const EXPORT_BATCH_SIZE: u64 = 500;
+
+const LEAD_COLUMNS: [&str; 8] = [
+    "name",
+    "email",
+    "school",
+    "major",
+    "graduation_year",
+    "rating",
+    "notes",
+    "scanned_at",
+];
+
+#[derive(Serialize, ToSchema)]
+pub struct LeadInfo {
+    pub id: i32,
+    pub participant_id: i32,
+    pub name: Option<String>,
+    pub email: String,
+    pub school: Option<String>,
+    pub major: Option<String>,
+    pub graduation_year: Option<i32>,
+    pub notes: Option<String>,
+    /// From 1 to 5
+    pub rating: Option<i32>,
+    pub scanned_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl LeadInfo {
+    fn new(
+        lead: sponsor_leads::Model,
+        user: users::Model,
+        profile: Option<user_profiles::Model>,
+    ) -> Self {
+        LeadInfo {
+            id: lead.id,
+            participant_id: lead.participant_id,
+            name: user.name,
+            email: user.email,
+            school: profile.as_ref().and_then(|p| p.school.clone()),
+            major: profile.as_ref().and_then(|p| p.major.clone()),
+            graduation_year: profile.and_then(|p| p.graduation_year),
+            notes: lead.notes,
+            rating: lead.rating,
+            scanned_at: lead.created_at,
+            updated_at: lead.updated_at,
+        }
+    }
+}
+
+/// The sponsor the caller scans leads for, which requires the sponsors module
+async fn require_representative(
+    state: &AppState,
+    role: &HackathonRole,
+) -> Result<sponsors::Model, AppError> {
+    require_feature(state, role.hackathon_id, |f| f.sponsors).await?;
+
+    let (sponsor, _) = find_user_sponsor(state, role.hackathon_id, role.user_id)
+        .await?
+        .ok_or(AppError::Forbidden)?;
+
+    Ok(sponsor)
+}
+
+fn validate_lead(notes: &Option<String>, rating: Option<i32>, v: &mut Validator) {
+    v.max_length("notes", notes.as_deref(), MAX_NOTES_LENGTH);
+    if let Some(rating) = rating {
+        v.range("rating", rating, 1..=5);
+    }
+}
+
+/// Whether the participant agrees to share their details with sponsors who scan them
+async fn shares_details(state: &AppState, user_id: i32) -> Result<bool, AppError> {
+    Ok(UserProfiles::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .is_some_and(|p| p.lead_sharing_consent))
+}
+
+/// Leads of a sponsor, leaving out participants who have since withdrawn their consent
+fn consenting_leads(sponsor_id: i32) -> Select<SponsorLeads> {
+    let consenting = sea_query::Query::select()
+        .column(user_profiles::Column::UserId)
+        .from(UserProfiles)
+        .and_where(user_profiles::Column::LeadSharingConsent.eq(true))
+        .to_owned();
+
+    SponsorLeads::find()
+        .filter(sponsor_leads::Column::SponsorId.eq(sponsor_id))
+        .filter(sponsor_leads::Column::ParticipantId.in_subquery(consenting))
+}
+
+/// The participants' details of each lead, in the same order, leaving out participants who
+/// have withdrawn their consent since the leads were read
+async fn with_participants(
+    db: &DatabaseConnection,
+    leads: Vec<sponsor_leads::Model>,
+) -> Result<Vec<LeadInfo>, AppError> {
+    let participant_ids: Vec<i32> = leads.iter().map(|l| l.participant_id).collect();
+    let mut participants: HashMap<i32, (users::Model, user_profiles::Model)> = Users::find()
+        .find_also_related(UserProfiles)
+        .filter(users::Column::Id.is_in(participant_ids))
+        .filter(user_profiles::Column::LeadSharingConsent.eq(true))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(user, profile)| Some((user.id, (user, profile?))))
+        .collect();
+
+    Ok(leads
+        .into_iter()
+        .filter_map(|lead| {
+            // A participant is a lead of each sponsor at most once
+            let (user, profile) = participants.remove(&lead.participant_id)?;
+            Some(LeadInfo::new(lead, user, Some(profile)))
+        })
+        .collect())
+}
+
+async fn lead_info(state: &AppState, lead: sponsor_leads::Model) -> Result<LeadInfo, AppError> {
+    let (user, profile) = Users::find_by_id(lead.participant_id)
+        .find_also_related(UserProfiles)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Participant not found"))?;
+
+    Ok(LeadInfo::new(lead, user, profile))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ScanLeadRequest {
+    /// Token from the participant's badge QR code
+    pub token: String,
+    pub notes: Option<String>,
+    /// From 1 to 5
+    pub rating: Option<i32>,
+}
+
+impl Validate for ScanLeadRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("token", &self.token, MAX_NAME_LENGTH);
+        validate_lead(&self.notes, self.rating, v);
+    }
+}
+
+/// Record a lead by scanning a participant's badge
+///
+/// Scanning a participant who is already a lead updates the notes and rating.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/leads",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = ScanLeadRequest,
+    responses(
+        (status = 200, description = "Lead recorded", body = LeadInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor representative, or participant has not agreed to share their details"),
+        (status = 404, description = "No participant with this badge, or sponsors are disabled"),
+        (status = 409, description = "Hackathon is archived"),
+        (status = 422, description = "Invalid notes or rating")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn scan_lead(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<ScanLeadRequest>,
+) -> Result<Json<LeadInfo>, AppError> {
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+    let sponsor = require_representative(&state, &role).await?;
+
+    let participant = find_by_badge(&state, role.hackathon_id, &req.token).await?;
+
+    if !shares_details(&state, participant.user_id).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    // Scanning again only replaces what was given, and concurrent scans of the same
+    // participant end up as one lead
+    let mut updated = vec![sponsor_leads::Column::UpdatedAt];
+    if req.notes.is_some() {
+        updated.push(sponsor_leads::Column::Notes);
+    }
+    if req.rating.is_some() {
+        updated.push(sponsor_leads::Column::Rating);
+    }
+
+    let lead = SponsorLeads::insert(sponsor_leads::ActiveModel {
+        sponsor_id: Set(sponsor.id),
+        participant_id: Set(participant.user_id),
+        scanned_by: Set(Some(role.user_id)),
+        notes: Set(req.notes),
+        rating: Set(req.rating),
+        updated_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([
+            sponsor_leads::Column::SponsorId,
+            sponsor_leads::Column::ParticipantId,
+        ])
+        .update_columns(updated)
+        .to_owned(),
+    )
+    .exec_with_returning(&state.db)
+    .await?;
+
+    Ok(Json(lead_info(&state, lead).await?))
+}
+
+const LEAD_SORT: Sort<sponsor_leads::Column> = Sort {
+    fields: &[
+        ("created_at", sponsor_leads::Column::CreatedAt),
+        ("updated_at", sponsor_leads::Column::UpdatedAt),
+    ],
+    default: "-created_at",
+    id: sponsor_leads::Column::Id,
+};
+
+/// List the leads of the current user's sponsor
+///
+/// Participants who have since withdrawn their consent are left out. Can be sorted by
+/// `created_at` or `updated_at`.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/leads",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Leads, newest first by default", body = Page<LeadInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor representative"),
+        (status = 404, description = "Hackathon not found, or sponsors are disabled"),
+        (status = 422, description = "Invalid page, sort or cursor")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn list_leads(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<LeadInfo>>, AppError> {
+    let sponsor = require_representative(&state, &role).await?;
+
+    let mut page = pagination::paginate(
+        &state.db,
+        consenting_leads(sponsor.id),
+        &page,
+        &LEAD_SORT,
+        |l| l,
+    )
+    .await?;
+    let leads = std::mem::take(&mut page.items);
+
+    Ok(Json(
+        page.with_items(with_participants(&state.db, leads).await?),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateLeadRequest {
+    pub notes: Option<String>,
+    /// From 1 to 5
+    pub rating: Option<i32>,
+}
+
+impl Validate for UpdateLeadRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_lead(&self.notes, self.rating, v);
+    }
+}
+
+async fn find_lead(
+    state: &AppState,
+    sponsor: &sponsors::Model,
+    lead_id: i32,
+) -> Result<sponsor_leads::Model, AppError> {
+    SponsorLeads::find_by_id(lead_id)
+        .filter(sponsor_leads::Column::SponsorId.eq(sponsor.id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Lead not found"))
+}
+
+/// Update the notes or rating of a lead
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}/leads/{lead_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("lead_id" = i32, Path, description = "Lead ID")
+    ),
+    request_body = UpdateLeadRequest,
+    responses(
+        (status = 200, description = "Lead updated", body = LeadInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor representative"),
+        (status = 404, description = "Lead not found, or sponsors are disabled"),
+        (status = 422, description = "Invalid notes or rating")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn update_lead(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<UpdateLeadRequest>,
+) -> Result<Json<LeadInfo>, AppError> {
+    let (_, lead_id) = params;
+    let sponsor = require_representative(&state, &role).await?;
+
+    let lead = find_lead(&state, &sponsor, lead_id).await?;
+    // Leads of participants who withdrew their consent are hidden, as in the list
+    if !shares_details(&state, lead.participant_id).await? {
+        return Err(AppError::NotFound("Lead not found"));
+    }
+
+    let mut active: sponsor_leads::ActiveModel = lead.into();
+    if let Some(notes) = req.notes {
+        active.notes = Set(Some(notes).filter(|n| !n.trim().is_empty()));
+    }
+    if req.rating.is_some() {
+        active.rating = Set(req.rating);
+    }
+    active.updated_at = Set(Utc::now().naive_utc());
+
+    let lead = active.update(&state.db).await?;
+
+    Ok(Json(lead_info(&state, lead).await?))
+}
+
+/// Delete a lead
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/leads/{lead_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("lead_id" = i32, Path, description = "Lead ID")
+    ),
+    responses(
+        (status = 204, description = "Lead deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor representative"),
+        (status = 404, description = "Lead not found, or sponsors are disabled")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn delete_lead(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, lead_id) = params;
+    let sponsor = require_representative(&state, &role).await?;
+
+    let lead = find_lead(&state, &sponsor, lead_id).await?;
+    SponsorLeads::delete_by_id(lead.id).exec(&state.db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportLeadsQuery {
+    /// `csv` (default) or `xlsx`
+    #[serde(default)]
+    #[param(inline)]
+    format: ExportFormat,
+    /// Sponsor to export, which organizers must give and representatives can leave out
+    sponsor_id: Option<i32>,
+}
+
+/// Export a sponsor's leads
+///
+/// The file is streamed while it is being generated, so a failure part way through ends the
+/// download early instead of returning an error status.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/leads/export",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ExportLeadsQuery
+    ),
+    responses(
+        (status = 200, description = "Leads as CSV or XLSX", content(
+            ("text/csv"),
+            ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 400, description = "Organizers must choose a sponsor"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a representative of this sponsor"),
+        (status = 404, description = "Sponsor not found, or sponsors are disabled")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn export_leads(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ExportLeadsQuery>,
+) -> Result<Response, AppError> {
+    let sponsor = if role.is_organizer() {
+        require_feature(&state, role.hackathon_id, |f| f.sponsors).await?;
+
+        let sponsor_id = query
+            .sponsor_id
+            .ok_or(AppError::BadRequest("Organizers must choose a sponsor"))?;
+        Sponsors::find_by_id(sponsor_id)
+            .filter(sponsors::Column::HackathonId.eq(role.hackathon_id))
+            .one(&state.db)
+            .await?
+            .ok_or(AppError::NotFound("Sponsor not found"))?
+    } else {
+        let sponsor = require_representative(&state, &role).await?;
+        if query.sponsor_id.is_some_and(|id| id != sponsor.id) {
+            return Err(AppError::Forbidden);
+        }
+        sponsor
+    };
+
+    audit::record(
+        &state.db,
+        Entry::in_hackathon(&role, "leads.export", "sponsor", sponsor.id),
+    )
+    .await?;
+
+    let sponsor_name: String = sponsor
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let file_name = format!("{}-{}-leads", role.slug, sponsor_name);
+    let db = state.db.clone();
+
+    Ok(writer::download(
+        &file_name,
+        query.format,
+        &LEAD_COLUMNS,
+        move |rows| write_leads(db, sponsor.id, rows),
+    ))
+}
+
+async fn write_leads(db: DatabaseConnection, sponsor_id: i32, rows: Rows) -> Result<(), AppError> {
+    let mut last_id = 0;
+
+    loop {
+        let batch = consenting_leads(sponsor_id)
+            .filter(sponsor_leads::Column::Id.gt(last_id))
+            .order_by_asc(sponsor_leads::Column::Id)
+            .limit(EXPORT_BATCH_SIZE)
+            .all(&db)
+            .await?;
+
+        let Some(last) = batch.last() else {
+            return Ok(());
+        };
+        last_id = last.id;
+
+        for lead in with_participants(&db, batch).await? {
+            rows.send(vec![
+                lead.name.unwrap_or_default(),
+                lead.email,
+                lead.school.unwrap_or_default(),
+                lead.major.unwrap_or_default(),
+                lead.graduation_year
+                    .map(|y| y.to_string())
+                    .unwrap_or_default(),
+                lead.rating.map(|r| r.to_string()).unwrap_or_default(),
+                lead.notes.unwrap_or_default(),
+                lead.scanned_at.to_string(),
+            ])
+            .await?;
+        }
+    }
+}
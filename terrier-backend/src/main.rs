@@ -1,18 +1,10 @@
 use axum::{
     BoxError, Router,
-    error_handling::HandleErrorLayer,
-    http::Uri,
-    middleware,
-    response::IntoResponse,
-    routing::{get, post},
-};
-use axum_oidc::{
-    EmptyAdditionalClaims, OidcAuthLayer, OidcClient, OidcLoginLayer, error::MiddlewareError,
-    handle_oidc_redirect,
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
 };
 use sea_orm::DatabaseConnection;
 use tokio::signal;
-use tower::ServiceBuilder;
 use tower_sessions::{
     Expiry, MemoryStore, SessionManagerLayer,
     cookie::{SameSite, time::Duration},
@@ -21,19 +13,48 @@ use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitEx
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+mod admins;
+mod analytics;
+mod audit;
 mod auth;
+mod badges;
 mod config;
+mod distributions;
 mod docs;
 mod entities;
+mod error;
+mod exports;
 mod hackathons;
+mod hardware;
+mod invites;
+mod leads;
+mod mentorship;
+mod organizations;
+mod pagination;
+mod profiles;
+mod reimbursements;
+mod resumes;
+mod sponsors;
+mod storage;
+mod tokens;
+mod validation;
 
-use config::Config;
+use analytics::cache::AnalyticsCache;
+use auth::{oidc::OidcProviders, user_cache::UserCache};
+use config::{AuthMode, Config};
 use docs::ApiDoc;
+use mentorship::events::TicketEvents;
+use storage::Storage;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DatabaseConnection,
     pub config: Config,
+    pub oidc: OidcProviders,
+    pub user_cache: UserCache,
+    pub storage: Option<Storage>,
+    pub ticket_events: TicketEvents,
+    pub analytics_cache: AnalyticsCache,
 }
 
 pub async fn create_app(app_state: AppState) -> Result<Router, BoxError> {
@@ -44,36 +65,25 @@ pub async fn create_app(app_state: AppState) -> Result<Router, BoxError> {
         .with_same_site(SameSite::Lax)
         .with_expiry(Expiry::OnInactivity(Duration::hours(24)));
 
-    let oidc_login_service = ServiceBuilder::new()
-        .layer(HandleErrorLayer::new(|e: MiddlewareError| async {
-            tracing::error!("OIDC Login error: {:?}", e);
-            e.into_response()
-        }))
-        .layer(OidcLoginLayer::<EmptyAdditionalClaims>::new());
-
-    let config = app_state.config.clone();
-    let redirect_url = format!("{}/auth/callback", config.api_url);
-
-    let oidc_client = OidcClient::<EmptyAdditionalClaims>::builder()
-        .with_default_http_client()
-        .with_redirect_url(Uri::try_from(redirect_url).expect("valid API_URL"))
-        .with_client_id(config.oidc_client_id)
-        .with_client_secret(config.oidc_client_secret)
-        .with_scopes(["openid", "email", "profile"].into_iter())
-        .discover(config.oidc_issuer)
-        .await
-        .unwrap()
-        .build();
-
-    let oidc_auth_service = ServiceBuilder::new()
-        .layer(HandleErrorLayer::new(|e: MiddlewareError| async {
-            tracing::error!("OIDC Auth error: {:?}", e);
-            e.into_response()
-        }))
-        .layer(OidcAuthLayer::new(oidc_client));
+    let auth_routes = match app_state.config.auth_mode {
+        AuthMode::Oidc => Router::new()
+            .route("/api/auth/login", get(auth::handlers::login))
+            .route("/api/auth/logout", get(auth::handlers::logout))
+            .route("/api/auth/callback", get(auth::handlers::callback)),
+        AuthMode::Dev => {
+            tracing::warn!(
+                "AUTH_MODE=dev: anyone can log in as any user, never use this in production"
+            );
+
+            Router::new()
+                .route("/api/auth/login", get(auth::dev::login))
+                .route("/api/auth/logout", get(auth::dev::logout))
+        }
+    };
 
     let router = Router::new()
-        // Protected routes
+        .merge(auth_routes)
+        // Protected routes, authenticated by session or API token in their extractors
         .route(
             "/api/hackathons/{slug}/role",
             get(hackathons::handlers::get_user_role),
@@ -82,24 +92,348 @@ pub async fn create_app(app_state: AppState) -> Result<Router, BoxError> {
             "/api/hackathons",
             post(hackathons::handlers::create_hackathon),
         )
-        .route("/api/auth/login", get(auth::handlers::login))
-        .route("/api/auth/logout", get(auth::handlers::logout))
-        // OIDC authentication layer
-        .layer(middleware::from_fn_with_state(
-            app_state.clone(),
-            auth::middleware::sync_user_middleware,
-        ))
-        .layer(oidc_login_service)
+        .route(
+            "/api/hackathons/{slug}",
+            get(hackathons::handlers::get_hackathon)
+                .patch(hackathons::handlers::update_hackathon)
+                .delete(hackathons::handlers::delete_hackathon),
+        )
+        .route(
+            "/api/hackathons/{slug}/publish",
+            post(hackathons::handlers::set_published),
+        )
+        .route(
+            "/api/hackathons/{slug}/archive",
+            post(hackathons::handlers::set_archived),
+        )
+        .route(
+            "/api/hackathons/{slug}/duplicate",
+            post(hackathons::handlers::duplicate_hackathon),
+        )
+        .route(
+            "/api/hackathons/{slug}/phase",
+            post(hackathons::handlers::set_phase),
+        )
+        .route(
+            "/api/hackathons/{slug}/settings",
+            get(hackathons::handlers::get_settings).patch(hackathons::handlers::update_settings),
+        )
+        .route(
+            "/api/hackathons/{slug}/settings/history",
+            get(hackathons::handlers::get_settings_history),
+        )
+        .route(
+            "/api/hackathons/{slug}/profiles/summary",
+            get(profiles::handlers::get_profile_summary),
+        )
+        .route(
+            "/api/hackathons/{slug}/profiles/{user_id}",
+            get(profiles::handlers::get_hackathon_profile),
+        )
+        .route(
+            "/api/profile",
+            get(profiles::handlers::get_profile).patch(profiles::handlers::update_profile),
+        )
+        .route(
+            "/api/profile/resume",
+            get(resumes::handlers::get_resume)
+                .put(resumes::handlers::upload_resume)
+                .delete(resumes::handlers::delete_resume)
+                .layer(DefaultBodyLimit::max(resumes::handlers::UPLOAD_BODY_LIMIT)),
+        )
+        .route(
+            "/api/hackathons/{slug}/resumes",
+            get(resumes::handlers::list_resumes),
+        )
+        .route(
+            "/api/hackathons/{slug}/resumes/download",
+            get(resumes::handlers::download_resume_book),
+        )
+        .route(
+            "/api/hackathons/{slug}/resumes/access-log",
+            get(resumes::handlers::get_resume_access_log),
+        )
+        .route(
+            "/api/hackathons/{slug}/resumes/{user_id}",
+            get(resumes::handlers::download_resume),
+        )
+        .route(
+            "/api/hackathons/{slug}/sponsor-tiers",
+            get(sponsors::handlers::list_sponsor_tiers)
+                .post(sponsors::handlers::create_sponsor_tier),
+        )
+        .route(
+            "/api/hackathons/{slug}/sponsor-tiers/{tier_id}",
+            patch(sponsors::handlers::update_sponsor_tier)
+                .delete(sponsors::handlers::delete_sponsor_tier),
+        )
+        .route(
+            "/api/hackathons/{slug}/sponsors",
+            get(sponsors::handlers::list_sponsors).post(sponsors::handlers::create_sponsor),
+        )
+        .route(
+            "/api/hackathons/{slug}/sponsors/me",
+            get(sponsors::handlers::get_my_sponsor),
+        )
+        .route(
+            "/api/hackathons/{slug}/sponsors/{sponsor_id}",
+            get(sponsors::handlers::get_sponsor)
+                .patch(sponsors::handlers::update_sponsor)
+                .delete(sponsors::handlers::delete_sponsor),
+        )
+        .route(
+            "/api/hackathons/{slug}/sponsors/{sponsor_id}/representatives",
+            post(sponsors::handlers::add_sponsor_representative),
+        )
+        .route(
+            "/api/hackathons/{slug}/sponsors/{sponsor_id}/representatives/{user_id}",
+            delete(sponsors::handlers::remove_sponsor_representative),
+        )
+        .route(
+            "/api/hackathons/{slug}/roles/import",
+            post(invites::handlers::import_roles).layer(DefaultBodyLimit::max(
+                invites::handlers::MAX_IMPORT_SIZE + 64 * 1024,
+            )),
+        )
+        .route(
+            "/api/hackathons/{slug}/invites",
+            get(invites::handlers::list_invites),
+        )
+        .route(
+            "/api/hackathons/{slug}/invites/{invite_id}",
+            delete(invites::handlers::revoke_invite),
+        )
+        .route(
+            "/api/hackathons/{slug}/exports/participants",
+            get(exports::handlers::export_participants),
+        )
+        .route(
+            "/api/hackathons/{slug}/analytics",
+            get(analytics::handlers::get_analytics),
+        )
+        .route(
+            "/api/hackathons/{slug}/badge",
+            get(badges::handlers::get_badge),
+        )
+        .route(
+            "/api/hackathons/{slug}/leads",
+            get(leads::handlers::list_leads).post(leads::handlers::scan_lead),
+        )
+        .route(
+            "/api/hackathons/{slug}/leads/export",
+            get(leads::handlers::export_leads),
+        )
+        .route(
+            "/api/hackathons/{slug}/leads/{lead_id}",
+            patch(leads::handlers::update_lead).delete(leads::handlers::delete_lead),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets",
+            get(mentorship::handlers::list_tickets).post(mentorship::handlers::create_ticket),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets/stats",
+            get(mentorship::handlers::get_ticket_stats),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets/events",
+            get(mentorship::handlers::stream_ticket_events),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets/{ticket_id}",
+            get(mentorship::handlers::get_ticket),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets/{ticket_id}/claim",
+            post(mentorship::handlers::claim_ticket),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets/{ticket_id}/release",
+            post(mentorship::handlers::release_ticket),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets/{ticket_id}/resolve",
+            post(mentorship::handlers::resolve_ticket),
+        )
+        .route(
+            "/api/hackathons/{slug}/tickets/{ticket_id}/cancel",
+            post(mentorship::handlers::cancel_ticket),
+        )
+        .route(
+            "/api/hackathons/{slug}/mentors",
+            get(mentorship::handlers::list_mentors).post(mentorship::handlers::add_mentor),
+        )
+        .route(
+            "/api/hackathons/{slug}/mentors/{user_id}",
+            delete(mentorship::handlers::remove_mentor),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware",
+            get(hardware::handlers::list_items).post(hardware::handlers::create_item),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/loans",
+            get(hardware::handlers::list_loans),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/loans/{loan_id}/approve",
+            post(hardware::handlers::approve_loan),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/loans/{loan_id}/reject",
+            post(hardware::handlers::reject_loan),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/loans/{loan_id}/checkout",
+            post(hardware::handlers::check_out_loan),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/loans/{loan_id}/return",
+            post(hardware::handlers::return_loan),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/loans/{loan_id}/cancel",
+            post(hardware::handlers::cancel_loan),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/{item_id}",
+            patch(hardware::handlers::update_item).delete(hardware::handlers::delete_item),
+        )
+        .route(
+            "/api/hackathons/{slug}/hardware/{item_id}/requests",
+            post(hardware::handlers::request_item),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements",
+            get(reimbursements::handlers::list_reimbursements)
+                .post(reimbursements::handlers::create_reimbursement),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/me",
+            get(reimbursements::handlers::get_my_reimbursement),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/summary",
+            get(reimbursements::handlers::get_reimbursement_summary),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/export",
+            get(reimbursements::handlers::export_payouts),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/finance",
+            get(reimbursements::handlers::list_finance_members),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/finance/{user_id}",
+            put(reimbursements::handlers::grant_finance)
+                .delete(reimbursements::handlers::revoke_finance),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/{request_id}",
+            get(reimbursements::handlers::get_reimbursement)
+                .patch(reimbursements::handlers::update_reimbursement),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/{request_id}/withdraw",
+            post(reimbursements::handlers::withdraw_reimbursement),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/{request_id}/review",
+            post(reimbursements::handlers::review_reimbursement),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/{request_id}/receipts",
+            post(reimbursements::handlers::upload_receipt).layer(DefaultBodyLimit::max(
+                reimbursements::handlers::UPLOAD_BODY_LIMIT,
+            )),
+        )
+        .route(
+            "/api/hackathons/{slug}/reimbursements/{request_id}/receipts/{receipt_id}",
+            get(reimbursements::handlers::download_receipt)
+                .delete(reimbursements::handlers::delete_receipt),
+        )
+        .route(
+            "/api/hackathons/{slug}/distributions",
+            get(distributions::handlers::list_items).post(distributions::handlers::create_item),
+        )
+        .route(
+            "/api/hackathons/{slug}/distributions/{item_id}",
+            patch(distributions::handlers::update_item)
+                .delete(distributions::handlers::delete_item),
+        )
+        .route(
+            "/api/hackathons/{slug}/distributions/{item_id}/claims",
+            get(distributions::handlers::list_claims).post(distributions::handlers::claim_item),
+        )
+        .route(
+            "/api/hackathons/{slug}/distributions/{item_id}/claims/{claim_id}",
+            delete(distributions::handlers::delete_claim),
+        )
+        .route(
+            "/api/hackathons/{slug}/distributions/{item_id}/summary",
+            get(distributions::handlers::get_item_summary),
+        )
+        .route(
+            "/api/hackathons/{slug}/volunteers",
+            get(distributions::handlers::list_volunteers)
+                .post(distributions::handlers::add_volunteer),
+        )
+        .route(
+            "/api/hackathons/{slug}/volunteers/{user_id}",
+            delete(distributions::handlers::remove_volunteer),
+        )
+        .route(
+            "/api/auth/tokens",
+            get(tokens::handlers::list_tokens).post(tokens::handlers::create_token),
+        )
+        .route(
+            "/api/auth/tokens/{id}",
+            delete(tokens::handlers::revoke_token),
+        )
+        .route(
+            "/api/organizations",
+            get(organizations::handlers::list_organizations)
+                .post(organizations::handlers::create_organization),
+        )
+        .route(
+            "/api/organizations/{slug}",
+            get(organizations::handlers::get_organization)
+                .patch(organizations::handlers::update_organization)
+                .delete(organizations::handlers::delete_organization),
+        )
+        .route(
+            "/api/organizations/{slug}/hackathons",
+            get(organizations::handlers::list_organization_hackathons),
+        )
+        .route(
+            "/api/organizations/{slug}/admins",
+            get(organizations::handlers::list_organization_admins)
+                .post(organizations::handlers::add_organization_admin),
+        )
+        .route(
+            "/api/organizations/{slug}/admins/{user_id}",
+            delete(organizations::handlers::remove_organization_admin),
+        )
+        .route(
+            "/api/admins",
+            get(admins::handlers::list_admins).post(admins::handlers::grant_admin),
+        )
+        .route(
+            "/api/admins/{user_id}",
+            delete(admins::handlers::revoke_admin),
+        )
+        .route(
+            "/api/admins/audit-log",
+            get(audit::handlers::list_audit_log),
+        )
         // Public routes
         .route(
             "/api/hackathons/public",
             get(hackathons::handlers::list_public_hackathons),
         )
         .route("/api/auth/status", get(auth::handlers::status))
-        .route(
-            "/api/auth/callback",
-            get(handle_oidc_redirect::<EmptyAdditionalClaims>),
-        )
+        .route("/api/auth/providers", get(auth::handlers::providers))
         .route(
             "/api/",
             get(|| async { "Visit /api/swagger for API documentation" }),
@@ -107,7 +441,6 @@ pub async fn create_app(app_state: AppState) -> Result<Router, BoxError> {
         .route("/api/health", get(|| async { "OK" }))
         .merge(SwaggerUi::new("/api/swagger").url("/api/openapi.json", ApiDoc::openapi()))
         // Middleware layers
-        .layer(oidc_auth_service)
         .layer(session_layer)
         .with_state(app_state);
 
@@ -142,21 +475,45 @@ async fn shutdown_signal() {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing_subscriber::registry()
         .with(EnvFilter::new(std::env::var("RUST_LOG").unwrap_or_else(
-            |_| "axum_oidc=debug,tower_sessions=debug,sqlx=warn,tower_http=debug".into(),
+            |_| "tower_sessions=debug,sqlx=warn,tower_http=debug".into(),
         )))
         .with(tracing_subscriber::fmt::layer())
         .try_init()?;
 
     let config = Config::from_env()?;
+    let db = sea_orm::Database::connect(&config.database_url).await?;
+
+    let promoted = auth::identity::seed_global_admins(&db, &config.admin_emails).await?;
+    if promoted > 0 {
+        tracing::info!("Granted global admin to {promoted} users from ADMIN_EMAILS");
+    }
+
+    hackathons::phase::spawn_scheduler(db.clone());
+
+    let storage = match &config.storage {
+        Some(storage) => {
+            Some(Storage::connect(storage).map_err(|e| format!("Failed to set up storage: {e}"))?)
+        }
+        None => {
+            tracing::warn!("S3_ENDPOINT is not set, so file uploads are disabled");
+            None
+        }
+    };
+
     let app_state = AppState {
-        db: sea_orm::Database::connect(&config.database_url).await?,
+        db,
+        oidc: OidcProviders::discover(&config).await?,
+        user_cache: UserCache::default(),
+        storage,
+        ticket_events: TicketEvents::default(),
+        analytics_cache: AnalyticsCache::default(),
         config: config.clone(),
     };
 
     let app = create_app(app_state).await.unwrap();
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
 
-    println!("Backend listening at {}", &config.api_url);
+    println!("Backend listening at {}", config.api_url);
 
     axum::serve(listener, app)
         .with_graceful_shutdown(shutdown_signal())
@@ -0,0 +1,53 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+
+use super::handlers::TicketInfo;
+
+/// Events buffered for each subscriber before slow mentors start missing updates
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Clone, Copy, Debug, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketEventKind {
+    Opened,
+    Claimed,
+    Released,
+    Resolved,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct TicketEvent {
+    #[serde(skip)]
+    pub hackathon_id: i32,
+    pub kind: TicketEventKind,
+    pub ticket: TicketInfo,
+}
+
+/// Broadcasts help ticket changes to the mentors streaming them.
+///
+/// Events are not persisted, so subscribers should load the queue when they connect.
+#[derive(Clone)]
+pub struct TicketEvents(broadcast::Sender<TicketEvent>);
+
+impl Default for TicketEvents {
+    fn default() -> Self {
+        TicketEvents(broadcast::channel(CHANNEL_CAPACITY).0)
+    }
+}
+
+impl TicketEvents {
+    pub fn publish(&self, hackathon_id: i32, kind: TicketEventKind, ticket: TicketInfo) {
+        // Sending only fails when nobody is listening, which is fine
+        let _ = self.0.send(TicketEvent {
+            hackathon_id,
+            kind,
+            ticket,
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TicketEvent> {
+        self.0.subscribe()
+    }
+}
@@ -0,0 +1,889 @@
+use std::{collections::HashMap, convert::Infallible};
+
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait, sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
+use utoipa::{IntoParams, ToSchema};
+
+use super::events::TicketEventKind;
+use crate::{
+    AppState,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    entities::{help_tickets, prelude::*, user_hackathon_roles, users},
+    error::AppError,
+    hackathons::{handlers::require_feature, phase::HackathonPhase, roles},
+    pagination::{self, Page, PageParams, Sort},
+    validation::{Path, Query, ValidJson, Validate, Validator},
+};
+
+/// Every status a help ticket can have
+pub const TICKET_STATUSES: [&str; 4] = ["open", "claimed", "resolved", "cancelled"];
+
+const MAX_TOPIC_LENGTH: usize = 200;
+const MAX_LOCATION_LENGTH: usize = 200;
+const MAX_DESCRIPTION_LENGTH: usize = 2000;
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct TicketUser {
+    pub id: i32,
+    pub name: Option<String>,
+    pub email: String,
+}
+
+impl From<users::Model> for TicketUser {
+    fn from(u: users::Model) -> Self {
+        TicketUser {
+            id: u.id,
+            name: u.name,
+            email: u.email,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct TicketInfo {
+    pub id: i32,
+    pub topic: String,
+    pub description: Option<String>,
+    /// Where the mentor can find the team, such as a table number
+    pub location: String,
+    /// One of `open`, `claimed`, `resolved` or `cancelled`
+    pub status: String,
+    pub opened_by: Option<TicketUser>,
+    pub mentor: Option<TicketUser>,
+    pub created_at: NaiveDateTime,
+    pub claimed_at: Option<NaiveDateTime>,
+    /// When the ticket was resolved or cancelled
+    pub closed_at: Option<NaiveDateTime>,
+}
+
+/// Tickets with the users who opened and claimed them
+async fn ticket_infos(
+    state: &AppState,
+    tickets: Vec<help_tickets::Model>,
+) -> Result<Vec<TicketInfo>, AppError> {
+    let user_ids: Vec<i32> = tickets
+        .iter()
+        .flat_map(|t| [Some(t.user_id), t.mentor_id])
+        .flatten()
+        .collect();
+
+    let users: HashMap<i32, users::Model> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let user = |id: Option<i32>| {
+        id.and_then(|id| users.get(&id))
+            .cloned()
+            .map(TicketUser::from)
+    };
+
+    Ok(tickets
+        .into_iter()
+        .map(|t| TicketInfo {
+            id: t.id,
+            opened_by: user(Some(t.user_id)),
+            mentor: user(t.mentor_id),
+            topic: t.topic,
+            description: t.description,
+            location: t.location,
+            status: t.status,
+            created_at: t.created_at,
+            claimed_at: t.claimed_at,
+            closed_at: t.closed_at,
+        })
+        .collect())
+}
+
+async fn ticket_info(
+    state: &AppState,
+    ticket: help_tickets::Model,
+) -> Result<TicketInfo, AppError> {
+    ticket_infos(state, vec![ticket])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::internal("Ticket missing from its own info"))
+}
+
+/// Check the hackathon uses the mentorship module
+async fn require_mentors(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    require_feature(state, role.hackathon_id, |f| f.mentors).await?;
+    Ok(())
+}
+
+async fn find_ticket(
+    state: &AppState,
+    role: &HackathonRole,
+    ticket_id: i32,
+) -> Result<help_tickets::Model, AppError> {
+    let ticket = HelpTickets::find_by_id(ticket_id)
+        .filter(help_tickets::Column::HackathonId.eq(role.hackathon_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Ticket not found"))?;
+
+    // Participants can only see their own tickets
+    if !role.is_mentor() && ticket.user_id != role.user_id {
+        return Err(AppError::NotFound("Ticket not found"));
+    }
+
+    Ok(ticket)
+}
+
+/// Move a ticket from one status to another, failing if someone else changed it first
+async fn transition(
+    state: &AppState,
+    ticket: help_tickets::Model,
+    from: &[&str],
+    mut active: help_tickets::ActiveModel,
+) -> Result<help_tickets::Model, AppError> {
+    if !from.contains(&ticket.status.as_str()) {
+        return Err(AppError::Conflict(
+            "Not possible while the ticket has this status",
+        ));
+    }
+
+    active.updated_at = Set(Utc::now().naive_utc());
+
+    // Only update the row if its status is still the one we checked, so two mentors cannot
+    // both claim the same ticket
+    let result = HelpTickets::update_many()
+        .set(active)
+        .filter(help_tickets::Column::Id.eq(ticket.id))
+        .filter(help_tickets::Column::Status.eq(&ticket.status))
+        .exec(&state.db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::Conflict(
+            "The ticket was changed by someone else, please reload it",
+        ));
+    }
+
+    HelpTickets::find_by_id(ticket.id)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Ticket not found"))
+}
+
+async fn publish(
+    state: &AppState,
+    kind: TicketEventKind,
+    ticket: help_tickets::Model,
+) -> Result<TicketInfo, AppError> {
+    let hackathon_id = ticket.hackathon_id;
+    let info = ticket_info(state, ticket).await?;
+    state
+        .ticket_events
+        .publish(hackathon_id, kind, info.clone());
+    Ok(info)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateTicketRequest {
+    /// What the team needs help with
+    pub topic: String,
+    pub description: Option<String>,
+    /// Where the mentor can find the team, such as a table number
+    pub location: String,
+}
+
+impl Validate for CreateTicketRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("topic", self.topic.trim(), MAX_TOPIC_LENGTH);
+        v.required("location", self.location.trim(), MAX_LOCATION_LENGTH);
+        v.max_length(
+            "description",
+            self.description.as_deref().map(str::trim),
+            MAX_DESCRIPTION_LENGTH,
+        );
+    }
+}
+
+/// Open a help ticket on behalf of the current participant's team
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/tickets",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = CreateTicketRequest,
+    responses(
+        (status = 200, description = "Ticket opened", body = TicketInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a participant in this hackathon"),
+        (status = 404, description = "Hackathon not found, or mentorship is disabled"),
+        (status = 409, description = "User already has a ticket waiting, or the event is not live"),
+        (status = 422, description = "Missing or too long topic, description or location")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn create_ticket(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<CreateTicketRequest>,
+) -> Result<Json<TicketInfo>, AppError> {
+    if !role.is_participant() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+    role.require_phase(&[HackathonPhase::EventLive])?;
+
+    let topic = req.topic.trim();
+    let location = req.location.trim();
+    let description = req
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+
+    // One ticket at a time keeps the queue fair
+    let waiting = HelpTickets::find()
+        .filter(help_tickets::Column::HackathonId.eq(role.hackathon_id))
+        .filter(help_tickets::Column::UserId.eq(role.user_id))
+        .filter(help_tickets::Column::Status.is_in(["open", "claimed"]))
+        .one(&state.db)
+        .await?;
+
+    if waiting.is_some() {
+        return Err(AppError::Conflict("You already have an open ticket"));
+    }
+
+    let ticket = help_tickets::ActiveModel {
+        hackathon_id: Set(role.hackathon_id),
+        user_id: Set(role.user_id),
+        topic: Set(topic.to_string()),
+        description: Set(description),
+        location: Set(location.to_string()),
+        status: Set("open".to_string()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(Json(
+        publish(&state, TicketEventKind::Opened, ticket).await?,
+    ))
+}
+
+const LIST_SORT: Sort<help_tickets::Column> = Sort {
+    fields: &[
+        ("created_at", help_tickets::Column::CreatedAt),
+        ("updated_at", help_tickets::Column::UpdatedAt),
+    ],
+    default: "created_at",
+    id: help_tickets::Column::Id,
+};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListTicketsQuery {
+    /// Only return tickets with this status
+    status: Option<String>,
+}
+
+/// List help tickets
+///
+/// Mentors and organizers see every ticket in the hackathon, oldest first so the front of the
+/// queue comes first. Participants see their own tickets. Can be sorted by `created_at` or
+/// `updated_at`.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/tickets",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ListTicketsQuery,
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Tickets, oldest first by default", body = Page<TicketInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a mentor or participant in this hackathon"),
+        (status = 404, description = "Hackathon not found, or mentorship is disabled"),
+        (status = 422, description = "Unknown status, or invalid page, sort or cursor")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn list_tickets(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ListTicketsQuery>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<TicketInfo>>, AppError> {
+    if !role.is_mentor() && !role.is_participant() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+
+    let mut select =
+        HelpTickets::find().filter(help_tickets::Column::HackathonId.eq(role.hackathon_id));
+
+    if !role.is_mentor() {
+        select = select.filter(help_tickets::Column::UserId.eq(role.user_id));
+    }
+
+    if let Some(status) = query.status {
+        if !TICKET_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::invalid("status", "Unknown ticket status"));
+        }
+        select = select.filter(help_tickets::Column::Status.eq(status));
+    }
+
+    let mut page = pagination::paginate(&state.db, select, &page, &LIST_SORT, |t| t).await?;
+    let tickets = std::mem::take(&mut page.items);
+
+    Ok(Json(page.with_items(ticket_infos(&state, tickets).await?)))
+}
+
+/// Get a help ticket
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/tickets/{ticket_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("ticket_id" = i32, Path, description = "Ticket ID")
+    ),
+    responses(
+        (status = 200, description = "The ticket", body = TicketInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "Ticket not found, or mentorship is disabled")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn get_ticket(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<TicketInfo>, AppError> {
+    let (_, ticket_id) = params;
+    require_mentors(&state, &role).await?;
+
+    let ticket = find_ticket(&state, &role, ticket_id).await?;
+    Ok(Json(ticket_info(&state, ticket).await?))
+}
+
+/// Claim an open ticket for the current mentor
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/tickets/{ticket_id}/claim",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("ticket_id" = i32, Path, description = "Ticket ID")
+    ),
+    responses(
+        (status = 200, description = "Ticket claimed", body = TicketInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a mentor in this hackathon"),
+        (status = 404, description = "Ticket not found, or mentorship is disabled"),
+        (status = 409, description = "Ticket is no longer open, or the hackathon is archived")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn claim_ticket(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<TicketInfo>, AppError> {
+    let (_, ticket_id) = params;
+    if !role.is_mentor() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let ticket = find_ticket(&state, &role, ticket_id).await?;
+    let active = help_tickets::ActiveModel {
+        status: Set("claimed".to_string()),
+        mentor_id: Set(Some(role.user_id)),
+        claimed_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+    let ticket = transition(&state, ticket, &["open"], active).await?;
+
+    Ok(Json(
+        publish(&state, TicketEventKind::Claimed, ticket).await?,
+    ))
+}
+
+/// Put a claimed ticket back in the queue
+///
+/// Only the mentor who claimed the ticket or an organizer can release it. The ticket keeps
+/// its place in the queue.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/tickets/{ticket_id}/release",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("ticket_id" = i32, Path, description = "Ticket ID")
+    ),
+    responses(
+        (status = 200, description = "Ticket released", body = TicketInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Ticket is claimed by another mentor"),
+        (status = 404, description = "Ticket not found, or mentorship is disabled"),
+        (status = 409, description = "Ticket is not claimed, or the hackathon is archived")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn release_ticket(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<TicketInfo>, AppError> {
+    let (_, ticket_id) = params;
+    require_mentors(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let ticket = find_ticket(&state, &role, ticket_id).await?;
+    if !role.is_organizer() && ticket.mentor_id != Some(role.user_id) {
+        return Err(AppError::Forbidden);
+    }
+
+    let active = help_tickets::ActiveModel {
+        status: Set("open".to_string()),
+        mentor_id: Set(None),
+        claimed_at: Set(None),
+        ..Default::default()
+    };
+    let ticket = transition(&state, ticket, &["claimed"], active).await?;
+
+    Ok(Json(
+        publish(&state, TicketEventKind::Released, ticket).await?,
+    ))
+}
+
+/// Mark a claimed ticket as resolved
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/tickets/{ticket_id}/resolve",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("ticket_id" = i32, Path, description = "Ticket ID")
+    ),
+    responses(
+        (status = 200, description = "Ticket resolved", body = TicketInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Ticket is claimed by another mentor"),
+        (status = 404, description = "Ticket not found, or mentorship is disabled"),
+        (status = 409, description = "Ticket is not claimed, or the hackathon is archived")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn resolve_ticket(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<TicketInfo>, AppError> {
+    let (_, ticket_id) = params;
+    require_mentors(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let ticket = find_ticket(&state, &role, ticket_id).await?;
+    if !role.is_organizer() && ticket.mentor_id != Some(role.user_id) {
+        return Err(AppError::Forbidden);
+    }
+
+    let active = help_tickets::ActiveModel {
+        status: Set("resolved".to_string()),
+        closed_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+    let ticket = transition(&state, ticket, &["claimed"], active).await?;
+
+    Ok(Json(
+        publish(&state, TicketEventKind::Resolved, ticket).await?,
+    ))
+}
+
+/// Cancel a ticket that no longer needs a mentor
+///
+/// Participants can cancel their own tickets and organizers can cancel any ticket.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/tickets/{ticket_id}/cancel",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("ticket_id" = i32, Path, description = "Ticket ID")
+    ),
+    responses(
+        (status = 200, description = "Ticket cancelled", body = TicketInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the participant who opened the ticket"),
+        (status = 404, description = "Ticket not found, or mentorship is disabled"),
+        (status = 409, description = "Ticket is already closed, or the hackathon is archived")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn cancel_ticket(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<TicketInfo>, AppError> {
+    let (_, ticket_id) = params;
+    require_mentors(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let ticket = find_ticket(&state, &role, ticket_id).await?;
+    if !role.is_organizer() && ticket.user_id != role.user_id {
+        return Err(AppError::Forbidden);
+    }
+
+    let active = help_tickets::ActiveModel {
+        status: Set("cancelled".to_string()),
+        closed_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+    let ticket = transition(&state, ticket, &["open", "claimed"], active).await?;
+
+    Ok(Json(
+        publish(&state, TicketEventKind::Cancelled, ticket).await?,
+    ))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TicketStats {
+    /// Tickets waiting for a mentor
+    pub queue_length: u64,
+    /// Tickets a mentor is working on
+    pub in_progress: u64,
+    pub resolved: u64,
+    pub cancelled: u64,
+    /// Average time from opening a ticket to a mentor claiming it
+    pub average_response_seconds: Option<i64>,
+    /// Average time from opening a ticket to it being resolved
+    pub average_resolution_seconds: Option<i64>,
+    /// How long the ticket at the front of the queue has been waiting
+    pub longest_wait_seconds: Option<i64>,
+}
+
+fn average(durations: impl Iterator<Item = chrono::TimeDelta>) -> Option<i64> {
+    let seconds: Vec<i64> = durations.map(|d| d.num_seconds()).collect();
+    if seconds.is_empty() {
+        return None;
+    }
+
+    Some(seconds.iter().sum::<i64>() / seconds.len() as i64)
+}
+
+/// Get queue length and mentor response times
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/tickets/stats",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Help queue statistics", body = TicketStats),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or mentorship is disabled")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn get_ticket_stats(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<TicketStats>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+
+    let tickets = HelpTickets::find()
+        .filter(help_tickets::Column::HackathonId.eq(role.hackathon_id))
+        .all(&state.db)
+        .await?;
+
+    let count = |status: &str| tickets.iter().filter(|t| t.status == status).count() as u64;
+    let now = Utc::now().naive_utc();
+
+    Ok(Json(TicketStats {
+        queue_length: count("open"),
+        in_progress: count("claimed"),
+        resolved: count("resolved"),
+        cancelled: count("cancelled"),
+        average_response_seconds: average(
+            tickets
+                .iter()
+                .filter_map(|t| Some(t.claimed_at? - t.created_at)),
+        ),
+        average_resolution_seconds: average(
+            tickets
+                .iter()
+                .filter(|t| t.status == "resolved")
+                .filter_map(|t| Some(t.closed_at? - t.created_at)),
+        ),
+        longest_wait_seconds: tickets
+            .iter()
+            .filter(|t| t.status == "open")
+            .map(|t| (now - t.created_at).num_seconds())
+            .max(),
+    }))
+}
+
+/// Stream help ticket changes as server-sent events
+///
+/// Each event is named after what happened to the ticket (`opened`, `claimed`, `released`,
+/// `resolved` or `cancelled`) and carries the ticket as JSON. Events are not replayed, so
+/// clients should list the tickets after connecting.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/tickets/events",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Stream of ticket events", content_type = "text/event-stream", body = TicketInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a mentor in this hackathon"),
+        (status = 404, description = "Hackathon not found, or mentorship is disabled")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn stream_ticket_events(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    if !role.is_mentor() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+
+    let hackathon_id = role.hackathon_id;
+    let stream = BroadcastStream::new(state.ticket_events.subscribe()).filter_map(move |event| {
+        // Subscribers that fall behind skip the events they missed
+        let event = event.ok().filter(|e| e.hackathon_id == hackathon_id)?;
+        let name = serde_json::to_value(event.kind).ok()?;
+
+        Event::default()
+            .event(name.as_str()?)
+            .json_data(&event.ticket)
+            .ok()
+            .map(Ok)
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MentorInfo {
+    pub user_id: i32,
+    pub email: String,
+    pub name: Option<String>,
+    /// Tickets the mentor has resolved in this hackathon
+    pub resolved: u64,
+}
+
+/// List a hackathon's mentors
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/mentors",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Mentors", body = Vec<MentorInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or mentorship is disabled")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn list_mentors(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<MentorInfo>>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+
+    let mentors = Users::find()
+        .inner_join(UserHackathonRoles)
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::Role.eq("mentor"))
+        .order_by_asc(users::Column::Email)
+        .all(&state.db)
+        .await?;
+
+    let resolved = HelpTickets::find()
+        .filter(help_tickets::Column::HackathonId.eq(role.hackathon_id))
+        .filter(help_tickets::Column::Status.eq("resolved"))
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        mentors
+            .into_iter()
+            .map(|u| MentorInfo {
+                resolved: resolved
+                    .iter()
+                    .filter(|t| t.mentor_id == Some(u.id))
+                    .count() as u64,
+                user_id: u.id,
+                email: u.email,
+                name: u.name,
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddMentorRequest {
+    /// Email of an existing user
+    pub email: String,
+}
+
+impl Validate for AddMentorRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", self.email.trim());
+    }
+}
+
+/// Give a user the mentor role in the hackathon
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/mentors",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = AddMentorRequest,
+    responses(
+        (status = 200, description = "User is now a mentor", body = MentorInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Mentorship is disabled, or no user with that email"),
+        (status = 409, description = "Several users have that email, user has another role, or the hackathon is archived"),
+        (status = 422, description = "Not an email address")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn add_mentor(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<AddMentorRequest>,
+) -> Result<Json<MentorInfo>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let user = roles::assign_by_email(&state, &role, &req.email, "mentor").await?;
+
+    let resolved = HelpTickets::find()
+        .filter(help_tickets::Column::HackathonId.eq(role.hackathon_id))
+        .filter(help_tickets::Column::MentorId.eq(user.id))
+        .filter(help_tickets::Column::Status.eq("resolved"))
+        .all(&state.db)
+        .await?
+        .len() as u64;
+
+    Ok(Json(MentorInfo {
+        user_id: user.id,
+        email: user.email,
+        name: user.name,
+        resolved,
+    }))
+}
+
+/// Remove a user's mentor role in the hackathon
+///
+/// Tickets the mentor had claimed go back to the queue.
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/mentors/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Mentor removed"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "User is not a mentor, or mentorship is disabled"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Mentorship"
+)]
+pub async fn remove_mentor(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, user_id) = params;
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_mentors(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let txn = state.db.begin().await?;
+    if !roles::unassign(&txn, role.hackathon_id, user_id, "mentor").await? {
+        return Err(AppError::NotFound("User is not a mentor in this hackathon"));
+    }
+
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "mentor.remove", "user", user_id),
+    )
+    .await?;
+
+    let claimed = HelpTickets::find()
+        .filter(help_tickets::Column::HackathonId.eq(role.hackathon_id))
+        .filter(help_tickets::Column::MentorId.eq(user_id))
+        .filter(help_tickets::Column::Status.eq("claimed"))
+        .all(&txn)
+        .await?;
+
+    HelpTickets::update_many()
+        .col_expr(help_tickets::Column::Status, Expr::value("open"))
+        .col_expr(help_tickets::Column::MentorId, Expr::value(None::<i32>))
+        .col_expr(
+            help_tickets::Column::ClaimedAt,
+            Expr::value(None::<NaiveDateTime>),
+        )
+        .col_expr(
+            help_tickets::Column::UpdatedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(help_tickets::Column::Id.is_in(claimed.iter().map(|t| t.id)))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    for ticket in claimed {
+        let ticket = help_tickets::Model {
+            status: "open".to_string(),
+            mentor_id: None,
+            claimed_at: None,
+            ..ticket
+        };
+        publish(&state, TicketEventKind::Released, ticket).await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,543 @@
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    admins::handlers::find_user_by_email,
+    audit::log::{self as audit, Entry},
+    auth::extractors::{AuthUser, OrganizationAdmin, RequireGlobalAdmin},
+    entities::{hackathons, organization_admins, organizations, prelude::*},
+    error::AppError,
+    hackathons::handlers::HackathonInfo,
+    pagination::{self, Page, PageParams, Sort},
+    validation::{
+        MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, Path, Query, ValidJson, Validate, Validator,
+    },
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationInfo {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<organizations::Model> for OrganizationInfo {
+    fn from(o: organizations::Model) -> Self {
+        OrganizationInfo {
+            id: o.id,
+            name: o.name,
+            slug: o.slug,
+            description: o.description,
+            created_at: o.created_at,
+        }
+    }
+}
+
+/// List the organizations the current user administers, or all of them for global admins
+#[utoipa::path(
+    get,
+    path = "/organizations",
+    responses(
+        (status = 200, description = "Organizations", body = Vec<OrganizationInfo>),
+        (status = 401, description = "Not authenticated"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn list_organizations(
+    auth: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<OrganizationInfo>>, AppError> {
+    let mut select = Organizations::find().order_by_asc(organizations::Column::Name);
+
+    if !auth.is_global_admin() {
+        select = select
+            .inner_join(OrganizationAdmins)
+            .filter(organization_admins::Column::UserId.eq(auth.user.id));
+    }
+
+    let organizations = select.all(&state.db).await?;
+
+    Ok(Json(
+        organizations
+            .into_iter()
+            .map(OrganizationInfo::from)
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+}
+
+impl Validate for CreateOrganizationRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name, MAX_NAME_LENGTH);
+        v.slug("slug", &self.slug);
+        v.max_length(
+            "description",
+            self.description.as_deref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
+    }
+}
+
+/// Create a new organization
+#[utoipa::path(
+    post,
+    path = "/organizations",
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 201, description = "Organization created", body = OrganizationInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a global admin"),
+        (status = 422, description = "Invalid field or slug already taken"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn create_organization(
+    admin: RequireGlobalAdmin,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<CreateOrganizationRequest>,
+) -> Result<(StatusCode, Json<OrganizationInfo>), AppError> {
+    let existing = Organizations::find()
+        .filter(organizations::Column::Slug.eq(&req.slug))
+        .one(&state.db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(AppError::invalid("slug", "This slug is already taken"));
+    }
+
+    let txn = state.db.begin().await?;
+    let organization = organizations::ActiveModel {
+        name: Set(req.name),
+        slug: Set(req.slug),
+        description: Set(req.description),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    tracing::info!("{} created organization {}", admin.email, organization.slug);
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: admin.user_id,
+            action: "organization.create",
+            target_type: "organization",
+            target_id: Some(organization.id),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(organization.into())))
+}
+
+/// Get an organization
+#[utoipa::path(
+    get,
+    path = "/organizations/{slug}",
+    params(
+        ("slug" = String, Path, description = "Organization slug")
+    ),
+    responses(
+        (status = 200, description = "The organization", body = OrganizationInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this organization"),
+        (status = 404, description = "Organization not found"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn get_organization(
+    admin: OrganizationAdmin,
+) -> Result<Json<OrganizationInfo>, AppError> {
+    Ok(Json(admin.organization.into()))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateOrganizationRequest {
+    pub name: Option<String>,
+    /// An empty string clears the description
+    pub description: Option<String>,
+}
+
+impl Validate for UpdateOrganizationRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            v.required("name", name, MAX_NAME_LENGTH);
+        }
+        v.max_length(
+            "description",
+            self.description.as_deref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
+    }
+}
+
+/// Update an organization's details
+#[utoipa::path(
+    patch,
+    path = "/organizations/{slug}",
+    params(
+        ("slug" = String, Path, description = "Organization slug")
+    ),
+    request_body = UpdateOrganizationRequest,
+    responses(
+        (status = 200, description = "Organization updated", body = OrganizationInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this organization"),
+        (status = 404, description = "Organization not found"),
+        (status = 422, description = "Invalid field"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn update_organization(
+    admin: OrganizationAdmin,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<UpdateOrganizationRequest>,
+) -> Result<Json<OrganizationInfo>, AppError> {
+    let before = admin.organization.clone();
+    let mut organization: organizations::ActiveModel = admin.organization.into();
+
+    if let Some(name) = req.name {
+        organization.name = Set(name);
+    }
+    if let Some(description) = req.description {
+        organization.description = Set(Some(description).filter(|d| !d.is_empty()));
+    }
+    organization.updated_at = Set(Utc::now().naive_utc());
+
+    let txn = state.db.begin().await?;
+    let result = organization.update(&txn).await?;
+
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: admin.user_id,
+            action: "organization.update",
+            target_type: "organization",
+            target_id: Some(result.id),
+            diff: audit::diff(&before, &result),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Json(result.into()))
+}
+
+/// Delete an organization, which must not own any hackathons
+#[utoipa::path(
+    delete,
+    path = "/organizations/{slug}",
+    params(
+        ("slug" = String, Path, description = "Organization slug")
+    ),
+    responses(
+        (status = 204, description = "Organization deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a global admin"),
+        (status = 404, description = "Organization not found"),
+        (status = 409, description = "Organization still owns hackathons"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn delete_organization(
+    admin: RequireGlobalAdmin,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let organization = Organizations::find()
+        .filter(organizations::Column::Slug.eq(&slug))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Organization not found"))?;
+
+    let hackathon_count = Hackathons::find()
+        .filter(hackathons::Column::OrganizationId.eq(organization.id))
+        .count(&state.db)
+        .await?;
+
+    if hackathon_count > 0 {
+        return Err(AppError::Conflict(
+            "The organization still owns hackathons, which must be deleted first",
+        ));
+    }
+
+    let txn = state.db.begin().await?;
+    Organizations::delete_by_id(organization.id)
+        .exec(&txn)
+        .await?;
+
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: admin.user_id,
+            action: "organization.delete",
+            target_type: "organization",
+            target_id: Some(organization.id),
+            diff: Some(json!({ "slug": slug })),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    tracing::info!("{} deleted organization {}", admin.email, slug);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+const HACKATHON_SORT: Sort<hackathons::Column> = Sort {
+    fields: &[
+        ("start_date", hackathons::Column::StartDate),
+        ("end_date", hackathons::Column::EndDate),
+        ("name", hackathons::Column::Name),
+    ],
+    default: "-start_date",
+    id: hackathons::Column::Id,
+};
+
+/// List an organization's hackathons, including unpublished and archived ones
+///
+/// Can be sorted by `start_date`, `end_date` or `name`.
+#[utoipa::path(
+    get,
+    path = "/organizations/{slug}/hackathons",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "The organization's hackathons, latest first by default", body = Page<HackathonInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this organization"),
+        (status = 404, description = "Organization not found"),
+        (status = 422, description = "Invalid page, sort or cursor"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn list_organization_hackathons(
+    admin: OrganizationAdmin,
+    State(state): State<AppState>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<HackathonInfo>>, AppError> {
+    let select =
+        Hackathons::find().filter(hackathons::Column::OrganizationId.eq(admin.organization.id));
+    let page = pagination::paginate(&state.db, select, &page, &HACKATHON_SORT, |h| h).await?;
+
+    Ok(Json(page.map(|hackathons| {
+        hackathons.into_iter().map(HackathonInfo::from).collect()
+    })))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationAdminInfo {
+    pub user_id: i32,
+    pub email: String,
+    pub name: Option<String>,
+    pub added_at: NaiveDateTime,
+}
+
+/// List an organization's admins
+#[utoipa::path(
+    get,
+    path = "/organizations/{slug}/admins",
+    params(
+        ("slug" = String, Path, description = "Organization slug")
+    ),
+    responses(
+        (status = 200, description = "The organization's admins", body = Vec<OrganizationAdminInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this organization"),
+        (status = 404, description = "Organization not found"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn list_organization_admins(
+    admin: OrganizationAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<OrganizationAdminInfo>>, AppError> {
+    let admins = OrganizationAdmins::find()
+        .filter(organization_admins::Column::OrganizationId.eq(admin.organization.id))
+        .order_by_asc(organization_admins::Column::CreatedAt)
+        .find_also_related(Users)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        admins
+            .into_iter()
+            .filter_map(|(admin, user)| {
+                user.map(|user| OrganizationAdminInfo {
+                    user_id: user.id,
+                    email: user.email,
+                    name: user.name,
+                    added_at: admin.created_at,
+                })
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddOrganizationAdminRequest {
+    /// Email of an existing user
+    pub email: String,
+}
+
+impl Validate for AddOrganizationAdminRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", self.email.trim());
+    }
+}
+
+/// Make a user an admin of an organization
+#[utoipa::path(
+    post,
+    path = "/organizations/{slug}/admins",
+    params(
+        ("slug" = String, Path, description = "Organization slug")
+    ),
+    request_body = AddOrganizationAdminRequest,
+    responses(
+        (status = 200, description = "User is now an admin of the organization", body = OrganizationAdminInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this organization"),
+        (status = 404, description = "Organization not found, or no user with that email"),
+        (status = 409, description = "Several users have that email"),
+        (status = 422, description = "Not an email address"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn add_organization_admin(
+    admin: OrganizationAdmin,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<AddOrganizationAdminRequest>,
+) -> Result<Json<OrganizationAdminInfo>, AppError> {
+    let user = find_user_by_email(&state, &req.email).await?;
+
+    let existing = OrganizationAdmins::find()
+        .filter(organization_admins::Column::OrganizationId.eq(admin.organization.id))
+        .filter(organization_admins::Column::UserId.eq(user.id))
+        .one(&state.db)
+        .await?;
+
+    let membership = match existing {
+        Some(membership) => membership,
+        None => {
+            let txn = state.db.begin().await?;
+            let membership = organization_admins::ActiveModel {
+                organization_id: Set(admin.organization.id),
+                user_id: Set(user.id),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            tracing::info!(
+                "{} added {} as an admin of {}",
+                admin.email,
+                user.email,
+                admin.organization.slug
+            );
+            audit::record(
+                &txn,
+                Entry {
+                    actor_id: admin.user_id,
+                    action: "organization_admin.add",
+                    target_type: "user",
+                    target_id: Some(user.id),
+                    diff: Some(json!({ "organization": admin.organization.slug })),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            txn.commit().await?;
+
+            membership
+        }
+    };
+
+    Ok(Json(OrganizationAdminInfo {
+        user_id: user.id,
+        email: user.email,
+        name: user.name,
+        added_at: membership.created_at,
+    }))
+}
+
+/// Remove an admin from an organization
+#[utoipa::path(
+    delete,
+    path = "/organizations/{slug}/admins/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Admin removed"),
+        (status = 400, description = "Admins cannot remove themselves"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this organization"),
+        (status = 404, description = "Organization not found, or user is not an admin of it"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn remove_organization_admin(
+    admin: OrganizationAdmin,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, user_id) = params;
+
+    // Prevents the last admin from locking the organization out
+    if user_id == admin.user_id {
+        return Err(AppError::BadRequest("Admins cannot remove themselves"));
+    }
+
+    let txn = state.db.begin().await?;
+    let result = OrganizationAdmins::delete_many()
+        .filter(organization_admins::Column::OrganizationId.eq(admin.organization.id))
+        .filter(organization_admins::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(
+            "User is not an admin of this organization",
+        ));
+    }
+
+    audit::record(
+        &txn,
+        Entry {
+            actor_id: admin.user_id,
+            action: "organization_admin.remove",
+            target_type: "user",
+            target_id: Some(user_id),
+            diff: Some(json!({ "organization": admin.organization.slug })),
+            ..Default::default()
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
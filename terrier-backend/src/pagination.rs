@@ -0,0 +1,476 @@
+//! Pagination and sorting of list endpoints, which all respond with a [`Page`].
+//!
+//! Clients either follow `next_cursor`, which stays correct while rows are added, or jump to
+//! a row with `offset`.
+
+use std::future::Future;
+
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ColumnTrait, ColumnType, Condition, DatabaseConnection, DbErr, EntityTrait, FromQueryResult,
+    IntoSimpleExpr, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    Select, SelectTwo, Value,
+    sea_query::{Expr, Func},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{error::AppError, validation::Validator};
+
+const DEFAULT_LIMIT: u64 = 50;
+const MAX_LIMIT: u64 = 200;
+
+/// Format of dates in cursors, which keeps fractional seconds so no row is skipped
+const CURSOR_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    /// Number of items to return, from 1 to 200 (default 50)
+    limit: Option<u64>,
+    /// `next_cursor` of the previous page
+    cursor: Option<String>,
+    /// Number of items to skip, which cannot be combined with `cursor`
+    offset: Option<u64>,
+    /// Field to sort by, prefixed with `-` for descending order
+    sort: Option<String>,
+}
+
+/// One page of a list
+#[derive(Serialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of items matching the filters, across every page
+    pub total: u64,
+    /// Pass as `cursor` to get the next page, missing on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(mut self, f: impl FnOnce(Vec<T>) -> Vec<U>) -> Page<U> {
+        let items = f(std::mem::take(&mut self.items));
+        self.with_items(items)
+    }
+
+    /// The same page with other items, usually built from the ones taken out of this page
+    pub fn with_items<U>(self, items: Vec<U>) -> Page<U> {
+        Page {
+            items,
+            total: self.total,
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// Fields a list can be sorted by
+pub struct Sort<C: 'static> {
+    /// Names accepted in `sort` and their columns, which must not be nullable
+    pub fields: &'static [(&'static str, C)],
+    /// Used when `sort` is missing, such as `-created_at`
+    pub default: &'static str,
+    /// Orders rows with the same value, so no row is skipped or repeated between pages
+    pub id: C,
+}
+
+/// A query that can be split into pages
+pub trait Pageable: QueryFilter + QueryOrder + QuerySelect + Clone + Send {
+    type Row: Send;
+
+    fn total(self, db: &DatabaseConnection) -> impl Future<Output = Result<u64, DbErr>> + Send;
+
+    fn fetch(
+        self,
+        db: &DatabaseConnection,
+    ) -> impl Future<Output = Result<Vec<Self::Row>, DbErr>> + Send;
+}
+
+impl<E> Pageable for Select<E>
+where
+    E: EntityTrait,
+    E::Model: FromQueryResult + Send + Sync,
+{
+    type Row = E::Model;
+
+    fn total(self, db: &DatabaseConnection) -> impl Future<Output = Result<u64, DbErr>> + Send {
+        PaginatorTrait::count(self, db)
+    }
+
+    fn fetch(
+        self,
+        db: &DatabaseConnection,
+    ) -> impl Future<Output = Result<Vec<Self::Row>, DbErr>> + Send {
+        self.all(db)
+    }
+}
+
+impl<E, F> Pageable for SelectTwo<E, F>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    E::Model: FromQueryResult + Send + Sync,
+    F::Model: FromQueryResult + Send + Sync,
+{
+    type Row = (E::Model, Option<F::Model>);
+
+    fn total(self, db: &DatabaseConnection) -> impl Future<Output = Result<u64, DbErr>> + Send {
+        PaginatorTrait::count(self, db)
+    }
+
+    fn fetch(
+        self,
+        db: &DatabaseConnection,
+    ) -> impl Future<Output = Result<Vec<Self::Row>, DbErr>> + Send {
+        self.all(db)
+    }
+}
+
+/// Position after the last row of a page
+#[derive(Serialize, Deserialize)]
+struct Cursor {
+    sort: String,
+    value: serde_json::Value,
+    id: i32,
+}
+
+/// The page asked for in [`PageParams`], once checked against the list's [`Sort`]
+struct PageRequest<'a, C> {
+    limit: u64,
+    /// Sort as given, such as `-created_at`, which cursors must match
+    sort_name: &'a str,
+    column: C,
+    descending: bool,
+    /// Sort value and id of the last row of the previous page
+    after: Option<(Value, i32)>,
+}
+
+/// Check `params` before anything is queried
+fn page_request<'a, C: ColumnTrait>(
+    params: &'a PageParams,
+    sort: &Sort<C>,
+) -> Result<PageRequest<'a, C>, AppError> {
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+    let sort_name = params.sort.as_deref().unwrap_or(sort.default);
+    let (field, descending) = match sort_name.strip_prefix('-') {
+        Some(field) => (field, true),
+        None => (sort_name, false),
+    };
+    let column = sort
+        .fields
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, column)| *column);
+
+    let mut v = Validator::default();
+    v.range("limit", limit, 1..=MAX_LIMIT);
+    if params.cursor.is_some() && params.offset.is_some() {
+        v.error("offset", "Cannot be combined with cursor");
+    }
+    if column.is_none() {
+        let names: Vec<_> = sort.fields.iter().map(|(name, _)| *name).collect();
+        v.error("sort", format!("Must be one of {}", names.join(", ")));
+    }
+    v.finish()?;
+    let column = column.ok_or(AppError::Internal)?;
+
+    let after = params
+        .cursor
+        .as_deref()
+        .map(|cursor| {
+            decode_cursor(cursor, sort_name, column)
+                .ok_or_else(|| AppError::invalid("cursor", "Invalid or expired cursor"))
+        })
+        .transpose()?;
+
+    Ok(PageRequest {
+        limit,
+        sort_name,
+        column,
+        descending,
+        after,
+    })
+}
+
+/// Fetch the page of `select` asked for in `params`, where `model` picks the row's model
+/// that holds the sort columns
+pub async fn paginate<Q, M>(
+    db: &DatabaseConnection,
+    select: Q,
+    params: &PageParams,
+    sort: &Sort<<M::Entity as EntityTrait>::Column>,
+    model: impl Fn(&Q::Row) -> &M,
+) -> Result<Page<Q::Row>, AppError>
+where
+    Q: Pageable,
+    M: ModelTrait,
+{
+    let PageRequest {
+        limit,
+        sort_name,
+        column,
+        descending,
+        after,
+    } = page_request(params, sort)?;
+
+    let total = select.clone().total(db).await?;
+
+    let order = if descending { Order::Desc } else { Order::Asc };
+    let mut select = select
+        .order_by(column, order.clone())
+        .order_by(sort.id, order);
+
+    select = match after {
+        Some((value, id)) => {
+            let condition = if descending {
+                Condition::any()
+                    .add(column.lt(value.clone()))
+                    .add(column.eq(value).and(sort.id.lt(id)))
+            } else {
+                Condition::any()
+                    .add(column.gt(value.clone()))
+                    .add(column.eq(value).and(sort.id.gt(id)))
+            };
+            select.filter(condition)
+        }
+        None => select.offset(params.offset.unwrap_or(0)),
+    };
+
+    // One extra row tells whether there is another page
+    let mut items = select.limit(limit + 1).fetch(db).await?;
+    let next_cursor = if items.len() as u64 > limit {
+        items.truncate(limit as usize);
+        items
+            .last()
+            .map(|row| encode_cursor(model(row), sort_name, column, sort.id))
+            .transpose()?
+    } else {
+        None
+    };
+
+    Ok(Page {
+        items,
+        total,
+        next_cursor,
+    })
+}
+
+fn encode_cursor<M: ModelTrait>(
+    model: &M,
+    sort: &str,
+    column: <M::Entity as EntityTrait>::Column,
+    id: <M::Entity as EntityTrait>::Column,
+) -> Result<String, AppError> {
+    let value = match model.get(column) {
+        Value::Int(Some(value)) => value.into(),
+        Value::BigInt(Some(value)) => value.into(),
+        Value::String(Some(value)) => (*value).into(),
+        Value::ChronoDateTime(Some(value)) => value.format(CURSOR_DATE_FORMAT).to_string().into(),
+        value => return Err(AppError::internal(format!("Cannot sort by {value:?}"))),
+    };
+    let Value::Int(Some(id)) = model.get(id) else {
+        return Err(AppError::internal("Row ids must be integers"));
+    };
+
+    let cursor = Cursor {
+        sort: sort.to_string(),
+        value,
+        id,
+    };
+    Ok(hex::encode(serde_json::to_vec(&cursor)?))
+}
+
+/// Read the sort value and id of a cursor, which must come from a list with the same sort
+fn decode_cursor<C: ColumnTrait>(cursor: &str, sort: &str, column: C) -> Option<(Value, i32)> {
+    let cursor: Cursor = serde_json::from_slice(&hex::decode(cursor).ok()?).ok()?;
+    if cursor.sort != sort {
+        return None;
+    }
+
+    let value = match column.def().get_column_type() {
+        ColumnType::Integer => Value::from(i32::try_from(cursor.value.as_i64()?).ok()?),
+        ColumnType::BigInteger => Value::from(cursor.value.as_i64()?),
+        ColumnType::String(_) | ColumnType::Text => Value::from(cursor.value.as_str()?),
+        ColumnType::DateTime | ColumnType::Timestamp => Value::from(
+            NaiveDateTime::parse_from_str(cursor.value.as_str()?, CURSOR_DATE_FORMAT).ok()?,
+        ),
+        _ => return None,
+    };
+    Some((value, cursor.id))
+}
+
+/// Filter for a search box, matching rows where `column` contains `text` ignoring case
+pub fn contains_ignoring_case(column: impl IntoSimpleExpr, text: &str) -> Condition {
+    let pattern = format!(
+        "%{}%",
+        text.to_lowercase()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+
+    Condition::all().add(Expr::expr(Func::lower(column.into_simple_expr())).like(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use chrono::NaiveDate;
+    use sea_orm::IdenStatic;
+
+    use super::*;
+    use crate::entities::sponsor_tiers;
+
+    const SORT: Sort<sponsor_tiers::Column> = Sort {
+        fields: &[
+            ("name", sponsor_tiers::Column::Name),
+            ("rank", sponsor_tiers::Column::Rank),
+            ("created_at", sponsor_tiers::Column::CreatedAt),
+        ],
+        default: "-created_at",
+        id: sponsor_tiers::Column::Id,
+    };
+
+    fn tier() -> sponsor_tiers::Model {
+        sponsor_tiers::Model {
+            id: 7,
+            hackathon_id: 1,
+            name: "Gold".to_string(),
+            rank: 3,
+            resume_book: true,
+            prize_track: false,
+            benefits: None,
+            created_at: NaiveDate::from_ymd_opt(2025, 1, 31)
+                .unwrap()
+                .and_hms_micro_opt(9, 30, 15, 123_456)
+                .unwrap(),
+        }
+    }
+
+    fn params(sort: Option<&str>, cursor: Option<String>) -> PageParams {
+        PageParams {
+            limit: None,
+            cursor,
+            offset: None,
+            sort: sort.map(str::to_string),
+        }
+    }
+
+    /// Fields rejected by [`page_request`]
+    fn rejected(params: &PageParams) -> Vec<String> {
+        match page_request(params, &SORT) {
+            Ok(_) => Vec::new(),
+            Err(error) => {
+                assert_eq!(error.status(), StatusCode::UNPROCESSABLE_ENTITY);
+                let AppError::Validation(fields) = error else {
+                    unreachable!()
+                };
+                fields.into_iter().map(|f| f.field).collect()
+            }
+        }
+    }
+
+    #[test]
+    fn cursors_round_trip() {
+        let tier = tier();
+        for (sort, column, value) in [
+            (
+                "-created_at",
+                sponsor_tiers::Column::CreatedAt,
+                Value::from(tier.created_at),
+            ),
+            ("name", sponsor_tiers::Column::Name, Value::from("Gold")),
+            ("-rank", sponsor_tiers::Column::Rank, Value::from(3)),
+        ] {
+            let cursor = encode_cursor(&tier, sort, column, sponsor_tiers::Column::Id).unwrap();
+            assert_eq!(
+                decode_cursor(&cursor, sort, column),
+                Some((value, 7)),
+                "{sort}"
+            );
+
+            let params = params(Some(sort), Some(cursor));
+            let request = page_request(&params, &SORT).unwrap();
+            assert_eq!(request.after.map(|(_, id)| id), Some(7));
+        }
+    }
+
+    #[test]
+    fn invalid_cursors_are_rejected() {
+        let tier = tier();
+        let cursor = |value: serde_json::Value| {
+            let cursor = Cursor {
+                sort: "-created_at".to_string(),
+                value,
+                id: 7,
+            };
+            hex::encode(serde_json::to_vec(&cursor).unwrap())
+        };
+        let other_sort = encode_cursor(
+            &tier,
+            "name",
+            sponsor_tiers::Column::Name,
+            sponsor_tiers::Column::Id,
+        )
+        .unwrap();
+
+        for invalid in [
+            "not hex".to_string(),
+            hex::encode("not json"),
+            other_sort,
+            cursor("yesterday".into()),
+            cursor(3.into()),
+        ] {
+            let params = params(None, Some(invalid));
+            assert_eq!(rejected(&params), ["cursor"]);
+        }
+
+        // A cursor from a list sorted the same way is accepted
+        assert!(rejected(&params(None, Some(cursor("2025-01-31T09:30:15.5".into())))).is_empty());
+    }
+
+    #[test]
+    fn integer_cursors_must_fit_the_column() {
+        let cursor = Cursor {
+            sort: "rank".to_string(),
+            value: i64::MAX.into(),
+            id: 7,
+        };
+        let cursor = hex::encode(serde_json::to_vec(&cursor).unwrap());
+        assert_eq!(rejected(&params(Some("rank"), Some(cursor))), ["cursor"]);
+    }
+
+    #[test]
+    fn sort_is_parsed() {
+        for (sort, sort_name, column, descending) in [
+            (None, "-created_at", "created_at", true),
+            (Some("name"), "name", "name", false),
+            (Some("-rank"), "-rank", "rank", true),
+        ] {
+            let params = params(sort, None);
+            let request = page_request(&params, &SORT).unwrap();
+            assert_eq!(request.sort_name, sort_name);
+            assert_eq!(request.column.as_str(), column);
+            assert_eq!(request.descending, descending);
+        }
+
+        for sort in ["benefits", "--rank", "", "-"] {
+            assert_eq!(rejected(&params(Some(sort), None)), ["sort"], "{sort}");
+        }
+    }
+
+    #[test]
+    fn limit_and_offset_are_checked() {
+        for limit in [0, MAX_LIMIT + 1] {
+            let params = PageParams {
+                limit: Some(limit),
+                ..params(None, None)
+            };
+            assert_eq!(rejected(&params), ["limit"]);
+        }
+
+        let params = PageParams {
+            offset: Some(10),
+            ..params(None, Some("00".to_string()))
+        };
+        assert_eq!(rejected(&params), ["offset"]);
+    }
+}
@@ -0,0 +1,581 @@
+use std::collections::BTreeMap;
+
+use axum::{Json, extract::State};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    sea_query,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    AppState,
+    auth::extractors::{AuthUser, HackathonRole},
+    entities::{hackathons, prelude::*, user_hackathon_roles, user_profiles, users},
+    error::AppError,
+    hackathons::settings::HackathonSettings,
+    validation::{Path, Query, ValidJson, Validate, Validator},
+};
+
+pub const SHIRT_SIZES: [&str; 7] = ["xs", "s", "m", "l", "xl", "xxl", "xxxl"];
+
+/// Dietary restrictions catering is ordered by. `none` cannot be combined with the others.
+pub const DIETARY_RESTRICTIONS: [&str; 9] = [
+    "none",
+    "vegetarian",
+    "vegan",
+    "halal",
+    "kosher",
+    "gluten_free",
+    "dairy_free",
+    "nut_allergy",
+    "other",
+];
+
+const MAX_SHORT_FIELD_LENGTH: usize = 255;
+const MAX_LONG_FIELD_LENGTH: usize = 2000;
+
+#[derive(Serialize, ToSchema)]
+pub struct ProfileInfo {
+    pub user_id: i32,
+    pub name: Option<String>,
+    /// Missing for judges and sponsors unless the user shares their resume with sponsors
+    pub email: Option<String>,
+    pub pronouns: Option<String>,
+    pub school: Option<String>,
+    pub major: Option<String>,
+    pub graduation_year: Option<i32>,
+    pub github: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+    pub resume_file_name: Option<String>,
+    pub resume_uploaded_at: Option<NaiveDateTime>,
+    /// Whether sponsors can see the resume in the resume book
+    pub resume_sponsor_consent: bool,
+    /// Whether sponsors can record the user as a lead by scanning their badge
+    pub lead_sharing_consent: bool,
+    /// The fields below are only visible to the user themselves and to organizers
+    pub phone: Option<String>,
+    pub dietary_restrictions: Vec<String>,
+    pub dietary_notes: Option<String>,
+    pub accessibility_needs: Option<String>,
+    pub shirt_size: Option<String>,
+    pub emergency_contact_name: Option<String>,
+    pub emergency_contact_phone: Option<String>,
+    pub emergency_contact_relationship: Option<String>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl ProfileInfo {
+    /// A user's full profile, which is empty if they have not filled it in yet
+    pub fn new(user: users::Model, profile: Option<user_profiles::Model>) -> Self {
+        let profile = profile.unwrap_or_else(|| user_profiles::Model {
+            user_id: user.id,
+            pronouns: None,
+            phone: None,
+            school: None,
+            major: None,
+            graduation_year: None,
+            dietary_restrictions: String::new(),
+            dietary_notes: None,
+            accessibility_needs: None,
+            shirt_size: None,
+            emergency_contact_name: None,
+            emergency_contact_phone: None,
+            emergency_contact_relationship: None,
+            github: None,
+            linkedin: None,
+            website: None,
+            resume_key: None,
+            resume_file_name: None,
+            resume_uploaded_at: None,
+            resume_sponsor_consent: false,
+            lead_sharing_consent: false,
+            created_at: NaiveDateTime::MIN,
+            updated_at: NaiveDateTime::MIN,
+        });
+
+        ProfileInfo {
+            user_id: user.id,
+            name: user.name,
+            email: Some(user.email),
+            pronouns: profile.pronouns,
+            school: profile.school,
+            major: profile.major,
+            graduation_year: profile.graduation_year,
+            github: profile.github,
+            linkedin: profile.linkedin,
+            website: profile.website,
+            resume_file_name: profile.resume_file_name,
+            resume_uploaded_at: profile.resume_uploaded_at,
+            resume_sponsor_consent: profile.resume_sponsor_consent,
+            lead_sharing_consent: profile.lead_sharing_consent,
+            phone: profile.phone,
+            dietary_restrictions: parse_dietary_restrictions(&profile.dietary_restrictions),
+            dietary_notes: profile.dietary_notes,
+            accessibility_needs: profile.accessibility_needs,
+            shirt_size: profile.shirt_size,
+            emergency_contact_name: profile.emergency_contact_name,
+            emergency_contact_phone: profile.emergency_contact_phone,
+            emergency_contact_relationship: profile.emergency_contact_relationship,
+            updated_at: Some(profile.updated_at).filter(|t| *t != NaiveDateTime::MIN),
+        }
+    }
+
+    /// Remove health and logistics details, and contact details unless the user shares their
+    /// resume with sponsors, leaving what a user would put on a resume
+    fn redact(self) -> Self {
+        // Sponsors only see that a resume exists, or how to reach its owner, if it is shared
+        // with them
+        let shared = self.resume_sponsor_consent;
+
+        ProfileInfo {
+            email: self.email.filter(|_| shared),
+            github: self.github.filter(|_| shared),
+            linkedin: self.linkedin.filter(|_| shared),
+            website: self.website.filter(|_| shared),
+            resume_file_name: self.resume_file_name.filter(|_| shared),
+            resume_uploaded_at: self.resume_uploaded_at.filter(|_| shared),
+            phone: None,
+            dietary_restrictions: Vec::new(),
+            dietary_notes: None,
+            accessibility_needs: None,
+            shirt_size: None,
+            emergency_contact_name: None,
+            emergency_contact_phone: None,
+            emergency_contact_relationship: None,
+            ..self
+        }
+    }
+
+    /// Required fields from [`crate::hackathons::settings::PROFILE_FIELDS`] that have not been filled in
+    fn missing_fields(&self, required: &[String]) -> Vec<String> {
+        required
+            .iter()
+            .filter(|field| match field.as_str() {
+                "pronouns" => self.pronouns.is_none(),
+                "phone" => self.phone.is_none(),
+                "school" => self.school.is_none(),
+                "major" => self.major.is_none(),
+                "graduation_year" => self.graduation_year.is_none(),
+                "dietary_restrictions" => self.dietary_restrictions.is_empty(),
+                "accessibility_needs" => self.accessibility_needs.is_none(),
+                "shirt_size" => self.shirt_size.is_none(),
+                "emergency_contact" => {
+                    self.emergency_contact_name.is_none() || self.emergency_contact_phone.is_none()
+                }
+                "github" => self.github.is_none(),
+                "linkedin" => self.linkedin.is_none(),
+                "website" => self.website.is_none(),
+                "resume" => self.resume_file_name.is_none(),
+                _ => false,
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+pub fn parse_dietary_restrictions(restrictions: &str) -> Vec<String> {
+    restrictions
+        .split(',')
+        .filter(|r| !r.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProfileQuery {
+    /// Report the fields this hackathon requires that are missing from the profile
+    hackathon: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProfileResponse {
+    pub profile: ProfileInfo,
+    /// Fields required by the hackathon given in the query that have not been filled in
+    pub missing_fields: Vec<String>,
+}
+
+/// Get the current user's profile
+#[utoipa::path(
+    get,
+    path = "/profile",
+    params(ProfileQuery),
+    responses(
+        (status = 200, description = "The current user's profile", body = ProfileResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Profiles"
+)]
+pub async fn get_profile(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<ProfileQuery>,
+) -> Result<Json<ProfileResponse>, AppError> {
+    let profile = UserProfiles::find_by_id(auth.user.id)
+        .one(&state.db)
+        .await?;
+
+    let profile = ProfileInfo::new(auth.user, profile);
+    let missing_fields = match &query.hackathon {
+        Some(slug) => {
+            let hackathon = Hackathons::find()
+                .filter(hackathons::Column::Slug.eq(slug))
+                .one(&state.db)
+                .await?
+                .ok_or(AppError::NotFound("Hackathon not found"))?;
+
+            profile.missing_fields(&HackathonSettings::of(&hackathon).required_profile_fields)
+        }
+        None => Vec::new(),
+    };
+
+    Ok(Json(ProfileResponse {
+        profile,
+        missing_fields,
+    }))
+}
+
+/// Fields left out are unchanged, and an empty string clears a field
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateProfileRequest {
+    pub pronouns: Option<String>,
+    pub phone: Option<String>,
+    pub school: Option<String>,
+    pub major: Option<String>,
+    /// `null` clears the year
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<i32>)]
+    pub graduation_year: Option<Option<i32>>,
+    /// From the dietary restrictions catering is ordered by
+    pub dietary_restrictions: Option<Vec<String>>,
+    pub dietary_notes: Option<String>,
+    pub accessibility_needs: Option<String>,
+    /// One of `xs`, `s`, `m`, `l`, `xl`, `xxl` or `xxxl`
+    pub shirt_size: Option<String>,
+    pub emergency_contact_name: Option<String>,
+    pub emergency_contact_phone: Option<String>,
+    pub emergency_contact_relationship: Option<String>,
+    pub github: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+    /// Share the uploaded resume with sponsors of hackathons the user participates in
+    pub resume_sponsor_consent: Option<bool>,
+    /// Let sponsors record the user's name, email and school when they scan their badge
+    pub lead_sharing_consent: Option<bool>,
+}
+
+/// Tell a field set to `null`, which is `Some(None)`, apart from one left out
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+impl Validate for UpdateProfileRequest {
+    fn validate(&self, v: &mut Validator) {
+        for (field, value, max) in [
+            ("pronouns", &self.pronouns, MAX_SHORT_FIELD_LENGTH),
+            ("phone", &self.phone, MAX_SHORT_FIELD_LENGTH),
+            ("school", &self.school, MAX_SHORT_FIELD_LENGTH),
+            ("major", &self.major, MAX_SHORT_FIELD_LENGTH),
+            (
+                "emergency_contact_name",
+                &self.emergency_contact_name,
+                MAX_SHORT_FIELD_LENGTH,
+            ),
+            (
+                "emergency_contact_phone",
+                &self.emergency_contact_phone,
+                MAX_SHORT_FIELD_LENGTH,
+            ),
+            (
+                "emergency_contact_relationship",
+                &self.emergency_contact_relationship,
+                MAX_SHORT_FIELD_LENGTH,
+            ),
+            ("dietary_notes", &self.dietary_notes, MAX_LONG_FIELD_LENGTH),
+            (
+                "accessibility_needs",
+                &self.accessibility_needs,
+                MAX_LONG_FIELD_LENGTH,
+            ),
+        ] {
+            v.max_length(field, value.as_deref(), max);
+        }
+
+        for (field, link) in [
+            ("github", &self.github),
+            ("linkedin", &self.linkedin),
+            ("website", &self.website),
+        ] {
+            if let Some(link) = link.as_deref().filter(|l| !l.is_empty()) {
+                v.max_length(field, Some(link), MAX_SHORT_FIELD_LENGTH);
+                v.url(field, link);
+            }
+        }
+
+        if let Some(size) = self.shirt_size.as_deref().filter(|s| !s.is_empty()) {
+            v.one_of("shirt_size", size, &SHIRT_SIZES);
+        }
+
+        if let Some(Some(year)) = self.graduation_year {
+            v.range("graduation_year", year, 1950..=2100);
+        }
+
+        if let Some(restrictions) = &self.dietary_restrictions {
+            for restriction in restrictions {
+                v.one_of("dietary_restrictions", restriction, &DIETARY_RESTRICTIONS);
+            }
+            if restrictions.len() > 1 && restrictions.iter().any(|r| r == "none") {
+                v.error(
+                    "dietary_restrictions",
+                    "none cannot be combined with other restrictions",
+                );
+            }
+        }
+    }
+}
+
+/// Update the current user's profile
+#[utoipa::path(
+    patch,
+    path = "/profile",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = ProfileInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 422, description = "Invalid field")
+    ),
+    tag = "Profiles"
+)]
+pub async fn update_profile(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<UpdateProfileRequest>,
+) -> Result<Json<ProfileInfo>, AppError> {
+    let existing = UserProfiles::find_by_id(auth.user.id)
+        .one(&state.db)
+        .await?;
+
+    let is_new = existing.is_none();
+    let mut profile: user_profiles::ActiveModel = match existing {
+        Some(profile) => profile.into(),
+        None => user_profiles::ActiveModel {
+            user_id: Set(auth.user.id),
+            ..Default::default()
+        },
+    };
+
+    for (column, value) in [
+        (&mut profile.pronouns, req.pronouns),
+        (&mut profile.phone, req.phone),
+        (&mut profile.school, req.school),
+        (&mut profile.major, req.major),
+        (&mut profile.dietary_notes, req.dietary_notes),
+        (&mut profile.accessibility_needs, req.accessibility_needs),
+        (&mut profile.shirt_size, req.shirt_size),
+        (
+            &mut profile.emergency_contact_name,
+            req.emergency_contact_name,
+        ),
+        (
+            &mut profile.emergency_contact_phone,
+            req.emergency_contact_phone,
+        ),
+        (
+            &mut profile.emergency_contact_relationship,
+            req.emergency_contact_relationship,
+        ),
+        (&mut profile.github, req.github),
+        (&mut profile.linkedin, req.linkedin),
+        (&mut profile.website, req.website),
+    ] {
+        if let Some(value) = value {
+            let value = value.trim();
+            *column = Set((!value.is_empty()).then(|| value.to_string()));
+        }
+    }
+
+    if let Some(year) = req.graduation_year {
+        profile.graduation_year = Set(year);
+    }
+    if let Some(consent) = req.resume_sponsor_consent {
+        profile.resume_sponsor_consent = Set(consent);
+    }
+    if let Some(consent) = req.lead_sharing_consent {
+        profile.lead_sharing_consent = Set(consent);
+    }
+    if let Some(mut restrictions) = req.dietary_restrictions {
+        restrictions.sort();
+        restrictions.dedup();
+        profile.dietary_restrictions = Set(restrictions.join(","));
+    }
+
+    let result = if is_new {
+        profile.insert(&state.db).await
+    } else {
+        profile.updated_at = Set(Utc::now().naive_utc());
+        profile.update(&state.db).await
+    }?;
+
+    Ok(Json(ProfileInfo::new(auth.user, Some(result))))
+}
+
+/// Get the profile of someone in a hackathon
+///
+/// Organizers see the full profile. Judges and sponsors can only see participants, without
+/// health or logistics details, and without contact details unless the participant shares
+/// their resume with sponsors. Sponsors cannot see participants who share nothing with them.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/profiles/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "The user's profile", body = ProfileInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not allowed to view this profile"),
+        (status = 404, description = "Hackathon not found, or user is not in it")
+    ),
+    tag = "Profiles"
+)]
+pub async fn get_hackathon_profile(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<ProfileInfo>, AppError> {
+    let (_, user_id) = params;
+
+    if !(role.is_organizer() || role.is_judge() || role.is_sponsor()) {
+        return Err(AppError::Forbidden);
+    }
+
+    let target_role = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.eq(user_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("User is not part of this hackathon"))?;
+
+    if !role.is_organizer() && target_role.role != "participant" {
+        return Err(AppError::Forbidden);
+    }
+
+    let (user, profile) = Users::find_by_id(user_id)
+        .find_also_related(UserProfiles)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("User not found"))?;
+
+    let profile = ProfileInfo::new(user, profile);
+
+    // Sponsors are not told whether participants who opted out are at the hackathon
+    if !role.is_organizer()
+        && role.is_sponsor()
+        && !(profile.resume_sponsor_consent || profile.lead_sharing_consent)
+    {
+        return Err(AppError::NotFound("User is not part of this hackathon"));
+    }
+
+    if role.is_organizer() {
+        Ok(Json(profile))
+    } else {
+        Ok(Json(profile.redact()))
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProfileSummary {
+    /// Everyone in the hackathon except applicants
+    pub people: u64,
+    /// People who have not filled in a profile at all
+    pub missing_profiles: u64,
+    /// Count per shirt size, with `unspecified` for people who have not picked one
+    pub shirt_sizes: BTreeMap<String, u64>,
+    /// Count per dietary restriction, where people can have several
+    pub dietary_restrictions: BTreeMap<String, u64>,
+    /// People who described accessibility needs
+    pub accessibility_needs: u64,
+}
+
+/// Get aggregate profile counts for catering and swag ordering
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/profiles/summary",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Profile counts", body = ProfileSummary),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found")
+    ),
+    tag = "Profiles"
+)]
+pub async fn get_profile_summary(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<ProfileSummary>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    // Applicants who have not been accepted are not fed or given swag
+    let people = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::Role.ne("applicant"))
+        .count(&state.db)
+        .await?;
+
+    let people_ids = sea_query::Query::select()
+        .column(user_hackathon_roles::Column::UserId)
+        .from(UserHackathonRoles)
+        .and_where(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .and_where(user_hackathon_roles::Column::Role.ne("applicant"))
+        .to_owned();
+
+    let profiles = UserProfiles::find()
+        .filter(user_profiles::Column::UserId.in_subquery(people_ids))
+        .all(&state.db)
+        .await?;
+
+    let mut summary = ProfileSummary {
+        people,
+        missing_profiles: people.saturating_sub(profiles.len() as u64),
+        shirt_sizes: BTreeMap::new(),
+        dietary_restrictions: BTreeMap::new(),
+        accessibility_needs: 0,
+    };
+
+    if summary.missing_profiles > 0 {
+        summary
+            .shirt_sizes
+            .insert("unspecified".to_string(), summary.missing_profiles);
+    }
+
+    for profile in profiles {
+        let size = profile
+            .shirt_size
+            .unwrap_or_else(|| "unspecified".to_string());
+        *summary.shirt_sizes.entry(size).or_default() += 1;
+
+        for restriction in parse_dietary_restrictions(&profile.dietary_restrictions) {
+            *summary.dietary_restrictions.entry(restriction).or_default() += 1;
+        }
+
+        if profile.accessibility_needs.is_some() {
+            summary.accessibility_needs += 1;
+        }
+    }
+
+    Ok(Json(summary))
+}
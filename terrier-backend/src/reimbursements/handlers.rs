@@ -0,0 +1,1377 @@
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    Json,
+    extract::{Multipart, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    AppState,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    entities::{
+        prelude::*, reimbursement_receipts, reimbursement_requests, user_hackathon_roles, users,
+    },
+    error::AppError,
+    exports::writer::{self, ExportFormat, Rows},
+    hackathons::{handlers::require_feature, phase::HackathonPhase, settings::Reimbursements},
+    pagination::{self, Page, PageParams, Sort},
+    storage::sanitize_file_name,
+    validation::{Path, Query, ValidJson, Validate, Validator},
+};
+
+/// Every status a reimbursement request can have
+pub const REIMBURSEMENT_STATUSES: [&str; 5] = [
+    "submitted",
+    "approved",
+    "partially_approved",
+    "denied",
+    "withdrawn",
+];
+
+/// Requests that will be paid out
+const PAYOUT_STATUSES: [&str; 2] = ["approved", "partially_approved"];
+
+/// Requesters looked up at a time while exporting payouts
+const EXPORT_BATCH_SIZE: usize = 500;
+
+const PAYOUT_COLUMNS: [&str; 7] = [
+    "name",
+    "email",
+    "region",
+    "status",
+    "amount_requested",
+    "amount_approved",
+    "reviewed_at",
+];
+
+/// Largest receipt that can be uploaded
+pub const MAX_RECEIPT_SIZE: usize = 10 * 1024 * 1024;
+
+/// Request body limit for uploads, leaving room for the multipart framing around the file
+pub const UPLOAD_BODY_LIMIT: usize = MAX_RECEIPT_SIZE + 64 * 1024;
+
+const MAX_RECEIPTS: usize = 10;
+const MAX_TEXT_LENGTH: usize = 2000;
+
+/// The content type of a receipt, from the magic bytes at the start of the file
+fn receipt_content_type(content: &[u8]) -> Option<&'static str> {
+    if content.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else if content.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if content.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else {
+        None
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReceiptInfo {
+    pub id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    /// In bytes
+    pub size: i32,
+    pub uploaded_at: NaiveDateTime,
+}
+
+impl From<reimbursement_receipts::Model> for ReceiptInfo {
+    fn from(r: reimbursement_receipts::Model) -> Self {
+        ReceiptInfo {
+            id: r.id,
+            file_name: r.file_name,
+            content_type: r.content_type,
+            size: r.size,
+            uploaded_at: r.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Requester {
+    pub id: i32,
+    pub name: Option<String>,
+    pub email: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReimbursementInfo {
+    pub id: i32,
+    pub requester: Option<Requester>,
+    pub region: String,
+    /// Details of the trip
+    pub description: Option<String>,
+    pub amount_requested_cents: i64,
+    /// Set once the request is approved, in full or in part
+    pub amount_approved_cents: Option<i64>,
+    /// One of `submitted`, `approved`, `partially_approved`, `denied` or `withdrawn`
+    pub status: String,
+    pub review_notes: Option<String>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub receipts: Vec<ReceiptInfo>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Requests with their requesters and receipts
+async fn reimbursement_infos(
+    state: &AppState,
+    requests: Vec<reimbursement_requests::Model>,
+) -> Result<Vec<ReimbursementInfo>, AppError> {
+    let request_ids: Vec<i32> = requests.iter().map(|r| r.id).collect();
+    let user_ids: Vec<i32> = requests.iter().map(|r| r.user_id).collect();
+
+    let users: HashMap<i32, users::Model> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let mut receipts: HashMap<i32, Vec<ReceiptInfo>> = HashMap::new();
+    for receipt in ReimbursementReceipts::find()
+        .filter(reimbursement_receipts::Column::RequestId.is_in(request_ids))
+        .order_by_asc(reimbursement_receipts::Column::CreatedAt)
+        .all(&state.db)
+        .await?
+    {
+        receipts
+            .entry(receipt.request_id)
+            .or_default()
+            .push(receipt.into());
+    }
+
+    Ok(requests
+        .into_iter()
+        .map(|r| ReimbursementInfo {
+            requester: users.get(&r.user_id).map(|u| Requester {
+                id: u.id,
+                name: u.name.clone(),
+                email: u.email.clone(),
+            }),
+            receipts: receipts.remove(&r.id).unwrap_or_default(),
+            id: r.id,
+            region: r.region,
+            description: r.description,
+            amount_requested_cents: r.amount_requested_cents,
+            amount_approved_cents: r.amount_approved_cents,
+            status: r.status,
+            review_notes: r.review_notes,
+            reviewed_at: r.reviewed_at,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        })
+        .collect())
+}
+
+async fn reimbursement_info(
+    state: &AppState,
+    request: reimbursement_requests::Model,
+) -> Result<ReimbursementInfo, AppError> {
+    reimbursement_infos(state, vec![request])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::internal("Request missing from its own info"))
+}
+
+/// Check the hackathon uses the reimbursements module, returning its budget and regions
+async fn require_reimbursements(
+    state: &AppState,
+    role: &HackathonRole,
+) -> Result<Reimbursements, AppError> {
+    let settings = require_feature(state, role.hackathon_id, |f| f.reimbursements).await?;
+    Ok(settings.reimbursements)
+}
+
+async fn require_finance(
+    state: &AppState,
+    role: &HackathonRole,
+) -> Result<Reimbursements, AppError> {
+    if !role.is_finance() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_reimbursements(state, role).await
+}
+
+async fn find_request(
+    state: &AppState,
+    role: &HackathonRole,
+    request_id: i32,
+) -> Result<reimbursement_requests::Model, AppError> {
+    let request = ReimbursementRequests::find_by_id(request_id)
+        .filter(reimbursement_requests::Column::HackathonId.eq(role.hackathon_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Reimbursement request not found"))?;
+
+    // Participants can only see their own request
+    if !role.is_finance() && request.user_id != role.user_id {
+        return Err(AppError::NotFound("Reimbursement request not found"));
+    }
+
+    Ok(request)
+}
+
+/// The caller's own request, which can only change until it has been reviewed
+async fn find_own_pending_request(
+    state: &AppState,
+    role: &HackathonRole,
+    request_id: i32,
+) -> Result<reimbursement_requests::Model, AppError> {
+    let request = find_request(state, role, request_id).await?;
+    if request.user_id != role.user_id {
+        return Err(AppError::Forbidden);
+    }
+    if request.status != "submitted" {
+        return Err(AppError::Conflict(
+            "The request can no longer be changed once it has been reviewed",
+        ));
+    }
+
+    Ok(request)
+}
+
+/// Change the user's own request, failing if it was reviewed or withdrawn since it was read
+async fn update_pending(
+    db: &impl ConnectionTrait,
+    request_id: i32,
+    mut active: reimbursement_requests::ActiveModel,
+) -> Result<reimbursement_requests::Model, AppError> {
+    active.updated_at = Set(Utc::now().naive_utc());
+
+    let result = ReimbursementRequests::update_many()
+        .set(active)
+        .filter(reimbursement_requests::Column::Id.eq(request_id))
+        .filter(reimbursement_requests::Column::Status.eq("submitted"))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::Conflict(
+            "The request can no longer be changed once it has been reviewed",
+        ));
+    }
+
+    ReimbursementRequests::find_by_id(request_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Reimbursement request not found"))
+}
+
+/// Check the region exists and the amount is positive and within its per-person cap
+fn check_amount(
+    reimbursements: &Reimbursements,
+    region: &str,
+    amount_cents: i64,
+) -> Result<(), AppError> {
+    let region = reimbursements
+        .region(region)
+        .ok_or_else(|| AppError::invalid("region", "Unknown region"))?;
+
+    if amount_cents <= 0 || amount_cents > region.cap_cents {
+        return Err(AppError::invalid(
+            "amount_cents",
+            format!("Must be between 1 and {} cents", region.cap_cents),
+        ));
+    }
+
+    Ok(())
+}
+
+fn clean_text(text: Option<String>) -> Option<String> {
+    text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateReimbursementRequest {
+    /// One of the hackathon's reimbursement regions
+    pub region: String,
+    pub amount_cents: i64,
+    /// Details of the trip
+    pub description: Option<String>,
+}
+
+impl Validate for CreateReimbursementRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.at_least("amount_cents", self.amount_cents, 1);
+        v.max_length(
+            "description",
+            self.description.as_deref().map(str::trim),
+            MAX_TEXT_LENGTH,
+        );
+    }
+}
+
+/// Request a travel reimbursement for the current participant
+///
+/// Each participant can make one request per hackathon, for up to their region's cap.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/reimbursements",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = CreateReimbursementRequest,
+    responses(
+        (status = 200, description = "Request submitted", body = ReimbursementInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a participant in this hackathon"),
+        (status = 404, description = "Hackathon not found, or reimbursements are disabled"),
+        (status = 409, description = "User already has a request, or the hackathon is archived"),
+        (status = 422, description = "Unknown region, amount above the region's cap, or description too long")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn create_reimbursement(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<CreateReimbursementRequest>,
+) -> Result<Json<ReimbursementInfo>, AppError> {
+    if !role.is_participant() {
+        return Err(AppError::Forbidden);
+    }
+
+    let reimbursements = require_reimbursements(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    check_amount(&reimbursements, &req.region, req.amount_cents)?;
+    let description = clean_text(req.description);
+
+    let existing = ReimbursementRequests::find()
+        .filter(reimbursement_requests::Column::HackathonId.eq(role.hackathon_id))
+        .filter(reimbursement_requests::Column::UserId.eq(role.user_id))
+        .one(&state.db)
+        .await?;
+
+    // A withdrawn request is replaced rather than kept, since each user has a single request
+    let request = match existing {
+        Some(existing) if existing.status != "withdrawn" => {
+            return Err(AppError::Conflict(
+                "You already have a reimbursement request",
+            ));
+        }
+        Some(existing) => {
+            let mut active: reimbursement_requests::ActiveModel = existing.into();
+            active.region = Set(req.region);
+            active.description = Set(description);
+            active.amount_requested_cents = Set(req.amount_cents);
+            active.status = Set("submitted".to_string());
+            active.updated_at = Set(Utc::now().naive_utc());
+            active.update(&state.db).await
+        }
+        None => {
+            reimbursement_requests::ActiveModel {
+                hackathon_id: Set(role.hackathon_id),
+                user_id: Set(role.user_id),
+                region: Set(req.region),
+                description: Set(description),
+                amount_requested_cents: Set(req.amount_cents),
+                status: Set("submitted".to_string()),
+                ..Default::default()
+            }
+            .insert(&state.db)
+            .await
+        }
+    }?;
+
+    Ok(Json(reimbursement_info(&state, request).await?))
+}
+
+/// Get the current user's reimbursement request
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/reimbursements/me",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "The user's request", body = ReimbursementInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "No request, or reimbursements are disabled")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn get_my_reimbursement(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<ReimbursementInfo>, AppError> {
+    require_reimbursements(&state, &role).await?;
+
+    let request = ReimbursementRequests::find()
+        .filter(reimbursement_requests::Column::HackathonId.eq(role.hackathon_id))
+        .filter(reimbursement_requests::Column::UserId.eq(role.user_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("You have no reimbursement request"))?;
+
+    Ok(Json(reimbursement_info(&state, request).await?))
+}
+
+const LIST_SORT: Sort<reimbursement_requests::Column> = Sort {
+    fields: &[
+        ("created_at", reimbursement_requests::Column::CreatedAt),
+        ("updated_at", reimbursement_requests::Column::UpdatedAt),
+        (
+            "amount_requested_cents",
+            reimbursement_requests::Column::AmountRequestedCents,
+        ),
+    ],
+    default: "created_at",
+    id: reimbursement_requests::Column::Id,
+};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListReimbursementsQuery {
+    /// Only return requests with this status
+    status: Option<String>,
+    /// Only return requests from this region
+    region: Option<String>,
+}
+
+/// List a hackathon's reimbursement requests
+///
+/// Can be sorted by `created_at`, `updated_at` or `amount_requested_cents`.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/reimbursements",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ListReimbursementsQuery,
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Requests, oldest first by default", body = Page<ReimbursementInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "No access to this hackathon's finances"),
+        (status = 404, description = "Hackathon not found, or reimbursements are disabled"),
+        (status = 422, description = "Unknown status, or invalid page, sort or cursor")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn list_reimbursements(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ListReimbursementsQuery>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<ReimbursementInfo>>, AppError> {
+    require_finance(&state, &role).await?;
+
+    let mut select = ReimbursementRequests::find()
+        .filter(reimbursement_requests::Column::HackathonId.eq(role.hackathon_id));
+
+    if let Some(status) = query.status {
+        if !REIMBURSEMENT_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::invalid("status", "Unknown reimbursement status"));
+        }
+        select = select.filter(reimbursement_requests::Column::Status.eq(status));
+    }
+
+    if let Some(region) = query.region {
+        select = select.filter(reimbursement_requests::Column::Region.eq(region));
+    }
+
+    let mut page = pagination::paginate(&state.db, select, &page, &LIST_SORT, |r| r).await?;
+    let requests = std::mem::take(&mut page.items);
+
+    Ok(Json(
+        page.with_items(reimbursement_infos(&state, requests).await?),
+    ))
+}
+
+/// Get a reimbursement request
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/reimbursements/{request_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("request_id" = i32, Path, description = "Request ID")
+    ),
+    responses(
+        (status = 200, description = "The request", body = ReimbursementInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "Request not found, or reimbursements are disabled")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn get_reimbursement(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<ReimbursementInfo>, AppError> {
+    let (_, request_id) = params;
+    require_reimbursements(&state, &role).await?;
+
+    let request = find_request(&state, &role, request_id).await?;
+    Ok(Json(reimbursement_info(&state, request).await?))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateReimbursementRequest {
+    pub region: Option<String>,
+    pub amount_cents: Option<i64>,
+    /// An empty string clears the description
+    pub description: Option<String>,
+}
+
+impl Validate for UpdateReimbursementRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(amount_cents) = self.amount_cents {
+            v.at_least("amount_cents", amount_cents, 1);
+        }
+        v.max_length(
+            "description",
+            self.description.as_deref().map(str::trim),
+            MAX_TEXT_LENGTH,
+        );
+    }
+}
+
+/// Update the current user's request before it is reviewed
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}/reimbursements/{request_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("request_id" = i32, Path, description = "Request ID")
+    ),
+    request_body = UpdateReimbursementRequest,
+    responses(
+        (status = 200, description = "Request updated", body = ReimbursementInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the user's own request"),
+        (status = 404, description = "Request not found, or reimbursements are disabled"),
+        (status = 409, description = "Request was already reviewed or withdrawn, or the hackathon is archived"),
+        (status = 422, description = "Unknown region, amount above the region's cap, or description too long")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn update_reimbursement(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<UpdateReimbursementRequest>,
+) -> Result<Json<ReimbursementInfo>, AppError> {
+    let (_, request_id) = params;
+    let reimbursements = require_reimbursements(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let request = find_own_pending_request(&state, &role, request_id).await?;
+
+    let region = req.region.unwrap_or_else(|| request.region.clone());
+    let amount_cents = req.amount_cents.unwrap_or(request.amount_requested_cents);
+    check_amount(&reimbursements, &region, amount_cents)?;
+
+    let mut active = reimbursement_requests::ActiveModel {
+        region: Set(region),
+        amount_requested_cents: Set(amount_cents),
+        ..Default::default()
+    };
+    if req.description.is_some() {
+        active.description = Set(clean_text(req.description));
+    }
+
+    let request = update_pending(&state.db, request.id, active).await?;
+
+    Ok(Json(reimbursement_info(&state, request).await?))
+}
+
+/// Withdraw the current user's request before it is reviewed
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/reimbursements/{request_id}/withdraw",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("request_id" = i32, Path, description = "Request ID")
+    ),
+    responses(
+        (status = 200, description = "Request withdrawn", body = ReimbursementInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the user's own request"),
+        (status = 404, description = "Request not found, or reimbursements are disabled"),
+        (status = 409, description = "Request was already reviewed or withdrawn, or the hackathon is archived")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn withdraw_reimbursement(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<ReimbursementInfo>, AppError> {
+    let (_, request_id) = params;
+    require_reimbursements(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let request = find_own_pending_request(&state, &role, request_id).await?;
+
+    let active = reimbursement_requests::ActiveModel {
+        status: Set("withdrawn".to_string()),
+        ..Default::default()
+    };
+    let request = update_pending(&state.db, request.id, active).await?;
+
+    Ok(Json(reimbursement_info(&state, request).await?))
+}
+
+/// Attach a receipt to the current user's request
+///
+/// The body is `multipart/form-data` with a PDF, PNG or JPEG in a `file` field.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/reimbursements/{request_id}/receipts",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("request_id" = i32, Path, description = "Request ID")
+    ),
+    request_body(content_type = "multipart/form-data", description = "Receipt in a `file` field"),
+    responses(
+        (status = 200, description = "Receipt uploaded", body = ReceiptInfo),
+        (status = 400, description = "No file, or the request already has the most receipts allowed"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the user's own request"),
+        (status = 404, description = "Request not found, or reimbursements are disabled"),
+        (status = 409, description = "Request was already reviewed or withdrawn, or the hackathon is archived"),
+        (status = 413, description = "Receipt is larger than 10 MiB"),
+        (status = 415, description = "Receipt is not a PDF, PNG or JPEG"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn upload_receipt(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    mut multipart: Multipart,
+) -> Result<Json<ReceiptInfo>, AppError> {
+    let (_, request_id) = params;
+    require_reimbursements(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let request = find_own_pending_request(&state, &role, request_id).await?;
+
+    let mut upload = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| AppError::BadRequest("Invalid multipart upload"))?
+    {
+        if field.name() == Some("file") {
+            let file_name = field.file_name().unwrap_or("receipt").to_string();
+            let content = field.bytes().await.map_err(|_| AppError::PayloadTooLarge)?;
+            upload = Some((file_name, content));
+        }
+    }
+
+    let (file_name, content) = upload.ok_or(AppError::BadRequest("Missing file field"))?;
+    if content.len() > MAX_RECEIPT_SIZE {
+        return Err(AppError::PayloadTooLarge);
+    }
+    let content_type = receipt_content_type(&content).ok_or(AppError::UnsupportedMediaType(
+        "Receipts must be PDF, PNG or JPEG files",
+    ))?;
+
+    let receipts = ReimbursementReceipts::find()
+        .filter(reimbursement_receipts::Column::RequestId.eq(request.id))
+        .all(&state.db)
+        .await?;
+
+    if receipts.len() >= MAX_RECEIPTS {
+        return Err(AppError::BadRequest(
+            "This request already has the maximum number of receipts",
+        ));
+    }
+
+    let key = format!(
+        "receipts/{}/{}",
+        request.id,
+        hex::encode(rand::random::<[u8; 16]>())
+    );
+    let size = content.len() as i32;
+
+    state
+        .storage()?
+        .put(&key, content, content_type)
+        .await
+        .map_err(|e| {
+            AppError::internal(format_args!(
+                "Failed to store receipt for request {}: {e}",
+                request.id
+            ))
+        })?;
+
+    let receipt = reimbursement_receipts::ActiveModel {
+        request_id: Set(request.id),
+        storage_key: Set(key),
+        file_name: Set(sanitize_file_name(&file_name)),
+        content_type: Set(content_type.to_string()),
+        size: Set(size),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok(Json(receipt.into()))
+}
+
+async fn find_receipt(
+    state: &AppState,
+    request: &reimbursement_requests::Model,
+    receipt_id: i32,
+) -> Result<reimbursement_receipts::Model, AppError> {
+    ReimbursementReceipts::find_by_id(receipt_id)
+        .filter(reimbursement_receipts::Column::RequestId.eq(request.id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Receipt not found"))
+}
+
+/// Download a receipt
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/reimbursements/{request_id}/receipts/{receipt_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("request_id" = i32, Path, description = "Request ID"),
+        ("receipt_id" = i32, Path, description = "Receipt ID")
+    ),
+    responses(
+        (status = 200, description = "The receipt file"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "Receipt not found, or reimbursements are disabled"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn download_receipt(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32, i32)>,
+) -> Result<Response, AppError> {
+    let (_, request_id, receipt_id) = params;
+    require_reimbursements(&state, &role).await?;
+
+    let request = find_request(&state, &role, request_id).await?;
+    let receipt = find_receipt(&state, &request, receipt_id).await?;
+
+    let content = state
+        .storage()?
+        .get(&receipt.storage_key)
+        .await
+        .map_err(|e| {
+            AppError::internal(format_args!(
+                "Failed to read {} from storage: {e}",
+                receipt.storage_key
+            ))
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, receipt.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", receipt.file_name),
+            ),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+/// Remove a receipt from the current user's request
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/reimbursements/{request_id}/receipts/{receipt_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("request_id" = i32, Path, description = "Request ID"),
+        ("receipt_id" = i32, Path, description = "Receipt ID")
+    ),
+    responses(
+        (status = 204, description = "Receipt deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the user's own request"),
+        (status = 404, description = "Receipt not found, or reimbursements are disabled"),
+        (status = 409, description = "Request was already reviewed or withdrawn, or the hackathon is archived"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn delete_receipt(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, request_id, receipt_id) = params;
+    require_reimbursements(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let request = find_own_pending_request(&state, &role, request_id).await?;
+    let receipt = find_receipt(&state, &request, receipt_id).await?;
+    let storage = state.storage()?;
+
+    ReimbursementReceipts::delete_by_id(receipt.id)
+        .exec(&state.db)
+        .await?;
+
+    // The database row is what makes a receipt visible, so a leftover file is harmless
+    if let Err(e) = storage.delete(&receipt.storage_key).await {
+        tracing::warn!(
+            "Failed to delete {} from storage: {}",
+            receipt.storage_key,
+            e
+        );
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    /// Reimburse the full amount requested
+    Approve,
+    /// Reimburse part of the amount requested
+    Partial,
+    Deny,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ReviewReimbursementRequest {
+    pub decision: ReviewDecision,
+    /// Amount to reimburse for partial approvals, less than the amount requested
+    pub amount_cents: Option<i64>,
+    /// Shown to the requester
+    pub notes: Option<String>,
+}
+
+impl Validate for ReviewReimbursementRequest {
+    fn validate(&self, v: &mut Validator) {
+        match self.amount_cents {
+            Some(amount_cents) => v.at_least("amount_cents", amount_cents, 1),
+            None if matches!(self.decision, ReviewDecision::Partial) => {
+                v.error("amount_cents", "Required for a partial approval")
+            }
+            None => {}
+        }
+        v.max_length(
+            "notes",
+            self.notes.as_deref().map(str::trim),
+            MAX_TEXT_LENGTH,
+        );
+    }
+}
+
+/// Approve, partially approve or deny a reimbursement request
+///
+/// Reviewed requests can be reviewed again to change the decision. Approvals cannot take the
+/// total approved above the hackathon's budget.
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/reimbursements/{request_id}/review",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("request_id" = i32, Path, description = "Request ID")
+    ),
+    request_body = ReviewReimbursementRequest,
+    responses(
+        (status = 200, description = "Request reviewed", body = ReimbursementInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "No access to this hackathon's finances"),
+        (status = 404, description = "Request not found, or reimbursements are disabled"),
+        (status = 409, description = "Request was withdrawn, the budget would be exceeded, or the hackathon is archived"),
+        (status = 422, description = "Missing or invalid amount for a partial approval, or notes too long")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn review_reimbursement(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<ReviewReimbursementRequest>,
+) -> Result<Json<ReimbursementInfo>, AppError> {
+    let (_, request_id) = params;
+    let reimbursements = require_finance(&state, &role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let txn = state.db.begin().await?;
+
+    // Locking every request in the hackathon makes concurrent reviews wait for each other, so
+    // each one checks the budget against the approvals made before it
+    let requests = ReimbursementRequests::find()
+        .filter(reimbursement_requests::Column::HackathonId.eq(role.hackathon_id))
+        .lock_exclusive()
+        .all(&txn)
+        .await?;
+
+    let request = requests
+        .iter()
+        .find(|r| r.id == request_id)
+        .cloned()
+        .ok_or(AppError::NotFound("Reimbursement request not found"))?;
+    if request.status == "withdrawn" {
+        return Err(AppError::Conflict("The request was withdrawn"));
+    }
+
+    let (status, amount) = match req.decision {
+        ReviewDecision::Approve => ("approved", Some(request.amount_requested_cents)),
+        ReviewDecision::Partial => {
+            let amount = req.amount_cents.ok_or_else(|| {
+                AppError::invalid("amount_cents", "Required for a partial approval")
+            })?;
+            if amount <= 0 || amount >= request.amount_requested_cents {
+                return Err(AppError::invalid(
+                    "amount_cents",
+                    "Must be positive and less than the requested amount",
+                ));
+            }
+            ("partially_approved", Some(amount))
+        }
+        ReviewDecision::Deny => ("denied", None),
+    };
+    let notes = clean_text(req.notes);
+
+    if let (Some(budget), Some(amount)) = (reimbursements.budget_cents, amount) {
+        let approved_elsewhere: i64 = requests
+            .iter()
+            .filter(|r| r.id != request.id && PAYOUT_STATUSES.contains(&r.status.as_str()))
+            .filter_map(|r| r.amount_approved_cents)
+            .sum();
+
+        if approved_elsewhere + amount > budget {
+            return Err(AppError::Conflict(
+                "This would exceed the reimbursement budget",
+            ));
+        }
+    }
+
+    let before = request.clone();
+    let now = Utc::now().naive_utc();
+    let mut active: reimbursement_requests::ActiveModel = request.into();
+    active.status = Set(status.to_string());
+    active.amount_approved_cents = Set(amount);
+    active.review_notes = Set(notes);
+    active.reviewed_by = Set(Some(role.user_id));
+    active.reviewed_at = Set(Some(now));
+    active.updated_at = Set(now);
+
+    let request = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        Entry {
+            diff: audit::diff(&before, &request),
+            ..Entry::in_hackathon(&role, "reimbursement.review", "reimbursement", request.id)
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        "User {} marked reimbursement {} as {} in {}",
+        role.user_id,
+        request.id,
+        request.status,
+        role.slug
+    );
+
+    Ok(Json(reimbursement_info(&state, request).await?))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RegionSummary {
+    pub name: String,
+    pub cap_cents: i64,
+    /// Requests awaiting review
+    pub pending: u64,
+    pub approved_cents: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReimbursementSummary {
+    pub budget_cents: Option<i64>,
+    /// Total of approved and partially approved requests
+    pub approved_cents: i64,
+    /// Budget left after approved requests, if the hackathon has a budget
+    pub remaining_cents: Option<i64>,
+    /// Total requested by requests awaiting review
+    pub pending_cents: i64,
+    /// Counts by status
+    pub statuses: BTreeMap<String, u64>,
+    pub regions: Vec<RegionSummary>,
+}
+
+/// Get the budget and how much of it has been approved
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/reimbursements/summary",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Reimbursement totals", body = ReimbursementSummary),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "No access to this hackathon's finances"),
+        (status = 404, description = "Hackathon not found, or reimbursements are disabled")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn get_reimbursement_summary(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<ReimbursementSummary>, AppError> {
+    let reimbursements = require_finance(&state, &role).await?;
+
+    let requests = ReimbursementRequests::find()
+        .filter(reimbursement_requests::Column::HackathonId.eq(role.hackathon_id))
+        .all(&state.db)
+        .await?;
+
+    let approved = |region: Option<&str>| -> i64 {
+        requests
+            .iter()
+            .filter(|r| PAYOUT_STATUSES.contains(&r.status.as_str()))
+            .filter(|r| region.is_none_or(|name| r.region == name))
+            .filter_map(|r| r.amount_approved_cents)
+            .sum()
+    };
+
+    let mut statuses = BTreeMap::new();
+    for request in &requests {
+        *statuses.entry(request.status.clone()).or_insert(0) += 1;
+    }
+
+    let approved_cents = approved(None);
+
+    Ok(Json(ReimbursementSummary {
+        budget_cents: reimbursements.budget_cents,
+        approved_cents,
+        remaining_cents: reimbursements.budget_cents.map(|b| b - approved_cents),
+        pending_cents: requests
+            .iter()
+            .filter(|r| r.status == "submitted")
+            .map(|r| r.amount_requested_cents)
+            .sum(),
+        statuses,
+        regions: reimbursements
+            .regions
+            .iter()
+            .map(|region| RegionSummary {
+                name: region.name.clone(),
+                cap_cents: region.cap_cents,
+                pending: requests
+                    .iter()
+                    .filter(|r| r.region == region.name && r.status == "submitted")
+                    .count() as u64,
+                approved_cents: approved(Some(&region.name)),
+            })
+            .collect(),
+    }))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportPayoutsQuery {
+    /// `csv` (default) or `xlsx`
+    #[serde(default)]
+    #[param(inline)]
+    format: ExportFormat,
+}
+
+/// Export approved payouts
+///
+/// The file is streamed while it is being generated, so a failure part way through ends the
+/// download early instead of returning an error status.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/reimbursements/export",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ExportPayoutsQuery
+    ),
+    responses(
+        (status = 200, description = "Approved payouts as CSV or XLSX", content(
+            ("text/csv"),
+            ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "No access to this hackathon's finances"),
+        (status = 404, description = "Hackathon not found, or reimbursements are disabled")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn export_payouts(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ExportPayoutsQuery>,
+) -> Result<Response, AppError> {
+    require_finance(&state, &role).await?;
+
+    let db = state.db.clone();
+    let hackathon_id = role.hackathon_id;
+
+    Ok(writer::download(
+        &format!("{}-payouts", role.slug),
+        query.format,
+        &PAYOUT_COLUMNS,
+        move |rows| write_payouts(db, hackathon_id, rows),
+    ))
+}
+
+async fn write_payouts(
+    db: DatabaseConnection,
+    hackathon_id: i32,
+    rows: Rows,
+) -> Result<(), AppError> {
+    let requests = ReimbursementRequests::find()
+        .filter(reimbursement_requests::Column::HackathonId.eq(hackathon_id))
+        .filter(reimbursement_requests::Column::Status.is_in(PAYOUT_STATUSES))
+        .order_by_asc(reimbursement_requests::Column::ReviewedAt)
+        .all(&db)
+        .await?;
+
+    let dollars = |cents: i64| format!("{}.{:02}", cents / 100, cents % 100);
+
+    for batch in requests.chunks(EXPORT_BATCH_SIZE) {
+        let users: HashMap<i32, users::Model> = Users::find()
+            .filter(users::Column::Id.is_in(batch.iter().map(|r| r.user_id)))
+            .all(&db)
+            .await?
+            .into_iter()
+            .map(|u| (u.id, u))
+            .collect();
+
+        for request in batch {
+            let (name, email) = users
+                .get(&request.user_id)
+                .map(|u| (u.name.clone().unwrap_or_default(), u.email.clone()))
+                .unwrap_or_default();
+
+            rows.send(vec![
+                name,
+                email,
+                request.region.clone(),
+                request.status.clone(),
+                dollars(request.amount_requested_cents),
+                dollars(request.amount_approved_cents.unwrap_or(0)),
+                request
+                    .reviewed_at
+                    .map(|t| t.to_string())
+                    .unwrap_or_default(),
+            ])
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct FinanceMember {
+    pub user_id: i32,
+    pub email: String,
+    pub name: Option<String>,
+}
+
+/// List the organizers with access to the hackathon's finances
+///
+/// Admins always have access and are not listed.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/reimbursements/finance",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Organizers with finance access", body = Vec<FinanceMember>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "Hackathon not found, or reimbursements are disabled")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn list_finance_members(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<FinanceMember>>, AppError> {
+    if !role.is_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_reimbursements(&state, &role).await?;
+
+    let members = Users::find()
+        .inner_join(UserHackathonRoles)
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::Role.eq("organizer"))
+        .filter(user_hackathon_roles::Column::Finance.eq(true))
+        .order_by_asc(users::Column::Email)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        members
+            .into_iter()
+            .map(|u| FinanceMember {
+                user_id: u.id,
+                email: u.email,
+                name: u.name,
+            })
+            .collect(),
+    ))
+}
+
+/// Give or take away an organizer's access to the hackathon's finances
+async fn set_finance(
+    state: &AppState,
+    role: &HackathonRole,
+    user_id: i32,
+    finance: bool,
+) -> Result<(), AppError> {
+    if !role.is_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_reimbursements(state, role).await?;
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+
+    let organizer = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.eq(user_id))
+        .filter(user_hackathon_roles::Column::Role.eq("organizer"))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound(
+            "User is not an organizer of this hackathon",
+        ))?;
+
+    let action = if finance {
+        "finance.grant"
+    } else {
+        "finance.revoke"
+    };
+
+    let txn = state.db.begin().await?;
+    let mut active: user_hackathon_roles::ActiveModel = organizer.into();
+    active.finance = Set(finance);
+    active.update(&txn).await?;
+    audit::record(&txn, Entry::in_hackathon(role, action, "user", user_id)).await?;
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Give an organizer access to the hackathon's finances
+#[utoipa::path(
+    put,
+    path = "/hackathons/{slug}/reimbursements/finance/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Finance access granted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "User is not an organizer, or reimbursements are disabled"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn grant_finance(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, user_id) = params;
+    set_finance(&state, &role, user_id, true).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Take away an organizer's access to the hackathon's finances
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/reimbursements/finance/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Finance access revoked"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin of this hackathon"),
+        (status = 404, description = "User is not an organizer, or reimbursements are disabled"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Reimbursements"
+)]
+pub async fn revoke_finance(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, user_id) = params;
+    set_finance(&state, &role, user_id, false).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hackathons::settings::ReimbursementRegion;
+
+    #[test]
+    fn receipts_are_recognized_by_their_magic_bytes() {
+        assert_eq!(receipt_content_type(b"%PDF-1.7\n"), Some("application/pdf"));
+        assert_eq!(
+            receipt_content_type(b"\x89PNG\r\n\x1a\n\0\0"),
+            Some("image/png")
+        );
+        assert_eq!(
+            receipt_content_type(b"\xff\xd8\xff\xe0"),
+            Some("image/jpeg")
+        );
+        assert_eq!(receipt_content_type(b"GIF89a"), None);
+        assert_eq!(receipt_content_type(b""), None);
+    }
+
+    /// The field rejected by [`check_amount`], if any
+    fn amount_error(region: &str, amount_cents: i64) -> Option<String> {
+        let reimbursements = Reimbursements {
+            budget_cents: None,
+            regions: vec![ReimbursementRegion {
+                name: "Domestic".to_string(),
+                cap_cents: 30_000,
+            }],
+        };
+
+        match check_amount(&reimbursements, region, amount_cents) {
+            Ok(()) => None,
+            Err(AppError::Validation(fields)) => Some(fields[0].field.clone()),
+            Err(e) => panic!("unexpected error {e:?}"),
+        }
+    }
+
+    #[test]
+    fn amounts_must_be_positive_and_within_the_cap() {
+        assert_eq!(amount_error("Domestic", 1), None);
+        assert_eq!(amount_error("Domestic", 30_000), None);
+        assert_eq!(amount_error("Domestic", 0).as_deref(), Some("amount_cents"));
+        assert_eq!(
+            amount_error("Domestic", 30_001).as_deref(),
+            Some("amount_cents")
+        );
+        assert_eq!(amount_error("Overseas", 100).as_deref(), Some("region"));
+    }
+}
@@ -0,0 +1,661 @@
+use std::io::Write;
+
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Multipart, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, EntityTrait, JoinType, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, SelectTwo,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{sync::mpsc, task::JoinSet};
+use utoipa::{IntoParams, ToSchema};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    AppState,
+    analytics::handlers::users_with_roles,
+    auth::extractors::{AuthUser, HackathonRole},
+    entities::{prelude::*, resume_access_logs, user_hackathon_roles, user_profiles, users},
+    error::AppError,
+    exports::writer::BodyWriter,
+    hackathons::handlers::require_feature,
+    pagination::{self, Page, PageParams, Sort, contains_ignoring_case},
+    profiles::handlers::ProfileInfo,
+    sponsors::handlers::has_sponsor_benefit,
+    storage::{self, Storage},
+    validation::{Path, Query},
+};
+
+/// Largest resume that can be uploaded
+pub const MAX_RESUME_SIZE: usize = 5 * 1024 * 1024;
+
+/// Request body limit for uploads, leaving room for the multipart framing around the file
+pub const UPLOAD_BODY_LIMIT: usize = MAX_RESUME_SIZE + 64 * 1024;
+
+/// Resumes read from storage at once for the resume book, which bounds its memory use
+const CONCURRENT_READS: usize = 4;
+
+fn resume_key(user_id: i32) -> String {
+    format!("resumes/{user_id}.pdf")
+}
+
+/// A safe file name for a resume, which always ends in `.pdf`
+fn sanitize_file_name(name: &str) -> String {
+    let name = storage::sanitize_file_name(name);
+
+    if name.to_lowercase().ends_with(".pdf") {
+        name
+    } else {
+        format!("{name}.pdf")
+    }
+}
+
+fn pdf_response(file_name: &str, content: Bytes) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", sanitize_file_name(file_name)),
+            ),
+        ],
+        content,
+    )
+        .into_response()
+}
+
+/// Upload a resume for the current user, replacing any previous one
+///
+/// The body is `multipart/form-data` with the PDF in a `file` field.
+#[utoipa::path(
+    put,
+    path = "/profile/resume",
+    request_body(content_type = "multipart/form-data", description = "PDF in a `file` field"),
+    responses(
+        (status = 200, description = "Resume uploaded", body = ProfileInfo),
+        (status = 400, description = "No file in the request"),
+        (status = 401, description = "Not authenticated"),
+        (status = 413, description = "Resume is larger than 5 MB"),
+        (status = 415, description = "Resume is not a PDF"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Resumes"
+)]
+pub async fn upload_resume(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<ProfileInfo>, AppError> {
+    let mut upload = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| AppError::BadRequest("Invalid multipart upload"))?
+    {
+        if field.name() == Some("file") {
+            let file_name = field.file_name().unwrap_or("resume.pdf").to_string();
+            let content = field.bytes().await.map_err(|_| AppError::PayloadTooLarge)?;
+            upload = Some((file_name, content));
+        }
+    }
+
+    let (file_name, content) = upload.ok_or(AppError::BadRequest("Missing file field"))?;
+    if content.len() > MAX_RESUME_SIZE {
+        return Err(AppError::PayloadTooLarge);
+    }
+    if !content.starts_with(b"%PDF-") {
+        return Err(AppError::UnsupportedMediaType("Resumes must be PDF files"));
+    }
+
+    let key = resume_key(auth.user.id);
+    state
+        .storage()?
+        .put(&key, content, "application/pdf")
+        .await
+        .map_err(|e| {
+            AppError::internal(format_args!(
+                "Failed to store resume for user {}: {e}",
+                auth.user.id
+            ))
+        })?;
+
+    let existing = UserProfiles::find_by_id(auth.user.id)
+        .one(&state.db)
+        .await?;
+
+    let now = Utc::now().naive_utc();
+    let profile = match existing {
+        Some(profile) => {
+            let mut profile: user_profiles::ActiveModel = profile.into();
+            profile.resume_key = Set(Some(key));
+            profile.resume_file_name = Set(Some(sanitize_file_name(&file_name)));
+            profile.resume_uploaded_at = Set(Some(now));
+            profile.updated_at = Set(now);
+            profile.update(&state.db).await
+        }
+        None => {
+            user_profiles::ActiveModel {
+                user_id: Set(auth.user.id),
+                resume_key: Set(Some(key)),
+                resume_file_name: Set(Some(sanitize_file_name(&file_name))),
+                resume_uploaded_at: Set(Some(now)),
+                ..Default::default()
+            }
+            .insert(&state.db)
+            .await
+        }
+    }?;
+
+    Ok(Json(ProfileInfo::new(auth.user, Some(profile))))
+}
+
+async fn find_resume(
+    state: &AppState,
+    user_id: i32,
+) -> Result<(user_profiles::Model, String), AppError> {
+    let profile = UserProfiles::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("No resume uploaded"))?;
+
+    let key = profile
+        .resume_key
+        .clone()
+        .ok_or(AppError::NotFound("No resume uploaded"))?;
+    Ok((profile, key))
+}
+
+async fn download(state: &AppState, key: &str) -> Result<Bytes, AppError> {
+    state
+        .storage()?
+        .get(key)
+        .await
+        .map_err(|e| AppError::internal(format_args!("Failed to read {key} from storage: {e}")))
+}
+
+/// Download the current user's resume
+#[utoipa::path(
+    get,
+    path = "/profile/resume",
+    responses(
+        (status = 200, description = "The resume", content_type = "application/pdf"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "No resume uploaded"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Resumes"
+)]
+pub async fn get_resume(
+    auth: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let (profile, key) = find_resume(&state, auth.user.id).await?;
+    let content = download(&state, &key).await?;
+
+    Ok(pdf_response(
+        profile.resume_file_name.as_deref().unwrap_or("resume.pdf"),
+        content,
+    ))
+}
+
+/// Delete the current user's resume
+#[utoipa::path(
+    delete,
+    path = "/profile/resume",
+    responses(
+        (status = 204, description = "Resume deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "No resume uploaded"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Resumes"
+)]
+pub async fn delete_resume(
+    auth: AuthUser,
+    State(state): State<AppState>,
+) -> Result<StatusCode, AppError> {
+    let (profile, key) = find_resume(&state, auth.user.id).await?;
+
+    state.storage()?.delete(&key).await.map_err(|e| {
+        AppError::internal(format_args!("Failed to delete {key} from storage: {e}"))
+    })?;
+
+    let mut profile: user_profiles::ActiveModel = profile.into();
+    profile.resume_key = Set(None);
+    profile.resume_file_name = Set(None);
+    profile.resume_uploaded_at = Set(None);
+    profile.updated_at = Set(Utc::now().naive_utc());
+    profile.update(&state.db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ResumeBookQuery {
+    /// Matches part of a name, school or major, ignoring case
+    search: Option<String>,
+    /// Matches part of the school name, ignoring case
+    school: Option<String>,
+    /// Matches part of the major, ignoring case
+    major: Option<String>,
+    graduation_year: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ResumeBookEntry {
+    pub user_id: i32,
+    pub name: Option<String>,
+    pub email: String,
+    pub school: Option<String>,
+    pub major: Option<String>,
+    pub graduation_year: Option<i32>,
+    pub github: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+    pub resume_file_name: Option<String>,
+    pub resume_uploaded_at: Option<NaiveDateTime>,
+}
+
+/// Consenting participants of the hackathon with a resume, matching the query
+fn resume_book(hackathon_id: i32, query: &ResumeBookQuery) -> SelectTwo<UserProfiles, Users> {
+    let mut select = UserProfiles::find()
+        .find_also_related(Users)
+        .filter(
+            user_profiles::Column::UserId
+                .in_subquery(users_with_roles(hackathon_id, &["participant"])),
+        )
+        .filter(user_profiles::Column::ResumeSponsorConsent.eq(true))
+        .filter(user_profiles::Column::ResumeKey.is_not_null());
+
+    if let Some(search) = query.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        let search = search.trim();
+        select = select.filter(
+            Condition::any()
+                .add(contains_ignoring_case(users::Column::Name, search))
+                .add(contains_ignoring_case(
+                    user_profiles::Column::School,
+                    search,
+                ))
+                .add(contains_ignoring_case(user_profiles::Column::Major, search)),
+        );
+    }
+    if let Some(school) = query.school.as_deref().filter(|s| !s.trim().is_empty()) {
+        select = select.filter(contains_ignoring_case(
+            user_profiles::Column::School,
+            school.trim(),
+        ));
+    }
+    if let Some(major) = query.major.as_deref().filter(|s| !s.trim().is_empty()) {
+        select = select.filter(contains_ignoring_case(
+            user_profiles::Column::Major,
+            major.trim(),
+        ));
+    }
+    if let Some(year) = query.graduation_year {
+        select = select.filter(user_profiles::Column::GraduationYear.eq(year));
+    }
+
+    select
+}
+
+fn book_entry(profile: user_profiles::Model, user: users::Model) -> ResumeBookEntry {
+    ResumeBookEntry {
+        user_id: user.id,
+        name: user.name,
+        email: user.email,
+        school: profile.school,
+        major: profile.major,
+        graduation_year: profile.graduation_year,
+        github: profile.github,
+        linkedin: profile.linkedin,
+        website: profile.website,
+        resume_file_name: profile.resume_file_name,
+        resume_uploaded_at: profile.resume_uploaded_at,
+    }
+}
+
+/// Check the caller can use the resume book, which organizers can and sponsors can if their
+/// tier includes it
+async fn require_resume_book(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    if !role.is_sponsor() {
+        return Err(AppError::Forbidden);
+    }
+
+    require_feature(state, role.hackathon_id, |f| f.resume_book).await?;
+
+    if !has_sponsor_benefit(state, role, |tier| tier.resume_book).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(())
+}
+
+/// Record which participants' details a sponsor accessed
+async fn log_access(
+    state: &AppState,
+    role: &HackathonRole,
+    action: &str,
+    participant_ids: Vec<i32>,
+) -> Result<(), AppError> {
+    if participant_ids.is_empty() {
+        return Ok(());
+    }
+
+    let logs = participant_ids
+        .into_iter()
+        .map(|participant_id| resume_access_logs::ActiveModel {
+            hackathon_id: Set(role.hackathon_id),
+            user_id: Set(role.user_id),
+            participant_id: Set(Some(participant_id)),
+            action: Set(action.to_string()),
+            ..Default::default()
+        });
+
+    ResumeAccessLogs::insert_many(logs).exec(&state.db).await?;
+
+    Ok(())
+}
+
+const BOOK_SORT: Sort<user_profiles::Column> = Sort {
+    fields: &[
+        ("created_at", user_profiles::Column::CreatedAt),
+        ("updated_at", user_profiles::Column::UpdatedAt),
+    ],
+    default: "-updated_at",
+    id: user_profiles::Column::UserId,
+};
+
+/// Search the resume book
+///
+/// Lists participants who uploaded a resume and agreed to share it with sponsors, most
+/// recently updated profiles first. Can be sorted by `created_at` or `updated_at`.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/resumes",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ResumeBookQuery,
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Matching resumes", body = Page<ResumeBookEntry>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor of this hackathon, or tier has no resume book access"),
+        (status = 404, description = "Hackathon not found, or the resume book is disabled"),
+        (status = 422, description = "Invalid page, sort or cursor")
+    ),
+    tag = "Resumes"
+)]
+pub async fn list_resumes(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ResumeBookQuery>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<ResumeBookEntry>>, AppError> {
+    require_resume_book(&state, &role).await?;
+
+    let select = resume_book(role.hackathon_id, &query);
+    let page =
+        pagination::paginate(&state.db, select, &page, &BOOK_SORT, |(profile, _)| profile).await?;
+    let page = page.map(|rows| {
+        rows.into_iter()
+            .filter_map(|(profile, user)| Some(book_entry(profile, user?)))
+            .collect()
+    });
+
+    // Every participant listed has had their details seen
+    let participant_ids = page.items.iter().map(|entry| entry.user_id).collect();
+    log_access(&state, &role, "search", participant_ids).await?;
+
+    Ok(Json(page))
+}
+
+/// Download one participant's resume from the resume book
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/resumes/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("user_id" = i32, Path, description = "Participant user ID")
+    ),
+    responses(
+        (status = 200, description = "The resume", content_type = "application/pdf"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor of this hackathon, or tier has no resume book access"),
+        (status = 404, description = "Resume not in the resume book, or the resume book is disabled"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Resumes"
+)]
+pub async fn download_resume(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Response, AppError> {
+    let (_, user_id) = params;
+    require_resume_book(&state, &role).await?;
+
+    let is_participant = UserHackathonRoles::find()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.eq(user_id))
+        .filter(user_hackathon_roles::Column::Role.eq("participant"))
+        .one(&state.db)
+        .await?
+        .is_some();
+
+    let (profile, key) = find_resume(&state, user_id).await?;
+    if !is_participant || !profile.resume_sponsor_consent {
+        return Err(AppError::NotFound("No resume shared by this participant"));
+    }
+
+    let content = download(&state, &key).await?;
+    log_access(&state, &role, "download", vec![user_id]).await?;
+
+    Ok(pdf_response(
+        profile.resume_file_name.as_deref().unwrap_or("resume.pdf"),
+        content,
+    ))
+}
+
+/// Download every resume matching the search as a ZIP archive
+///
+/// The archive is streamed while resumes are read, so a failure part way through ends the
+/// download early instead of returning an error status.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/resumes/download",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ResumeBookQuery
+    ),
+    responses(
+        (status = 200, description = "ZIP archive of resumes", content_type = "application/zip"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor of this hackathon, or tier has no resume book access"),
+        (status = 404, description = "Hackathon not found, or the resume book is disabled"),
+        (status = 503, description = "File storage is not configured")
+    ),
+    tag = "Resumes"
+)]
+pub async fn download_resume_book(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(query): Query<ResumeBookQuery>,
+) -> Result<Response, AppError> {
+    require_resume_book(&state, &role).await?;
+
+    let entries = resume_book(role.hackathon_id, &query)
+        .order_by_asc(users::Column::Name)
+        .all(&state.db)
+        .await?;
+    let storage = state.storage()?.clone();
+
+    let mut files = Vec::with_capacity(entries.len());
+    let mut participant_ids = Vec::with_capacity(entries.len());
+    for (profile, user) in entries {
+        let (Some(key), Some(user)) = (profile.resume_key, user) else {
+            continue;
+        };
+
+        // Prefix with the user ID so participants with the same name do not collide
+        let name = sanitize_file_name(user.name.as_deref().unwrap_or(&user.email));
+        files.push((format!("{}_{}", user.id, name), key));
+        participant_ids.push(user.id);
+    }
+
+    log_access(&state, &role, "bulk_download", participant_ids).await?;
+
+    let (resume_tx, resume_rx) = mpsc::channel(CONCURRENT_READS);
+    tokio::spawn(read_resumes(storage, files, resume_tx));
+
+    let (writer, body) = BodyWriter::new();
+    tokio::task::spawn_blocking(move || write_archive(resume_rx, writer));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}-resumes.zip\"", role.slug),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+type Resume = Result<(String, Bytes), AppError>;
+
+/// Read the resumes in `files`, named by their key, a few at a time and in whatever order
+/// they arrive
+async fn read_resumes(
+    storage: Storage,
+    files: Vec<(String, String)>,
+    resumes: mpsc::Sender<Resume>,
+) {
+    let mut files = files.into_iter();
+    let mut reads = JoinSet::new();
+
+    loop {
+        while reads.len() < CONCURRENT_READS {
+            let Some((name, key)) = files.next() else {
+                break;
+            };
+            let storage = storage.clone();
+            reads.spawn(async move {
+                let content = storage.get(&key).await.map_err(|e| {
+                    AppError::internal(format_args!("Failed to read {key} from storage: {e}"))
+                })?;
+                Ok((name, content))
+            });
+        }
+
+        let Some(read) = reads.join_next().await else {
+            return;
+        };
+        let resume = read.unwrap_or_else(|e| Err(AppError::internal(e)));
+
+        // The client went away, and dropping the reads cancels them
+        if resumes.send(resume).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Zip resumes into the response body as they are read
+fn write_archive(mut resumes: mpsc::Receiver<Resume>, mut body: BodyWriter) {
+    let result = fill_archive(&mut resumes, &mut body);
+    body.finish(result);
+}
+
+fn fill_archive(
+    resumes: &mut mpsc::Receiver<Resume>,
+    body: &mut BodyWriter,
+) -> Result<(), AppError> {
+    // Resumes are already compressed PDFs, so storing them is as good as deflating
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut archive = ZipWriter::new_stream(body);
+
+    while let Some(resume) = resumes.blocking_recv() {
+        let (name, content) = resume?;
+        archive.start_file(name, options)?;
+        archive.write_all(&content)?;
+    }
+
+    archive.finish()?;
+    Ok(())
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ResumeAccess {
+    pub id: i32,
+    pub user_id: i32,
+    pub email: Option<String>,
+    /// The participant whose details or resume were accessed
+    pub participant_id: Option<i32>,
+    /// One of `search`, `download` or `bulk_download`
+    pub action: String,
+    pub created_at: NaiveDateTime,
+}
+
+const ACCESS_SORT: Sort<resume_access_logs::Column> = Sort {
+    fields: &[("created_at", resume_access_logs::Column::CreatedAt)],
+    default: "-created_at",
+    id: resume_access_logs::Column::Id,
+};
+
+/// List every access to the hackathon's resume book
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/resumes/access-log",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Resume book accesses, newest first by default", body = Page<ResumeAccess>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 422, description = "Invalid page, sort or cursor")
+    ),
+    tag = "Resumes"
+)]
+pub async fn get_resume_access_log(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<ResumeAccess>>, AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    let select = ResumeAccessLogs::find()
+        .filter(resume_access_logs::Column::HackathonId.eq(role.hackathon_id))
+        .join(
+            JoinType::LeftJoin,
+            resume_access_logs::Relation::Users1.def(),
+        )
+        .select_also(Users);
+    let page = pagination::paginate(&state.db, select, &page, &ACCESS_SORT, |(log, _)| log).await?;
+
+    Ok(Json(page.map(|logs| {
+        logs.into_iter()
+            .map(|(log, user)| ResumeAccess {
+                id: log.id,
+                user_id: log.user_id,
+                email: user.map(|u| u.email),
+                participant_id: log.participant_id,
+                action: log.action,
+                created_at: log.created_at,
+            })
+            .collect()
+    })))
+}
@@ -0,0 +1,995 @@
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    admins::handlers::find_user_by_email,
+    audit::log::{self as audit, Entry},
+    auth::extractors::HackathonRole,
+    entities::{
+        prelude::*, sponsor_representatives, sponsor_tiers, sponsors, user_hackathon_roles,
+    },
+    error::AppError,
+    hackathons::{handlers::require_feature, phase::HackathonPhase, roles},
+    pagination::{self, Page, PageParams, Sort},
+    validation::{
+        MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, Path, Query, ValidJson, Validate, Validator,
+    },
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct SponsorTierInfo {
+    pub id: i32,
+    pub name: String,
+    /// Tiers are listed by rank, lowest first
+    pub rank: i32,
+    /// Representatives can search and download the resume book
+    pub resume_book: bool,
+    /// Sponsors can run their own prize track
+    pub prize_track: bool,
+    /// Other benefits, shown to sponsors in this tier
+    pub benefits: Option<String>,
+}
+
+impl From<sponsor_tiers::Model> for SponsorTierInfo {
+    fn from(t: sponsor_tiers::Model) -> Self {
+        SponsorTierInfo {
+            id: t.id,
+            name: t.name,
+            rank: t.rank,
+            resume_book: t.resume_book,
+            prize_track: t.prize_track,
+            benefits: t.benefits,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SponsorInfo {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub website: Option<String>,
+    pub logo_url: Option<String>,
+    pub tier: Option<SponsorTierInfo>,
+    /// Only set when the sponsor's tier includes a prize track
+    pub prize_track_name: Option<String>,
+    pub prize_track_description: Option<String>,
+}
+
+impl SponsorInfo {
+    fn new(sponsor: sponsors::Model, tier: Option<sponsor_tiers::Model>) -> Self {
+        let has_prize_track = tier.as_ref().is_some_and(|t| t.prize_track);
+
+        SponsorInfo {
+            id: sponsor.id,
+            name: sponsor.name,
+            description: sponsor.description,
+            website: sponsor.website,
+            logo_url: sponsor.logo_url,
+            tier: tier.map(SponsorTierInfo::from),
+            prize_track_name: sponsor.prize_track_name.filter(|_| has_prize_track),
+            prize_track_description: sponsor.prize_track_description.filter(|_| has_prize_track),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SponsorRepresentativeInfo {
+    pub user_id: i32,
+    pub email: String,
+    pub name: Option<String>,
+    pub added_at: NaiveDateTime,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SponsorDetails {
+    #[serde(flatten)]
+    pub sponsor: SponsorInfo,
+    pub representatives: Vec<SponsorRepresentativeInfo>,
+}
+
+/// Check the hackathon uses the sponsors module
+async fn require_sponsors(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    require_feature(state, role.hackathon_id, |f| f.sponsors).await?;
+    Ok(())
+}
+
+/// Check the caller can manage the hackathon's sponsors, which cannot change once archived
+async fn require_sponsor_manager(state: &AppState, role: &HackathonRole) -> Result<(), AppError> {
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+
+    role.require_phase(&HackathonPhase::NOT_ARCHIVED)?;
+    require_sponsors(state, role).await
+}
+
+/// The sponsor a user represents in a hackathon, with its tier
+pub async fn find_user_sponsor(
+    state: &AppState,
+    hackathon_id: i32,
+    user_id: i32,
+) -> Result<Option<(sponsors::Model, Option<sponsor_tiers::Model>)>, AppError> {
+    let Some(sponsor) = Sponsors::find()
+        .inner_join(SponsorRepresentatives)
+        .filter(sponsors::Column::HackathonId.eq(hackathon_id))
+        .filter(sponsor_representatives::Column::UserId.eq(user_id))
+        .one(&state.db)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let tier = find_tier_by_id(state, sponsor.tier_id).await?;
+    Ok(Some((sponsor, tier)))
+}
+
+/// Whether the caller's sponsor tier includes a benefit. Organizers have every benefit.
+pub async fn has_sponsor_benefit(
+    state: &AppState,
+    role: &HackathonRole,
+    benefit: fn(&sponsor_tiers::Model) -> bool,
+) -> Result<bool, AppError> {
+    if role.is_organizer() {
+        return Ok(true);
+    }
+
+    let sponsor = find_user_sponsor(state, role.hackathon_id, role.user_id).await?;
+    Ok(sponsor
+        .and_then(|(_, tier)| tier)
+        .is_some_and(|t| benefit(&t)))
+}
+
+async fn find_tier_by_id(
+    state: &AppState,
+    tier_id: Option<i32>,
+) -> Result<Option<sponsor_tiers::Model>, AppError> {
+    let Some(tier_id) = tier_id else {
+        return Ok(None);
+    };
+
+    Ok(SponsorTiers::find_by_id(tier_id).one(&state.db).await?)
+}
+
+async fn find_tier(
+    state: &AppState,
+    role: &HackathonRole,
+    tier_id: i32,
+) -> Result<sponsor_tiers::Model, AppError> {
+    SponsorTiers::find_by_id(tier_id)
+        .filter(sponsor_tiers::Column::HackathonId.eq(role.hackathon_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Sponsor tier not found"))
+}
+
+async fn find_sponsor(
+    state: &AppState,
+    role: &HackathonRole,
+    sponsor_id: i32,
+) -> Result<sponsors::Model, AppError> {
+    Sponsors::find_by_id(sponsor_id)
+        .filter(sponsors::Column::HackathonId.eq(role.hackathon_id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Sponsor not found"))
+}
+
+async fn require_free_tier_name(
+    state: &AppState,
+    role: &HackathonRole,
+    name: &str,
+) -> Result<(), AppError> {
+    let existing = SponsorTiers::find()
+        .filter(sponsor_tiers::Column::HackathonId.eq(role.hackathon_id))
+        .filter(sponsor_tiers::Column::Name.eq(name))
+        .one(&state.db)
+        .await?;
+
+    match existing {
+        Some(_) => Err(AppError::Conflict("A tier with this name already exists")),
+        None => Ok(()),
+    }
+}
+
+/// List a hackathon's sponsor tiers
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/sponsor-tiers",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "Sponsor tiers by rank", body = Vec<SponsorTierInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a sponsor or organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or sponsors are disabled")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn list_sponsor_tiers(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SponsorTierInfo>>, AppError> {
+    if !role.is_sponsor() {
+        return Err(AppError::Forbidden);
+    }
+    require_sponsors(&state, &role).await?;
+
+    let tiers = SponsorTiers::find()
+        .filter(sponsor_tiers::Column::HackathonId.eq(role.hackathon_id))
+        .order_by_asc(sponsor_tiers::Column::Rank)
+        .order_by_asc(sponsor_tiers::Column::Name)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(tiers.into_iter().map(SponsorTierInfo::from).collect()))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateSponsorTierRequest {
+    pub name: String,
+    #[serde(default)]
+    pub rank: i32,
+    #[serde(default)]
+    pub resume_book: bool,
+    #[serde(default)]
+    pub prize_track: bool,
+    pub benefits: Option<String>,
+}
+
+impl Validate for CreateSponsorTierRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name, MAX_NAME_LENGTH);
+        v.max_length("benefits", self.benefits.as_deref(), MAX_DESCRIPTION_LENGTH);
+    }
+}
+
+/// Create a sponsor tier
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/sponsor-tiers",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = CreateSponsorTierRequest,
+    responses(
+        (status = 201, description = "Tier created", body = SponsorTierInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or sponsors are disabled"),
+        (status = 409, description = "A tier with this name exists, or the hackathon is archived"),
+        (status = 422, description = "Invalid field")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn create_sponsor_tier(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<CreateSponsorTierRequest>,
+) -> Result<(StatusCode, Json<SponsorTierInfo>), AppError> {
+    require_sponsor_manager(&state, &role).await?;
+
+    let name = req.name.trim();
+    require_free_tier_name(&state, &role, name).await?;
+
+    let txn = state.db.begin().await?;
+    let tier = sponsor_tiers::ActiveModel {
+        hackathon_id: Set(role.hackathon_id),
+        name: Set(name.to_string()),
+        rank: Set(req.rank),
+        resume_book: Set(req.resume_book),
+        prize_track: Set(req.prize_track),
+        benefits: Set(req.benefits),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "sponsor_tier.create", "sponsor_tier", tier.id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(tier.into())))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateSponsorTierRequest {
+    pub name: Option<String>,
+    pub rank: Option<i32>,
+    pub resume_book: Option<bool>,
+    pub prize_track: Option<bool>,
+    /// An empty string clears the benefits
+    pub benefits: Option<String>,
+}
+
+impl Validate for UpdateSponsorTierRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            v.required("name", name, MAX_NAME_LENGTH);
+        }
+        v.max_length("benefits", self.benefits.as_deref(), MAX_DESCRIPTION_LENGTH);
+    }
+}
+
+/// Update a sponsor tier
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}/sponsor-tiers/{tier_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("tier_id" = i32, Path, description = "Tier ID")
+    ),
+    request_body = UpdateSponsorTierRequest,
+    responses(
+        (status = 200, description = "Tier updated", body = SponsorTierInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Tier not found, or sponsors are disabled"),
+        (status = 409, description = "A tier with this name exists, or the hackathon is archived"),
+        (status = 422, description = "Invalid field")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn update_sponsor_tier(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<UpdateSponsorTierRequest>,
+) -> Result<Json<SponsorTierInfo>, AppError> {
+    let (_, tier_id) = params;
+    require_sponsor_manager(&state, &role).await?;
+
+    let tier = find_tier(&state, &role, tier_id).await?;
+    let mut active: sponsor_tiers::ActiveModel = tier.clone().into();
+
+    if let Some(name) = req.name {
+        let name = name.trim();
+        if name != tier.name {
+            require_free_tier_name(&state, &role, name).await?;
+        }
+        active.name = Set(name.to_string());
+    }
+    if let Some(rank) = req.rank {
+        active.rank = Set(rank);
+    }
+    if let Some(resume_book) = req.resume_book {
+        active.resume_book = Set(resume_book);
+    }
+    if let Some(prize_track) = req.prize_track {
+        active.prize_track = Set(prize_track);
+    }
+    if let Some(benefits) = req.benefits {
+        active.benefits = Set(Some(benefits).filter(|b| !b.trim().is_empty()));
+    }
+
+    let txn = state.db.begin().await?;
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        Entry {
+            diff: audit::diff(&tier, &updated),
+            ..Entry::in_hackathon(&role, "sponsor_tier.update", "sponsor_tier", updated.id)
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Delete a sponsor tier, leaving its sponsors without a tier
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/sponsor-tiers/{tier_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("tier_id" = i32, Path, description = "Tier ID")
+    ),
+    responses(
+        (status = 204, description = "Tier deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Tier not found, or sponsors are disabled"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn delete_sponsor_tier(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, tier_id) = params;
+    require_sponsor_manager(&state, &role).await?;
+
+    let tier = find_tier(&state, &role, tier_id).await?;
+    let txn = state.db.begin().await?;
+    SponsorTiers::delete_by_id(tier.id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        Entry {
+            diff: Some(json!({ "name": tier.name })),
+            ..Entry::in_hackathon(&role, "sponsor_tier.delete", "sponsor_tier", tier.id)
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+const SPONSOR_SORT: Sort<sponsors::Column> = Sort {
+    fields: &[
+        ("name", sponsors::Column::Name),
+        ("created_at", sponsors::Column::CreatedAt),
+    ],
+    default: "name",
+    id: sponsors::Column::Id,
+};
+
+/// List a hackathon's sponsors
+///
+/// Each sponsor includes its tier, so clients can group them by tier. Can be sorted by `name`
+/// or `created_at`.
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/sponsors",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Sponsors, by name by default", body = Page<SponsorInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "No access to this hackathon"),
+        (status = 404, description = "Hackathon not found, or sponsors are disabled"),
+        (status = 422, description = "Invalid page, sort or cursor")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn list_sponsors(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<SponsorInfo>>, AppError> {
+    require_sponsors(&state, &role).await?;
+
+    let select = Sponsors::find()
+        .find_also_related(SponsorTiers)
+        .filter(sponsors::Column::HackathonId.eq(role.hackathon_id));
+    let page = pagination::paginate(&state.db, select, &page, &SPONSOR_SORT, |(sponsor, _)| {
+        sponsor
+    })
+    .await?;
+
+    Ok(Json(page.map(|sponsors| {
+        sponsors
+            .into_iter()
+            .map(|(sponsor, tier)| SponsorInfo::new(sponsor, tier))
+            .collect()
+    })))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SponsorRequest {
+    pub name: Option<String>,
+    /// An ID from the hackathon's sponsor tiers
+    pub tier_id: Option<i32>,
+    pub description: Option<String>,
+    pub website: Option<String>,
+    pub logo_url: Option<String>,
+    /// Requires a tier that includes a prize track
+    pub prize_track_name: Option<String>,
+    pub prize_track_description: Option<String>,
+}
+
+impl Validate for SponsorRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            v.required("name", name, MAX_NAME_LENGTH);
+        }
+        v.max_length(
+            "prize_track_name",
+            self.prize_track_name.as_deref(),
+            MAX_NAME_LENGTH,
+        );
+
+        for (field, text) in [
+            ("description", &self.description),
+            ("prize_track_description", &self.prize_track_description),
+        ] {
+            v.max_length(field, text.as_deref(), MAX_DESCRIPTION_LENGTH);
+        }
+
+        for (field, url) in [("website", &self.website), ("logo_url", &self.logo_url)] {
+            if let Some(url) = url.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+                v.url(field, url);
+            }
+        }
+    }
+}
+
+impl SponsorRequest {
+    /// Apply the fields that were given, where an empty string clears a field
+    async fn apply(
+        self,
+        state: &AppState,
+        role: &HackathonRole,
+        sponsor: &mut sponsors::ActiveModel,
+    ) -> Result<(), AppError> {
+        if let Some(tier_id) = self.tier_id {
+            find_tier(state, role, tier_id).await.map_err(|e| match e {
+                AppError::NotFound(_) => AppError::invalid("tier_id", "Unknown sponsor tier"),
+                e => e,
+            })?;
+            sponsor.tier_id = Set(Some(tier_id));
+        }
+
+        if let Some(name) = self.name {
+            sponsor.name = Set(name.trim().to_string());
+        }
+
+        for (column, value) in [
+            (&mut sponsor.description, self.description),
+            (&mut sponsor.website, self.website),
+            (&mut sponsor.logo_url, self.logo_url),
+            (&mut sponsor.prize_track_name, self.prize_track_name),
+            (
+                &mut sponsor.prize_track_description,
+                self.prize_track_description,
+            ),
+        ] {
+            if let Some(value) = value {
+                let value = value.trim();
+                *column = Set((!value.is_empty()).then(|| value.to_string()));
+            }
+        }
+
+        // Only tiers with a prize track can have one
+        let has_prize_track = sponsor
+            .prize_track_name
+            .try_as_ref()
+            .is_some_and(|n| n.is_some());
+        if has_prize_track {
+            let tier_id = sponsor.tier_id.try_as_ref().copied().flatten();
+            let tier = find_tier_by_id(state, tier_id).await?;
+            if !tier.is_some_and(|t| t.prize_track) {
+                return Err(AppError::invalid(
+                    "prize_track_name",
+                    "The sponsor's tier does not include a prize track",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Add a sponsor company to a hackathon
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/sponsors",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    request_body = SponsorRequest,
+    responses(
+        (status = 201, description = "Sponsor created", body = SponsorInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Hackathon not found, or sponsors are disabled"),
+        (status = 409, description = "Hackathon is archived"),
+        (status = 422, description = "Invalid field, unknown tier, or tier has no prize track")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn create_sponsor(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<SponsorRequest>,
+) -> Result<(StatusCode, Json<SponsorInfo>), AppError> {
+    require_sponsor_manager(&state, &role).await?;
+
+    if req.name.is_none() {
+        return Err(AppError::invalid("name", "Required"));
+    }
+
+    let mut sponsor = sponsors::ActiveModel {
+        hackathon_id: Set(role.hackathon_id),
+        tier_id: Set(None),
+        description: Set(None),
+        website: Set(None),
+        logo_url: Set(None),
+        prize_track_name: Set(None),
+        prize_track_description: Set(None),
+        ..Default::default()
+    };
+    req.apply(&state, &role, &mut sponsor).await?;
+
+    let txn = state.db.begin().await?;
+    let sponsor = sponsor.insert(&txn).await?;
+    audit::record(
+        &txn,
+        Entry::in_hackathon(&role, "sponsor.create", "sponsor", sponsor.id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    let tier = find_tier_by_id(&state, sponsor.tier_id).await?;
+
+    Ok((StatusCode::CREATED, Json(SponsorInfo::new(sponsor, tier))))
+}
+
+async fn sponsor_details(
+    state: &AppState,
+    sponsor: sponsors::Model,
+) -> Result<SponsorDetails, AppError> {
+    let representatives = SponsorRepresentatives::find()
+        .find_also_related(Users)
+        .filter(sponsor_representatives::Column::SponsorId.eq(sponsor.id))
+        .order_by_asc(sponsor_representatives::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    let tier = find_tier_by_id(state, sponsor.tier_id).await?;
+
+    Ok(SponsorDetails {
+        sponsor: SponsorInfo::new(sponsor, tier),
+        representatives: representatives
+            .into_iter()
+            .filter_map(|(rep, user)| {
+                let user = user?;
+                Some(SponsorRepresentativeInfo {
+                    user_id: user.id,
+                    email: user.email,
+                    name: user.name,
+                    added_at: rep.created_at,
+                })
+            })
+            .collect(),
+    })
+}
+
+/// Get the sponsor the current user represents, with its tier benefits
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/sponsors/me",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug")
+    ),
+    responses(
+        (status = 200, description = "The user's sponsor", body = SponsorDetails),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "No access to this hackathon"),
+        (status = 404, description = "Not a sponsor representative, or sponsors are disabled")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn get_my_sponsor(
+    role: HackathonRole,
+    State(state): State<AppState>,
+) -> Result<Json<SponsorDetails>, AppError> {
+    require_sponsors(&state, &role).await?;
+
+    let (sponsor, _) = find_user_sponsor(&state, role.hackathon_id, role.user_id)
+        .await?
+        .ok_or(AppError::NotFound("You do not represent a sponsor"))?;
+
+    Ok(Json(sponsor_details(&state, sponsor).await?))
+}
+
+/// Get a sponsor with its representatives
+#[utoipa::path(
+    get,
+    path = "/hackathons/{slug}/sponsors/{sponsor_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("sponsor_id" = i32, Path, description = "Sponsor ID")
+    ),
+    responses(
+        (status = 200, description = "The sponsor", body = SponsorDetails),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Sponsor not found, or sponsors are disabled")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn get_sponsor(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<Json<SponsorDetails>, AppError> {
+    let (_, sponsor_id) = params;
+
+    if !role.is_organizer() {
+        return Err(AppError::Forbidden);
+    }
+    require_sponsors(&state, &role).await?;
+
+    let sponsor = find_sponsor(&state, &role, sponsor_id).await?;
+    Ok(Json(sponsor_details(&state, sponsor).await?))
+}
+
+/// Update a sponsor
+#[utoipa::path(
+    patch,
+    path = "/hackathons/{slug}/sponsors/{sponsor_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("sponsor_id" = i32, Path, description = "Sponsor ID")
+    ),
+    request_body = SponsorRequest,
+    responses(
+        (status = 200, description = "Sponsor updated", body = SponsorInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Sponsor not found, or sponsors are disabled"),
+        (status = 409, description = "Hackathon is archived"),
+        (status = 422, description = "Invalid field, unknown tier, or tier has no prize track")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn update_sponsor(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<SponsorRequest>,
+) -> Result<Json<SponsorInfo>, AppError> {
+    let (_, sponsor_id) = params;
+    require_sponsor_manager(&state, &role).await?;
+
+    let sponsor = find_sponsor(&state, &role, sponsor_id).await?;
+    let mut active: sponsors::ActiveModel = sponsor.clone().into();
+    req.apply(&state, &role, &mut active).await?;
+    active.updated_at = Set(Utc::now().naive_utc());
+
+    let txn = state.db.begin().await?;
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        Entry {
+            diff: audit::diff(&sponsor, &updated),
+            ..Entry::in_hackathon(&role, "sponsor.update", "sponsor", updated.id)
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    let tier = find_tier_by_id(&state, updated.tier_id).await?;
+
+    Ok(Json(SponsorInfo::new(updated, tier)))
+}
+
+/// Remove a sponsor and the sponsor role from its representatives
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/sponsors/{sponsor_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("sponsor_id" = i32, Path, description = "Sponsor ID")
+    ),
+    responses(
+        (status = 204, description = "Sponsor deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Sponsor not found, or sponsors are disabled"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn delete_sponsor(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, sponsor_id) = params;
+    require_sponsor_manager(&state, &role).await?;
+
+    let sponsor = find_sponsor(&state, &role, sponsor_id).await?;
+    let representative_ids: Vec<i32> = SponsorRepresentatives::find()
+        .filter(sponsor_representatives::Column::SponsorId.eq(sponsor.id))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|r| r.user_id)
+        .collect();
+
+    let txn = state.db.begin().await?;
+
+    UserHackathonRoles::delete_many()
+        .filter(user_hackathon_roles::Column::HackathonId.eq(role.hackathon_id))
+        .filter(user_hackathon_roles::Column::UserId.is_in(representative_ids))
+        .filter(user_hackathon_roles::Column::Role.eq("sponsor"))
+        .exec(&txn)
+        .await?;
+
+    Sponsors::delete_by_id(sponsor.id).exec(&txn).await?;
+
+    audit::record(
+        &txn,
+        Entry {
+            diff: Some(json!({ "name": sponsor.name })),
+            ..Entry::in_hackathon(&role, "sponsor.delete", "sponsor", sponsor.id)
+        },
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddSponsorRepresentativeRequest {
+    /// Email of an existing user
+    pub email: String,
+}
+
+impl Validate for AddSponsorRepresentativeRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", self.email.trim());
+    }
+}
+
+/// Link a user to a sponsor, giving them the sponsor role in the hackathon
+#[utoipa::path(
+    post,
+    path = "/hackathons/{slug}/sponsors/{sponsor_id}/representatives",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("sponsor_id" = i32, Path, description = "Sponsor ID")
+    ),
+    request_body = AddSponsorRepresentativeRequest,
+    responses(
+        (status = 200, description = "User now represents the sponsor", body = SponsorRepresentativeInfo),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Sponsor not found, sponsors are disabled, or no user with that email"),
+        (status = 409, description = "Several users have that email, user has another role or represents another sponsor, or the hackathon is archived"),
+        (status = 422, description = "Not an email address")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn add_sponsor_representative(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32)>,
+    ValidJson(req): ValidJson<AddSponsorRepresentativeRequest>,
+) -> Result<Json<SponsorRepresentativeInfo>, AppError> {
+    let (_, sponsor_id) = params;
+    require_sponsor_manager(&state, &role).await?;
+
+    let sponsor = find_sponsor(&state, &role, sponsor_id).await?;
+    let user = find_user_by_email(&state, &req.email).await?;
+
+    if let Some((current, _)) = find_user_sponsor(&state, role.hackathon_id, user.id).await? {
+        if current.id != sponsor.id {
+            return Err(AppError::Conflict(
+                "User already represents another sponsor",
+            ));
+        }
+
+        let representative = SponsorRepresentatives::find()
+            .filter(sponsor_representatives::Column::SponsorId.eq(sponsor.id))
+            .filter(sponsor_representatives::Column::UserId.eq(user.id))
+            .one(&state.db)
+            .await?
+            .ok_or_else(|| {
+                AppError::internal(format_args!(
+                    "User {} has no representative row for sponsor {}",
+                    user.id, sponsor.id
+                ))
+            })?;
+
+        return Ok(Json(SponsorRepresentativeInfo {
+            user_id: user.id,
+            email: user.email,
+            name: user.name,
+            added_at: representative.created_at,
+        }));
+    }
+
+    // Representatives cannot also be participants or judges in the same hackathon
+    let txn = state.db.begin().await?;
+    roles::assign(&txn, role.hackathon_id, user.id, "sponsor").await?;
+
+    let representative = sponsor_representatives::ActiveModel {
+        sponsor_id: Set(sponsor.id),
+        user_id: Set(user.id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    audit::record(
+        &txn,
+        Entry {
+            diff: Some(json!({ "sponsor": sponsor.id })),
+            ..Entry::in_hackathon(&role, "sponsor_representative.add", "user", user.id)
+        },
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    tracing::info!(
+        "Added {} as a representative of sponsor {} in {}",
+        user.email,
+        sponsor.name,
+        role.slug
+    );
+
+    Ok(Json(SponsorRepresentativeInfo {
+        user_id: user.id,
+        email: user.email,
+        name: user.name,
+        added_at: representative.created_at,
+    }))
+}
+
+/// Unlink a user from a sponsor and remove their sponsor role in the hackathon
+#[utoipa::path(
+    delete,
+    path = "/hackathons/{slug}/sponsors/{sponsor_id}/representatives/{user_id}",
+    params(
+        ("slug" = String, Path, description = "Hackathon slug"),
+        ("sponsor_id" = i32, Path, description = "Sponsor ID"),
+        ("user_id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Representative removed"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an organizer of this hackathon"),
+        (status = 404, description = "Sponsor not found, or user does not represent it"),
+        (status = 409, description = "Hackathon is archived")
+    ),
+    tag = "Sponsors"
+)]
+pub async fn remove_sponsor_representative(
+    role: HackathonRole,
+    State(state): State<AppState>,
+    Path(params): Path<(String, i32, i32)>,
+) -> Result<StatusCode, AppError> {
+    let (_, sponsor_id, user_id) = params;
+    require_sponsor_manager(&state, &role).await?;
+
+    let sponsor = find_sponsor(&state, &role, sponsor_id).await?;
+
+    let txn = state.db.begin().await?;
+
+    let result = SponsorRepresentatives::delete_many()
+        .filter(sponsor_representatives::Column::SponsorId.eq(sponsor.id))
+        .filter(sponsor_representatives::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("User does not represent this sponsor"));
+    }
+
+    roles::unassign(&txn, role.hackathon_id, user_id, "sponsor").await?;
+
+    audit::record(
+        &txn,
+        Entry {
+            diff: Some(json!({ "sponsor": sponsor.id })),
+            ..Entry::in_hackathon(&role, "sponsor_representative.remove", "user", user_id)
+        },
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
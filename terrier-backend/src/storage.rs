@@ -0,0 +1,124 @@
+//! Uploaded files, kept in an S3-compatible bucket rather than the database.
+
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use minio::s3::{
+    builders::ObjectToDelete,
+    client::{Client, ClientBuilder},
+    creds::StaticProvider,
+    http::BaseUrl,
+    types::S3Api,
+};
+
+use tokio::sync::OnceCell;
+
+use crate::{AppState, config::StorageConfig, error::AppError};
+
+pub type StorageError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Clone)]
+pub struct Storage {
+    client: Client,
+    bucket: String,
+    /// Set once the bucket is known to exist
+    ready: Arc<OnceCell<()>>,
+}
+
+impl Storage {
+    /// Set up a client for the bucket without contacting it, so the server can start while
+    /// storage is unreachable
+    pub fn connect(config: &StorageConfig) -> Result<Self, StorageError> {
+        let provider = StaticProvider::new(&config.access_key, &config.secret_key, None);
+        let client = ClientBuilder::new(config.endpoint.parse::<BaseUrl>()?)
+            .provider(Some(Box::new(provider)))
+            .build()?;
+
+        Ok(Storage {
+            client,
+            bucket: config.bucket.clone(),
+            ready: Arc::default(),
+        })
+    }
+
+    /// Create the bucket on first use if this is a fresh deployment
+    async fn bucket(&self) -> Result<&str, StorageError> {
+        self.ready
+            .get_or_try_init(|| async {
+                if !self.client.bucket_exists(&self.bucket).send().await?.exists {
+                    self.client.create_bucket(&self.bucket).send().await?;
+                    tracing::info!("Created storage bucket {}", self.bucket);
+                }
+                Ok::<_, StorageError>(())
+            })
+            .await?;
+
+        Ok(&self.bucket)
+    }
+
+    pub async fn put(
+        &self,
+        key: &str,
+        content: Bytes,
+        content_type: &str,
+    ) -> Result<(), StorageError> {
+        let bucket = self.bucket().await?;
+        self.client
+            .put_object_content(bucket, key, content)
+            .content_type(content_type.to_string())
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Bytes, StorageError> {
+        let bucket = self.bucket().await?;
+        let object = self.client.get_object(bucket, key).send().await?;
+        Ok(object.content.to_segmented_bytes().await?.to_bytes())
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let bucket = self.bucket().await?;
+        self.client
+            .delete_object(bucket, ObjectToDelete::from(key))
+            .send()
+            .await?;
+        Ok(())
+    }
+}
+
+impl AppState {
+    /// The file storage, which deployments without a bucket do not have
+    pub fn storage(&self) -> Result<&Storage, AppError> {
+        self.storage.as_ref().ok_or(AppError::ServiceUnavailable(
+            "File storage is not configured",
+        ))
+    }
+}
+
+/// Keep an uploaded file's name safe to put in a `Content-Disposition` header or a ZIP archive
+pub fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(100)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sanitize_file_name;
+
+    #[test]
+    fn file_names_keep_only_safe_characters() {
+        assert_eq!(sanitize_file_name("Résumé 2025.pdf"), "Résumé_2025.pdf");
+        assert_eq!(sanitize_file_name("../\"x\";.png"), "..__x__.png");
+        assert_eq!(sanitize_file_name(&"a".repeat(150)).len(), 100);
+    }
+}
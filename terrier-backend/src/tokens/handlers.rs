@@ -0,0 +1,249 @@
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::{Duration, NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    auth::{
+        extractors::{AuthUser, can_manage_organization},
+        tokens::{self, TokenScope},
+    },
+    entities::{api_tokens, hackathons, prelude::*, user_hackathon_roles},
+    error::AppError,
+    validation::{MAX_NAME_LENGTH, Path, ValidJson, Validate, Validator},
+};
+
+const DEFAULT_EXPIRY_DAYS: i64 = 30;
+const MAX_EXPIRY_DAYS: i64 = 365;
+
+#[derive(Serialize, ToSchema)]
+pub struct TokenInfo {
+    pub id: i32,
+    pub name: String,
+    /// First characters of the token, for telling tokens apart
+    pub prefix: String,
+    pub hackathon_slug: Option<String>,
+    pub scopes: Vec<TokenScope>,
+    pub expires_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TokenInfo {
+    fn from_model(token: api_tokens::Model, hackathon_slug: Option<String>) -> Self {
+        TokenInfo {
+            id: token.id,
+            name: token.name,
+            prefix: token.token_prefix,
+            hackathon_slug,
+            scopes: tokens::parse_scopes(&token.scopes),
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+            created_at: token.created_at,
+        }
+    }
+}
+
+/// Tokens cannot be used to manage other tokens, so a leaked token cannot mint new ones
+fn require_session(auth: &AuthUser) -> Result<(), AppError> {
+    if auth.token.is_some() {
+        Err(AppError::Forbidden)
+    } else {
+        Ok(())
+    }
+}
+
+/// List the current user's API tokens
+#[utoipa::path(
+    get,
+    path = "/auth/tokens",
+    responses(
+        (status = 200, description = "The current user's API tokens", body = Vec<TokenInfo>),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Tokens cannot manage tokens"),
+    ),
+    tag = "Authentication"
+)]
+pub async fn list_tokens(
+    auth: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<TokenInfo>>, AppError> {
+    require_session(&auth)?;
+
+    let tokens = ApiTokens::find()
+        .filter(api_tokens::Column::UserId.eq(auth.user.id))
+        .order_by_desc(api_tokens::Column::CreatedAt)
+        .find_also_related(Hackathons)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(
+        tokens
+            .into_iter()
+            .map(|(token, hackathon)| TokenInfo::from_model(token, hackathon.map(|h| h.slug)))
+            .collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateTokenRequest {
+    pub name: String,
+    /// Restrict the token to a single hackathon
+    pub hackathon_slug: Option<String>,
+    pub scopes: Vec<TokenScope>,
+    /// Days until the token expires (default 30, max 365)
+    pub expires_in_days: Option<i64>,
+}
+
+impl Validate for CreateTokenRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name, MAX_NAME_LENGTH);
+        if self.scopes.is_empty() {
+            v.error("scopes", "At least one scope is required");
+        }
+        if let Some(days) = self.expires_in_days {
+            v.range("expires_in_days", days, 1..=MAX_EXPIRY_DAYS);
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CreatedTokenResponse {
+    /// The raw token, which is only ever returned once
+    pub token: String,
+    pub info: TokenInfo,
+}
+
+/// Create a new API token
+#[utoipa::path(
+    post,
+    path = "/auth/tokens",
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 201, description = "Token created", body = CreatedTokenResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Scope or hackathon not available to this user"),
+        (status = 404, description = "Hackathon not found"),
+        (status = 422, description = "Invalid name, scopes or expiry"),
+    ),
+    tag = "Authentication"
+)]
+pub async fn create_token(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    ValidJson(req): ValidJson<CreateTokenRequest>,
+) -> Result<(StatusCode, Json<CreatedTokenResponse>), AppError> {
+    require_session(&auth)?;
+
+    let name = req.name.trim();
+    let expires_in_days = req.expires_in_days.unwrap_or(DEFAULT_EXPIRY_DAYS);
+
+    let is_global_admin = auth.is_global_admin();
+    if req.scopes.contains(&TokenScope::Admin) && !is_global_admin {
+        return Err(AppError::Forbidden);
+    }
+
+    let hackathon = match &req.hackathon_slug {
+        Some(slug) => {
+            let hackathon = Hackathons::find()
+                .filter(hackathons::Column::Slug.eq(slug))
+                .one(&state.db)
+                .await?
+                .ok_or(AppError::NotFound("Hackathon not found"))?;
+
+            // Users can only scope tokens to hackathons they have access to, either through a
+            // role or by managing the organization that owns the hackathon
+            if !is_global_admin {
+                let has_role = UserHackathonRoles::find()
+                    .filter(user_hackathon_roles::Column::UserId.eq(auth.user.id))
+                    .filter(user_hackathon_roles::Column::HackathonId.eq(hackathon.id))
+                    .one(&state.db)
+                    .await?
+                    .is_some();
+
+                let manages_organization = match hackathon.organization_id {
+                    Some(organization_id) => {
+                        can_manage_organization(&state, &auth, organization_id).await?
+                    }
+                    None => false,
+                };
+
+                if !has_role && !manages_organization {
+                    return Err(AppError::Forbidden);
+                }
+            }
+
+            Some(hackathon)
+        }
+        None => None,
+    };
+
+    let (raw_token, prefix) = tokens::generate_token();
+    let mut scopes = req.scopes;
+    scopes.sort();
+    scopes.dedup();
+
+    let token = api_tokens::ActiveModel {
+        user_id: Set(auth.user.id),
+        hackathon_id: Set(hackathon.as_ref().map(|h| h.id)),
+        name: Set(name.to_string()),
+        token_hash: Set(tokens::hash_token(&raw_token)),
+        token_prefix: Set(prefix),
+        scopes: Set(tokens::format_scopes(&scopes)),
+        expires_at: Set(Utc::now().naive_utc() + Duration::days(expires_in_days)),
+        ..Default::default()
+    };
+
+    let result = token.insert(&state.db).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedTokenResponse {
+            token: raw_token,
+            info: TokenInfo::from_model(result, hackathon.map(|h| h.slug)),
+        }),
+    ))
+}
+
+/// Revoke one of the current user's API tokens
+#[utoipa::path(
+    delete,
+    path = "/auth/tokens/{id}",
+    params(
+        ("id" = i32, Path, description = "Token ID")
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Tokens cannot manage tokens"),
+        (status = 404, description = "Token not found"),
+    ),
+    tag = "Authentication"
+)]
+pub async fn revoke_token(
+    auth: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    require_session(&auth)?;
+
+    let token = ApiTokens::find_by_id(id)
+        .filter(api_tokens::Column::UserId.eq(auth.user.id))
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::NotFound("Token not found"))?;
+
+    if token.revoked_at.is_none() {
+        let mut token: api_tokens::ActiveModel = token.into();
+        token.revoked_at = Set(Some(Utc::now().naive_utc()));
+        token.update(&state.db).await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,410 @@
+//! Checks on request bodies, which report every invalid field at once.
+
+use std::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use axum::{
+    Json,
+    extract::{
+        FromRequest, FromRequestParts, Request,
+        path::ErrorKind,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+    },
+    http::request::Parts,
+};
+use chrono::NaiveDateTime;
+use openidconnect::url::Url;
+use serde::de::DeserializeOwned;
+
+use crate::error::{AppError, FieldError};
+
+/// Format of dates sent by the frontend, as produced by `Date.toISOString()`
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
+const DATE_HINT: &str = "Expected a date like 2025-01-31T09:00:00.000Z";
+
+pub const MAX_NAME_LENGTH: usize = 200;
+pub const MAX_DESCRIPTION_LENGTH: usize = 5000;
+pub const MAX_URL_LENGTH: usize = 2048;
+
+const MIN_SLUG_LENGTH: usize = 3;
+const MAX_SLUG_LENGTH: usize = 64;
+
+/// Slugs that would clash with routes next to `/{slug}`, here or in the frontend
+const RESERVED_SLUGS: [&str; 10] = [
+    "admin", "admins", "api", "auth", "login", "logout", "new", "public", "settings", "static",
+];
+
+/// A request body that can check its own fields
+pub trait Validate {
+    /// Record every problem with the request, leaving checks that need the database to the
+    /// handler
+    fn validate(&self, _v: &mut Validator) {}
+}
+
+/// Free-form JSON, such as a merge patch, which is checked once it has been applied
+impl Validate for serde_json::Value {}
+
+#[derive(Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn error(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(FieldError::new(field, message));
+    }
+
+    /// Require text that is not blank and at most `max` characters long
+    pub fn required(&mut self, field: &str, value: &str, max: usize) {
+        if value.trim().is_empty() {
+            self.error(field, "Required");
+        } else {
+            self.max_length(field, Some(value), max);
+        }
+    }
+
+    pub fn max_length(&mut self, field: &str, value: Option<&str>, max: usize) {
+        if value.is_some_and(|v| v.chars().count() > max) {
+            self.error(field, format!("Must be at most {max} characters"));
+        }
+    }
+
+    pub fn slug(&mut self, field: &str, slug: &str) {
+        let length = slug.len();
+        if !slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            self.error(
+                field,
+                "Can only contain lowercase letters, digits and hyphens",
+            );
+        } else if slug.starts_with('-') || slug.ends_with('-') || slug.contains("--") {
+            self.error(field, "Hyphens must separate letters or digits");
+        } else if !(MIN_SLUG_LENGTH..=MAX_SLUG_LENGTH).contains(&length) {
+            self.error(
+                field,
+                format!("Must be {MIN_SLUG_LENGTH} to {MAX_SLUG_LENGTH} characters"),
+            );
+        } else if RESERVED_SLUGS.contains(&slug) {
+            self.error(field, "This slug is reserved");
+        }
+    }
+
+    /// Require an absolute `http` or `https` URL
+    pub fn url(&mut self, field: &str, url: &str) {
+        if url.len() > MAX_URL_LENGTH || !is_web_url(url) {
+            self.error(field, "Must be a link starting with http:// or https://");
+        }
+    }
+
+    pub fn email(&mut self, field: &str, email: &str) {
+        if email.len() > MAX_NAME_LENGTH || !is_email(email) {
+            self.error(field, "Must be an email address");
+        }
+    }
+
+    /// Parse a date in [`DATE_FORMAT`], returning `None` if it is invalid
+    pub fn date(&mut self, field: &str, date: &str) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(date, DATE_FORMAT)
+            .inspect_err(|_| self.error(field, DATE_HINT))
+            .ok()
+    }
+
+    /// Require `date` to be no earlier than the date in `other_field`
+    pub fn not_before(
+        &mut self,
+        field: &str,
+        date: NaiveDateTime,
+        other_field: &str,
+        other: NaiveDateTime,
+    ) {
+        if date < other {
+            self.error(field, format!("Must not be before {other_field}"));
+        }
+    }
+
+    pub fn range<T: PartialOrd + Display>(
+        &mut self,
+        field: &str,
+        value: T,
+        range: RangeInclusive<T>,
+    ) {
+        if !range.contains(&value) {
+            self.error(
+                field,
+                format!("Must be from {} to {}", range.start(), range.end()),
+            );
+        }
+    }
+
+    pub fn at_least<T: PartialOrd + Display>(&mut self, field: &str, value: T, min: T) {
+        if value < min {
+            self.error(field, format!("Must be at least {min}"));
+        }
+    }
+
+    pub fn one_of(&mut self, field: &str, value: &str, options: &[&str]) {
+        if !options.contains(&value) {
+            self.error(field, format!("Must be one of {}", options.join(", ")));
+        }
+    }
+
+    pub fn finish(self) -> Result<(), AppError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(self.errors))
+        }
+    }
+}
+
+/// Run every check on `value`
+pub fn check<T: Validate>(value: &T) -> Result<(), AppError> {
+    let mut v = Validator::default();
+    value.validate(&mut v);
+    v.finish()
+}
+
+pub fn parse_date(field: &str, date: &str) -> Result<NaiveDateTime, AppError> {
+    NaiveDateTime::parse_from_str(date, DATE_FORMAT)
+        .map_err(|_| AppError::invalid(field, DATE_HINT))
+}
+
+fn is_web_url(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+fn is_email(email: &str) -> bool {
+    email
+        .split_once('@')
+        .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'))
+        && !email.contains(char::is_whitespace)
+}
+
+/// JSON request body that is rejected with field-level errors unless it passes [`Validate`]
+pub struct ValidJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(rejection_error)?;
+
+        check(&value)?;
+        Ok(ValidJson(value))
+    }
+}
+
+/// Query string that is rejected with field-level errors when it cannot be parsed
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Query(value)) => Ok(Query(value)),
+            Err(QueryRejection::FailedToDeserializeQueryString(error)) => {
+                let detail = error.source().map(ToString::to_string).unwrap_or_default();
+                Err(AppError::Validation(vec![field_error(&detail, "query")]))
+            }
+            Err(_) => Err(AppError::BadRequest("Could not read the query string")),
+        }
+    }
+}
+
+/// Path parameters that are rejected with field-level errors when they cannot be parsed
+pub struct Path<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Path(value)),
+            Err(PathRejection::FailedToDeserializePathParams(error)) => match error.kind() {
+                ErrorKind::ParseErrorAtKey {
+                    key,
+                    value,
+                    expected_type,
+                } => Err(AppError::invalid(
+                    key.as_str(),
+                    format!("`{value}` is not a valid {expected_type}"),
+                )),
+                // Parameters extracted as a tuple or a single value have no name
+                ErrorKind::ParseErrorAtIndex {
+                    value,
+                    expected_type,
+                    ..
+                }
+                | ErrorKind::ParseError {
+                    value,
+                    expected_type,
+                } => Err(AppError::invalid(
+                    "path",
+                    format!("`{value}` is not a valid {expected_type}"),
+                )),
+                ErrorKind::DeserializeError { key, message, .. } => {
+                    Err(AppError::invalid(key.as_str(), message.as_str()))
+                }
+                ErrorKind::InvalidUtf8InPathParam { key } => {
+                    Err(AppError::invalid(key.as_str(), "Must be valid UTF-8"))
+                }
+                _ => Err(AppError::BadRequest("Invalid path parameter")),
+            },
+            // Only happens if the extractor is used on a route without parameters
+            Err(error) => Err(AppError::internal(error.body_text())),
+        }
+    }
+}
+
+/// Turn a serde error into an error on the field it names, or on `fallback`
+fn field_error(detail: &str, fallback: &str) -> FieldError {
+    // Either prefixed with the path of the field, as in `tier_id: invalid type: ...`, or a
+    // missing field at the top level, as in "missing field `name` at line 1"
+    let missing = detail
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split_once('`'));
+
+    match (detail.split_once(": "), missing) {
+        (Some((field, message)), _) if !field.contains(' ') => FieldError::new(field, message),
+        (_, Some((field, _))) => FieldError::new(field, "Required"),
+        _ => FieldError::new(fallback, detail),
+    }
+}
+
+fn rejection_error(rejection: JsonRejection) -> AppError {
+    match rejection {
+        JsonRejection::JsonDataError(error) => {
+            let detail = error.source().map(ToString::to_string).unwrap_or_default();
+            AppError::Validation(vec![field_error(&detail, "body")])
+        }
+        JsonRejection::JsonSyntaxError(_) => AppError::BadRequest("The body is not valid JSON"),
+        JsonRejection::MissingJsonContentType(_) => {
+            AppError::UnsupportedMediaType("Expected a JSON body")
+        }
+        _ => AppError::BadRequest("Could not read the request body"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Messages recorded by `check`
+    fn errors(check: impl FnOnce(&mut Validator)) -> Vec<String> {
+        let mut v = Validator::default();
+        check(&mut v);
+        v.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn valid_slugs() {
+        for slug in ["abc", "hack-2025", "a1b2c3", &"a".repeat(MAX_SLUG_LENGTH)] {
+            assert!(errors(|v| v.slug("slug", slug)).is_empty(), "{slug}");
+        }
+    }
+
+    #[test]
+    fn invalid_slugs() {
+        for (slug, message) in [
+            (
+                "Hack",
+                "Can only contain lowercase letters, digits and hyphens",
+            ),
+            (
+                "hack_2025",
+                "Can only contain lowercase letters, digits and hyphens",
+            ),
+            (
+                "héllo",
+                "Can only contain lowercase letters, digits and hyphens",
+            ),
+            ("-hack", "Hyphens must separate letters or digits"),
+            ("hack-", "Hyphens must separate letters or digits"),
+            ("ha--ck", "Hyphens must separate letters or digits"),
+            ("ab", "Must be 3 to 64 characters"),
+            (
+                &"a".repeat(MAX_SLUG_LENGTH + 1),
+                "Must be 3 to 64 characters",
+            ),
+            ("admin", "This slug is reserved"),
+            ("new", "This slug is reserved"),
+        ] {
+            assert_eq!(errors(|v| v.slug("slug", slug)), [message], "{slug}");
+        }
+    }
+
+    #[test]
+    fn dates_must_match_the_frontend_format() {
+        let mut v = Validator::default();
+        let date = v.date("start_date", "2025-01-31T09:00:00.000Z");
+        assert_eq!(date.unwrap().to_string(), "2025-01-31 09:00:00");
+
+        for date in [
+            "2025-01-31",
+            "2025-01-31T09:00:00",
+            "2025-02-30T09:00:00.000Z",
+        ] {
+            assert_eq!(
+                errors(|v| assert!(v.date("date", date).is_none())),
+                [DATE_HINT]
+            );
+        }
+    }
+
+    #[test]
+    fn dates_must_be_in_order() {
+        let start = parse_date("start_date", "2025-01-31T09:00:00.000Z").unwrap();
+        let end = parse_date("end_date", "2025-02-01T09:00:00.000Z").unwrap();
+
+        assert!(errors(|v| v.not_before("end_date", end, "start_date", start)).is_empty());
+        assert!(errors(|v| v.not_before("end_date", start, "start_date", start)).is_empty());
+        assert_eq!(
+            errors(|v| v.not_before("end_date", start, "start_date", end)),
+            ["Must not be before start_date"]
+        );
+    }
+
+    #[test]
+    fn emails() {
+        for email in ["a@b.co", "first.last+tag@cs.example.edu"] {
+            assert!(errors(|v| v.email("email", email)).is_empty(), "{email}");
+        }
+        for email in ["", "a", "@b.co", "a@b", "a b@c.co", "a@b.co "] {
+            assert_eq!(
+                errors(|v| v.email("email", email)),
+                ["Must be an email address"],
+                "{email}"
+            );
+        }
+    }
+
+    #[test]
+    fn finish_reports_every_field() {
+        let mut v = Validator::default();
+        v.required("name", " ", MAX_NAME_LENGTH);
+        v.url("website", "ftp://example.com");
+        v.range("rating", 6, 1..=5);
+
+        let Err(AppError::Validation(fields)) = v.finish() else {
+            panic!("expected a validation error");
+        };
+        let fields: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, ["name", "website", "rating"]);
+    }
+}
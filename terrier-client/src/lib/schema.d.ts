@@ -79,7 +79,7 @@ export interface paths {
 			path?: never;
 			cookie?: never;
 		};
-		/** List all active hackathons */
+		/** List published hackathons */
 		get: operations["list_public_hackathons"];
 		put?: never;
 		post?: never;
@@ -131,6 +131,16 @@ export interface components {
 		LoginQuery: {
 			redirect_uri?: string | null;
 		};
+		Page_HackathonInfo: {
+			items: components["schemas"]["HackathonInfo"][];
+			/** @description Pass as `cursor` to get the next page, missing on the last page */
+			next_cursor?: string | null;
+			/**
+			 * Format: int64
+			 * @description Number of items matching the filters, across every page
+			 */
+			total: number;
+		};
 		UserInfo: {
 			email: string;
 			id: string;
@@ -263,20 +273,35 @@ export interface operations {
 	};
 	list_public_hackathons: {
 		parameters: {
-			query?: never;
+			query?: {
+				/** @description Only list hackathons owned by the organization with this slug */
+				organization?: string | null;
+				/** @description Only list hackathons whose name contains this text, ignoring case */
+				search?: string | null;
+				/** @description Only list hackathons that have not ended by this time */
+				ends_after?: string | null;
+				/** @description Number of items to return, from 1 to 200 (default 50) */
+				limit?: number | null;
+				/** @description `next_cursor` of the previous page */
+				cursor?: string | null;
+				/** @description Number of items to skip, which cannot be combined with `cursor` */
+				offset?: number | null;
+				/** @description Field to sort by, prefixed with `-` for descending order */
+				sort?: string | null;
+			};
 			header?: never;
 			path?: never;
 			cookie?: never;
 		};
 		requestBody?: never;
 		responses: {
-			/** @description List of active hackathons */
+			/** @description Page of published hackathons */
 			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					"application/json": components["schemas"]["HackathonInfo"][];
+					"application/json": components["schemas"]["Page_HackathonInfo"];
 				};
 			};
 		};